@@ -0,0 +1,40 @@
+//! Compares the uncontended set / wait round trip of a [`FastEvent`] to an [`Event`].
+//!
+//! Run with `cargo bench --bench fast_event`.
+
+#[cfg(windows)]
+fn main() {
+    use {
+        minievent::{Event, FastEvent, Waitable},
+        std::time::{Duration, Instant},
+    };
+
+    const NUM_ITERATIONS: u32 = 1_000_000;
+
+    let event = Event::new_auto(false, None).unwrap();
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        event.set().unwrap();
+        event.wait(Duration::from_secs(1_000_000)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!("Event:     {:?} / set + wait", elapsed / NUM_ITERATIONS);
+
+    let event = FastEvent::new_auto(false);
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        event.set();
+        event.wait(Duration::from_secs(1_000_000)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!("FastEvent: {:?} / set + wait", elapsed / NUM_ITERATIONS);
+}
+
+#[cfg(not(windows))]
+fn main() {}
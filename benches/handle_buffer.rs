@@ -0,0 +1,51 @@
+//! Compares repeated [`wait_for_one`] calls to [`wait_for_one_with_buf`] calls reusing the same handle buffer
+//! over a small set of events, where the per-call overhead of collecting the handles dominates.
+//!
+//! Run with `cargo bench --bench handle_buffer`.
+
+#[cfg(windows)]
+fn main() {
+    use {
+        minievent::{wait_for_one, wait_for_one_with_buf, Event, WaitableExt},
+        std::{
+            ptr,
+            time::{Duration, Instant},
+        },
+    };
+
+    const NUM_ITERATIONS: u32 = 1_000_000;
+
+    // Only the last event is signaled, so every wait checks the whole set.
+    let e0 = Event::new_manual(false, None).unwrap();
+    let e1 = Event::new_manual(true, None).unwrap();
+    let waitables = [&e0 as &dyn WaitableExt, &e1 as _];
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        wait_for_one(&waitables, Duration::from_secs(0)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!(
+        "wait_for_one:          {:?} / wait",
+        elapsed / NUM_ITERATIONS
+    );
+
+    let mut buf = [ptr::null_mut(); 2];
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        wait_for_one_with_buf(&waitables, Duration::from_secs(0), &mut buf).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!(
+        "wait_for_one_with_buf: {:?} / wait",
+        elapsed / NUM_ITERATIONS
+    );
+}
+
+#[cfg(not(windows))]
+fn main() {}
@@ -0,0 +1,57 @@
+//! Compares repeated [`wait_for_one`] / [`wait_for_one_typed`] calls to a [`ReusableWaitSet`]
+//! over the same set of events.
+//!
+//! Run with `cargo bench --bench wait_set`.
+
+#[cfg(windows)]
+fn main() {
+    use {
+        minievent::{wait_for_one, wait_for_one_typed, Event, ReusableWaitSet},
+        std::time::{Duration, Instant},
+    };
+
+    const NUM_EVENTS: usize = 16;
+    const NUM_ITERATIONS: u32 = 100_000;
+
+    // Only the last event is signaled, so every wait checks the whole set.
+    let events: Vec<_> = (0..NUM_EVENTS)
+        .map(|idx| Event::new_manual(idx == NUM_EVENTS - 1, None).unwrap())
+        .collect();
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        let waitables: Vec<_> = events.iter().map(|e| e as _).collect();
+        wait_for_one(&waitables, Duration::from_secs(1_000_000)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!("wait_for_one:       {:?} / wait", elapsed / NUM_ITERATIONS);
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        wait_for_one_typed(&events, Duration::from_secs(1_000_000)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!("wait_for_one_typed: {:?} / wait", elapsed / NUM_ITERATIONS);
+
+    let mut set = ReusableWaitSet::with_capacity(NUM_EVENTS);
+
+    for e in events.iter() {
+        set.push(e);
+    }
+
+    let now = Instant::now();
+
+    for _ in 0..NUM_ITERATIONS {
+        set.wait_one(Duration::from_secs(1_000_000)).unwrap();
+    }
+
+    let elapsed = now.elapsed();
+    println!("ReusableWaitSet:    {:?} / wait", elapsed / NUM_ITERATIONS);
+}
+
+#[cfg(not(windows))]
+fn main() {}
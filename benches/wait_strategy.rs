@@ -0,0 +1,84 @@
+//! Compares the [`WaitStrategy`] variants for a signal which is already set when the wait begins,
+//! and for a signal set by another thread (a ping-pong between two threads over a pair of auto events).
+//!
+//! Run with `cargo bench --bench wait_strategy`.
+
+#[cfg(windows)]
+fn main() {
+    use {
+        minievent::{Event, WaitStrategy, Waitable},
+        std::{
+            sync::Arc,
+            thread,
+            time::{Duration, Instant},
+        },
+    };
+
+    const NUM_ITERATIONS: u32 = 100_000;
+
+    let strategies = [
+        ("Blocking:     ", WaitStrategy::Blocking),
+        (
+            "SpinThenBlock:",
+            WaitStrategy::SpinThenBlock { spin: 1_000 },
+        ),
+        ("Yielding:     ", WaitStrategy::Yielding),
+    ];
+
+    let event = |strategy| {
+        Event::new_auto(false, None)
+            .unwrap()
+            .with_wait_strategy(strategy)
+            .unwrap()
+    };
+
+    println!("Already set:");
+
+    for (name, strategy) in strategies.iter() {
+        let e = event(*strategy);
+
+        let now = Instant::now();
+
+        for _ in 0..NUM_ITERATIONS {
+            e.set().unwrap();
+            e.wait(Duration::from_secs(1_000_000)).unwrap();
+        }
+
+        let elapsed = now.elapsed();
+        println!("{} {:?} / wait", name, elapsed / NUM_ITERATIONS);
+    }
+
+    println!("Set by another thread:");
+
+    for (name, strategy) in strategies.iter() {
+        let ping = Arc::new(event(*strategy));
+        let pong = Arc::new(event(*strategy));
+
+        let t = {
+            let ping = ping.clone();
+            let pong = pong.clone();
+
+            thread::spawn(move || {
+                for _ in 0..NUM_ITERATIONS {
+                    ping.wait_infinite().unwrap();
+                    pong.set().unwrap();
+                }
+            })
+        };
+
+        let now = Instant::now();
+
+        for _ in 0..NUM_ITERATIONS {
+            ping.set().unwrap();
+            pong.wait_infinite().unwrap();
+        }
+
+        let elapsed = now.elapsed();
+        println!("{} {:?} / round trip", name, elapsed / NUM_ITERATIONS);
+
+        t.join().unwrap();
+    }
+}
+
+#[cfg(not(windows))]
+fn main() {}
@@ -0,0 +1,125 @@
+use {
+    crate::{
+        wait_for_one_sequential, WaitError, Waitable, WaitableResult, WaitablesResult, INFINITE_MS,
+    },
+    std::time::Duration,
+};
+
+/// Composite waitable which is signaled when any of its member waitables is signaled.
+///
+/// Allows a set of waitables to be passed around and waited on as a single object,
+/// including as a member of another waitable set / [`AnyOf`].
+///
+/// Waits on the members via [`wait_for_one_sequential`], so has no limit on the number of members,
+/// but may oversleep by up to a few milliseconds.
+/// Like [`wait_for_one`], waiting consumes the signal of at most one member - the one with the lowest index.
+///
+/// NOTE - does not implement `WaitableExt`, as there's no single OS object to return the handle of,
+/// so may only be waited on via its [`Waitable`] methods or [`wait_for_one_sequential`] / [`wait_for_all_sequential`].
+///
+/// [`AnyOf`]: struct.AnyOf.html
+/// [`Waitable`]: trait.Waitable.html
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_for_one_sequential`]: fn.wait_for_one_sequential.html
+/// [`wait_for_all_sequential`]: fn.wait_for_all_sequential.html
+pub struct AnyOf {
+    waitables: Vec<Box<dyn Waitable>>,
+}
+
+impl AnyOf {
+    /// Creates a new composite waitable over the member `waitables`.
+    pub fn new(waitables: Vec<Box<dyn Waitable>>) -> Self {
+        Self { waitables }
+    }
+
+    /// Blocks the thread until at least one of the member waitables is signaled or the duration `d` expires.
+    /// Returned index corresponds to the order of the member waitables passed to [`new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting on any of the member waitables fails, or if there are no member waitables.
+    ///
+    /// [`new`]: #method.new
+    pub fn wait_one(&self, d: Duration) -> Result<WaitablesResult, WaitError> {
+        let waitables: Vec<_> = self.waitables.iter().map(Box::as_ref).collect();
+
+        wait_for_one_sequential(&waitables, d)
+    }
+
+    /// Returns the number of member waitables.
+    pub fn len(&self) -> usize {
+        self.waitables.len()
+    }
+
+    /// Returns `true` if there are no member waitables.
+    pub fn is_empty(&self) -> bool {
+        self.waitables.is_empty()
+    }
+}
+
+impl Waitable for AnyOf {
+    /// Blocks the thread until at least one of the member waitables is signaled or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting on any of the member waitables fails, or if there are no member waitables.
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        match self.wait_one(d)? {
+            WaitablesResult::Timeout => Ok(WaitableResult::Timeout),
+            WaitablesResult::Abandoned(_) => Ok(WaitableResult::Abandoned),
+            WaitablesResult::IoCompletion => Ok(WaitableResult::IoCompletion),
+            _ => Ok(WaitableResult::Signaled),
+        }
+    }
+
+    /// Blocks the thread until at least one of the member waitables is signaled
+    /// or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting on any of the member waitables fails, or if there are no member waitables.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait(if ms == INFINITE_MS {
+            Duration::from_secs(u64::MAX)
+        } else {
+            Duration::from_millis(ms.into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Event};
+
+    #[test]
+    fn nested() {
+        let e0 = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+        let inner = AnyOf::new(vec![
+            Box::new(Event::new_auto(false, None).unwrap()), // Not signaled.
+            Box::new(Event::new_auto(true, None).unwrap()),  // Signaled.
+        ]);
+        assert_eq!(inner.len(), 2);
+
+        let w: [&dyn Waitable; 2] = [&e0, &inner];
+
+        let res = wait_for_one_sequential(&w, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        // The signal was consumed.
+
+        let res = wait_for_one_sequential(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        e0.set().unwrap();
+
+        let res = wait_for_one_sequential(&w, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+
+        // Empty.
+
+        let empty = AnyOf::new(Vec::new());
+        assert!(empty.is_empty());
+        empty.wait(Duration::from_millis(1)).err().unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use {
+    crate::{Semaphore, SemaphoreError, WaitError, Waitable, WaitableResult},
+    std::{
+        sync::{Mutex, MutexGuard},
+        time::Duration,
+    },
+};
+
+/// Reusable barrier - blocks `count` threads until all of them have arrived, then releases them all.
+///
+/// Unlike `std::sync::Barrier`, is implemented over the crate's [`Semaphore`]s, so a thread may [`arrive`]
+/// and then wait for the rest of the threads alongside other waitables, e.g. via [`wait_for_one`].
+///
+/// Each round (generation) is released via its own semaphore, alternating between two,
+/// so a fast thread which arrives for the next round can't consume a release meant for the previous one.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+/// [`arrive`]: #method.arrive
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub struct Barrier {
+    count: usize,
+    /// Indexed by the generation parity.
+    semaphores: [Semaphore; 2],
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Number of threads which arrived in the current generation.
+    num_arrived: usize,
+    generation: usize,
+}
+
+/// Result of waiting on a [`Barrier`] via [`wait`](struct.Barrier.html#method.wait).
+///
+/// [`Barrier`]: struct.Barrier.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Returns `true` if the caller was the last thread to arrive at the barrier in its round.
+    /// Exactly one thread per round is the leader.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
+
+/// A thread's arrival at a [`Barrier`], returned by [`arrive`].
+///
+/// Signaled once all threads have arrived in its round.
+///
+/// NOTE - must be waited on until signaled exactly once - each arrival consumes one of its round's releases.
+///
+/// [`Barrier`]: struct.Barrier.html
+/// [`arrive`]: struct.Barrier.html#method.arrive
+#[must_use]
+#[derive(Debug)]
+pub struct BarrierArrival<'b> {
+    semaphore: &'b Semaphore,
+    is_leader: bool,
+}
+
+impl<'b> BarrierArrival<'b> {
+    /// Returns `true` if the caller was the last thread to arrive at the barrier in its round -
+    /// i.e. the arrival is already signaled.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
+
+impl Barrier {
+    /// Creates a new barrier which releases the threads once `count` of them have arrived.
+    /// A `count` of `0` is treated as `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS semaphore creation failed.
+    pub fn new(count: usize) -> Result<Barrier, SemaphoreError> {
+        let count = count.max(1);
+
+        Ok(Barrier {
+            count,
+            semaphores: [
+                Semaphore::new(0, count, None)?,
+                Semaphore::new(0, count, None)?,
+            ],
+            state: Mutex::new(State {
+                num_arrived: 0,
+                generation: 0,
+            }),
+        })
+    }
+
+    /// Blocks the thread until all `count` threads have arrived at the barrier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn wait(&self) -> Result<BarrierWaitResult, SemaphoreError> {
+        let arrival = self.arrive()?;
+
+        arrival
+            .wait_infinite()
+            .map_err(|err| SemaphoreError::FailedToWait(err.into()))?;
+
+        Ok(BarrierWaitResult {
+            is_leader: arrival.is_leader,
+        })
+    }
+
+    /// Arrives at the barrier without blocking.
+    ///
+    /// The returned [`BarrierArrival`] must then be waited on until signaled -
+    /// e.g. alongside other waitables via [`wait_for_one`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`BarrierArrival`]: struct.BarrierArrival.html
+    /// [`wait_for_one`]: fn.wait_for_one.html
+    pub fn arrive(&self) -> Result<BarrierArrival<'_>, SemaphoreError> {
+        let mut state = self.state();
+
+        let semaphore = &self.semaphores[state.generation % 2];
+        let is_leader = state.num_arrived + 1 == self.count;
+
+        if is_leader {
+            // Releases all threads of this round, including the leader itself.
+            semaphore.increment(self.count)?;
+
+            state.num_arrived = 0;
+            state.generation = state.generation.wrapping_add(1);
+        } else {
+            state.num_arrived += 1;
+        }
+
+        Ok(BarrierArrival {
+            semaphore,
+            is_leader,
+        })
+    }
+
+    /// Returns the number of threads the barrier waits for.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // Never propagate a poisoned lock - the state is always valid.
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<'b> Waitable for BarrierArrival<'b> {
+    /// Blocks the thread until all threads have arrived in the arrival's round or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.semaphore.wait(d)
+    }
+
+    /// Blocks the thread until all threads have arrived in the arrival's round or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.semaphore.wait_raw_ms(ms)
+    }
+}
+
+#[cfg(windows)]
+impl<'b> crate::WaitableExt for BarrierArrival<'b> {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.semaphore.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{wait_for_one, wait_for_one_infinite, Event, WaitablesResult},
+        std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            thread,
+        },
+    };
+
+    #[test]
+    fn rounds() {
+        const N: usize = 4;
+        const NUM_ROUNDS: usize = 100;
+
+        let barrier = Arc::new(Barrier::new(N).unwrap());
+        let num_arrived = Arc::new(AtomicUsize::new(0));
+        let num_leaders = Arc::new(AtomicUsize::new(0));
+
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let barrier = barrier.clone();
+                let num_arrived = num_arrived.clone();
+                let num_leaders = num_leaders.clone();
+
+                thread::spawn(move || {
+                    for round in 0..NUM_ROUNDS {
+                        num_arrived.fetch_add(1, Ordering::SeqCst);
+
+                        if barrier.wait().unwrap().is_leader() {
+                            num_leaders.fetch_add(1, Ordering::SeqCst);
+                        }
+
+                        // No thread slipped through before all threads of this round arrived.
+                        assert!(num_arrived.load(Ordering::SeqCst) >= (round + 1) * N);
+                    }
+                })
+            })
+            .collect();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(num_leaders.load(Ordering::SeqCst), NUM_ROUNDS);
+    }
+
+    #[test]
+    fn arrive() {
+        let barrier = Arc::new(Barrier::new(2).unwrap());
+        let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+        let arrival = barrier.arrive().unwrap();
+        assert!(!arrival.is_leader());
+
+        let res = wait_for_one(&[&e, &arrival], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        let t = {
+            let barrier = barrier.clone();
+            thread::spawn(move || barrier.wait().unwrap())
+        };
+
+        let res = wait_for_one_infinite(&[&e, &arrival]).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        assert!(t.join().unwrap().is_leader());
+    }
+}
@@ -0,0 +1,201 @@
+use {
+    crate::{Event, EventError, WaitError, Waitable, WaitableResult},
+    std::{
+        sync::{Mutex, MutexGuard},
+        time::Duration,
+    },
+};
+
+/// Fork-join countdown event - a manual event which becomes signaled when its count reaches zero.
+///
+/// Starts with the count passed to [`new`]; each [`signal`] decrements the count, [`add`] increments it.
+/// Waiting threads wake up once the count reaches zero, e.g. when all of the forked workers have [`signal`]ed completion.
+///
+/// [`new`]: #method.new
+/// [`signal`]: #method.signal
+/// [`add`]: #method.add
+pub struct CountdownEvent {
+    event: Event,
+    /// Changed together with the event state, so [`signal`](#method.signal) / [`add`](#method.add) don't race.
+    count: Mutex<usize>,
+}
+
+impl CountdownEvent {
+    /// Creates a new countdown event with the initial `count`.
+    /// The event is signaled if `count` is `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed.
+    pub fn new(count: usize) -> Result<CountdownEvent, EventError> {
+        Ok(CountdownEvent {
+            event: Event::new_manual(count == 0, None)?,
+            count: Mutex::new(count),
+        })
+    }
+
+    /// Decrements the count, [`set`]ting the event if it reaches zero.
+    ///
+    /// Returns `true` if this call brought the count to zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count is already zero.
+    ///
+    /// [`set`]: struct.Event.html#method.set
+    pub fn signal(&self) -> Result<bool, EventError> {
+        let mut count = self.count();
+
+        *count = count
+            .checked_sub(1)
+            .expect("the countdown event was signaled more times than its count");
+
+        if *count == 0 {
+            self.event.set()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Increments the count by `n`.
+    ///
+    /// If the count was zero, [`reset`]s the event, so it may be reused for another fork-join round.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`reset`]: struct.Event.html#method.reset
+    pub fn add(&self, n: usize) -> Result<(), EventError> {
+        if n == 0 {
+            return Ok(());
+        }
+
+        let mut count = self.count();
+
+        if *count == 0 {
+            self.event.reset()?;
+        }
+
+        *count += n;
+
+        Ok(())
+    }
+
+    /// Returns the current count.
+    ///
+    /// NOTE - the value is a racy snapshot: by the time this method returns,
+    /// other threads may have already changed the count.
+    pub fn current_count(&self) -> usize {
+        *self.count()
+    }
+
+    fn count(&self) -> MutexGuard<'_, usize> {
+        // Never propagate a poisoned lock - the count is always valid.
+        self.count.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Waitable for CountdownEvent {
+    /// Blocks the thread until the count reaches zero or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.event.wait(d)
+    }
+
+    /// Blocks the thread until the count reaches zero or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.event.wait_raw_ms(ms)
+    }
+}
+
+#[cfg(windows)]
+impl crate::WaitableExt for CountdownEvent {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.event.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            thread,
+        },
+    };
+
+    #[test]
+    fn fork_join() {
+        const N: usize = 8;
+
+        let countdown = Arc::new(CountdownEvent::new(N).unwrap());
+        let num_done = Arc::new(AtomicUsize::new(0));
+
+        let res = countdown.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let threads: Vec<_> = (0..N)
+            .map(|idx| {
+                let countdown = countdown.clone();
+                let num_done = num_done.clone();
+
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(10 * idx as u64));
+                    num_done.fetch_add(1, Ordering::SeqCst);
+                    countdown.signal().unwrap()
+                })
+            })
+            .collect();
+
+        countdown.wait_infinite().unwrap();
+
+        // All workers are done.
+        assert_eq!(num_done.load(Ordering::SeqCst), N);
+        assert_eq!(countdown.current_count(), 0);
+
+        // Exactly one worker brought the count to zero.
+        let num_last = threads
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .filter(|&last| last)
+            .count();
+        assert_eq!(num_last, 1);
+
+        // Re-armed.
+        countdown.add(2).unwrap();
+
+        let res = countdown.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        assert!(!countdown.signal().unwrap());
+        assert!(countdown.signal().unwrap());
+
+        let res = countdown.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+
+    #[test]
+    #[should_panic]
+    fn signal_past_zero() {
+        let countdown = CountdownEvent::new(0).unwrap();
+        let _ = countdown.signal();
+    }
+}
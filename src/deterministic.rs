@@ -0,0 +1,149 @@
+//! Injectable scheduler hooks for deterministic testing of concurrent code.
+//!
+//! Only compiled in with the `deterministic` feature.
+//! Every [`Event`] / [`Semaphore`] calls into its [`Scheduler`] when it is waited on / signaled,
+//! which allows tests to control e.g. the order in which threads woken up by an auto event
+//! or a semaphore proceed - normally up to the OS and not reproducible.
+//!
+//! Objects use the pass-through [`OsScheduler`] unless given a different one via
+//! `Event::with_scheduler` / `Semaphore::with_scheduler`.
+//!
+//! NOTE - only [`Waitable`] methods and the objects' own wait methods call the scheduler;
+//! waits on multiple waitables (e.g. [`wait_for_one`]) go directly to the OS.
+//!
+//! [`Event`]: ../struct.Event.html
+//! [`Semaphore`]: ../struct.Semaphore.html
+//! [`Scheduler`]: trait.Scheduler.html
+//! [`OsScheduler`]: struct.OsScheduler.html
+//! [`Waitable`]: ../trait.Waitable.html
+//! [`wait_for_one`]: ../fn.wait_for_one.html
+
+use std::sync::Arc;
+
+/// Hooks called by the waitables around their OS operations.
+///
+/// `object` is the raw handle / pointer to the waitable's OS object.
+/// On Linux it is the `eventfd` of the auto reset event, the address of the manual reset event's flag,
+/// or the pointer to the POSIX semaphore.
+/// All methods do nothing by default and may block the calling thread.
+pub trait Scheduler: Send + Sync {
+    /// Called by the thread before it waits on the `object`.
+    fn before_wait(&self, _object: *mut ()) {}
+
+    /// Called by the thread after it was woken up by the `object` being signaled,
+    /// before the wait returns.
+    fn after_wake(&self, _object: *mut ()) {}
+
+    /// Called by the thread before it signals the `object` - i.e. sets the event / increments the semaphore.
+    fn before_signal(&self, _object: *mut ()) {}
+}
+
+/// Default pass-through scheduler - the OS alone determines the order of operations.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct OsScheduler;
+
+impl Scheduler for OsScheduler {}
+
+pub(crate) fn os_scheduler() -> Arc<dyn Scheduler> {
+    Arc::new(OsScheduler)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Semaphore, Waitable},
+        std::{
+            collections::VecDeque,
+            sync::{Condvar, Mutex},
+            thread,
+        },
+    };
+
+    /// Lets the threads woken up by a waitable proceed one at a time, in the order of their names in the script.
+    /// A woken up thread's turn ends when it next signals any object using the scheduler.
+    struct ScriptedScheduler {
+        script: Mutex<VecDeque<&'static str>>,
+        turn: Condvar,
+    }
+
+    impl ScriptedScheduler {
+        fn new(script: &[&'static str]) -> Self {
+            Self {
+                script: Mutex::new(script.iter().copied().collect()),
+                turn: Condvar::new(),
+            }
+        }
+
+        fn is_my_turn(script: &VecDeque<&'static str>) -> bool {
+            script.front().copied() == thread::current().name()
+        }
+    }
+
+    impl Scheduler for ScriptedScheduler {
+        fn after_wake(&self, _: *mut ()) {
+            let script = self.script.lock().unwrap();
+
+            let _script = self
+                .turn
+                .wait_while(script, |script| !Self::is_my_turn(script))
+                .unwrap();
+        }
+
+        fn before_signal(&self, _: *mut ()) {
+            let mut script = self.script.lock().unwrap();
+
+            if Self::is_my_turn(&script) {
+                script.pop_front();
+                self.turn.notify_all();
+            }
+        }
+    }
+
+    #[test]
+    fn scripted_wakeup_order() {
+        let scheduler: Arc<dyn Scheduler> = Arc::new(ScriptedScheduler::new(&["c", "a", "b"]));
+
+        let s = Arc::new(
+            Semaphore::new(0, 3, None)
+                .unwrap()
+                .with_scheduler(scheduler.clone()),
+        );
+        let done = Arc::new(
+            Semaphore::new(0, 3, None)
+                .unwrap()
+                .with_scheduler(scheduler.clone()),
+        );
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let threads: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let s = s.clone();
+                let done = done.clone();
+                let order = order.clone();
+
+                thread::Builder::new()
+                    .name(name.to_string())
+                    .spawn(move || {
+                        s.wait_infinite().unwrap();
+                        order
+                            .lock()
+                            .unwrap()
+                            .push(thread::current().name().unwrap().to_owned());
+                        done.increment_one().unwrap(); // Ends the thread's turn.
+                    })
+                    .unwrap()
+            })
+            .collect();
+
+        // Whichever threads the OS wakes up, they proceed in the scripted order.
+        s.increment(3).unwrap();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), vec!["c", "a", "b"]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Registry of all live waitables created by the crate.
+//!
+//! Only compiled in with the `diagnostics` feature.
+//! Every [`Event`] / [`Semaphore`] registers itself on creation and deregisters when dropped,
+//! so [`dump`] may be used to find leaked or forgotten objects in a large application.
+//!
+//! [`Event`]: ../struct.Event.html
+//! [`Semaphore`]: ../struct.Semaphore.html
+//! [`dump`]: fn.dump.html
+
+use std::{
+    backtrace::Backtrace,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Kind of the registered waitable object.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ObjectKind {
+    /// An auto reset [`Event`](../struct.Event.html).
+    AutoEvent,
+    /// A manual reset [`Event`](../struct.Event.html).
+    ManualEvent,
+    /// A [`Semaphore`](../struct.Semaphore.html).
+    Semaphore,
+}
+
+/// Snapshot of a single live waitable object.
+#[derive(Clone, Debug)]
+pub struct ObjectInfo {
+    /// Raw handle / pointer to the waitable's OS object.
+    pub handle: usize,
+    /// Name of the object, if it was created with one.
+    pub name: Option<String>,
+    /// Kind of the object.
+    pub kind: ObjectKind,
+    /// Backtrace captured when the object was created.
+    pub backtrace: Arc<Backtrace>,
+}
+
+static REGISTRY: Mutex<Vec<ObjectInfo>> = Mutex::new(Vec::new());
+
+/// Returns a snapshot of all currently live waitables created by the crate,
+/// in order of creation.
+pub fn dump() -> Vec<ObjectInfo> {
+    registry().clone()
+}
+
+pub(crate) fn register(handle: *mut (), name: Option<&str>, kind: ObjectKind) {
+    registry().push(ObjectInfo {
+        handle: handle as usize,
+        name: name.map(str::to_owned),
+        kind,
+        backtrace: Arc::new(Backtrace::force_capture()),
+    });
+}
+
+pub(crate) fn deregister(handle: *mut ()) {
+    let handle = handle as usize;
+    registry().retain(|info| info.handle != handle);
+}
+
+/// Returns the name the live object `handle` was registered with, if any.
+#[cfg(windows)]
+pub(crate) fn name_of(handle: *mut ()) -> Option<String> {
+    let handle = handle as usize;
+
+    registry()
+        .iter()
+        .find(|info| info.handle == handle)
+        .and_then(|info| info.name.clone())
+}
+
+fn registry() -> MutexGuard<'static, Vec<ObjectInfo>> {
+    // Registration happens in `Drop`, so never propagate a poisoned lock.
+    REGISTRY.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+// Named objects are only supported on Windows - see the Linux backends for their registration tests.
+#[cfg(all(test, windows))]
+mod tests {
+    use {
+        super::*,
+        crate::{Event, Semaphore},
+    };
+
+    fn find(name: &str) -> Option<ObjectInfo> {
+        dump()
+            .into_iter()
+            .find(|info| info.name.as_deref() == Some(name))
+    }
+
+    #[test]
+    fn dump_live_objects() {
+        let auto = Event::new_auto(false, "minievent_diagnostics_auto").unwrap();
+        let manual = Event::new_manual(false, "minievent_diagnostics_manual").unwrap();
+        let semaphore = Semaphore::new(0, 1, Some("minievent_diagnostics_semaphore")).unwrap();
+
+        let info = find("minievent_diagnostics_auto").unwrap();
+        assert_eq!(info.kind, ObjectKind::AutoEvent);
+
+        let info = find("minievent_diagnostics_manual").unwrap();
+        assert_eq!(info.kind, ObjectKind::ManualEvent);
+
+        let info = find("minievent_diagnostics_semaphore").unwrap();
+        assert_eq!(info.kind, ObjectKind::Semaphore);
+
+        drop(auto);
+        drop(manual);
+        drop(semaphore);
+
+        assert!(find("minievent_diagnostics_auto").is_none());
+        assert!(find("minievent_diagnostics_manual").is_none());
+        assert!(find("minievent_diagnostics_semaphore").is_none());
+    }
+}
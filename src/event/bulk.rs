@@ -0,0 +1,67 @@
+use {
+    crate::{Event, EventError},
+    std::convert::TryInto,
+};
+
+impl Event {
+    /// Creates `N` new unnamed auto reset events, e.g. per-worker wakeup events of a thread pool.
+    ///
+    /// `set` - gives the initial state of all the events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the OS event creations failed.
+    /// The events already created by then are closed before the error is returned.
+    pub fn new_auto_array<const N: usize>(set: bool) -> Result<[Event; N], EventError> {
+        Ok(Self::new_auto_vec(N, set)?
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("created exactly `N` events")))
+    }
+
+    /// Creates `n` new unnamed auto reset events, e.g. per-worker wakeup events of a thread pool.
+    ///
+    /// `set` - gives the initial state of all the events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the OS event creations failed.
+    /// The events already created by then are closed before the error is returned.
+    pub fn new_auto_vec(n: usize, set: bool) -> Result<Vec<Event>, EventError> {
+        // Stops at the first error, dropping (i.e. closing) the events collected so far.
+        (0..n).map(|_| Event::new_auto(set, None)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+        std::time::Duration,
+    };
+
+    #[test]
+    fn new_auto_array() {
+        let events = Event::new_auto_array::<4>(true).unwrap(); // Signaled.
+
+        for e in events.iter() {
+            assert!(!e.is_manual());
+
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Timeout);
+        }
+
+        let events = Event::new_auto_vec(3, false).unwrap(); // Not signaled.
+        assert_eq!(events.len(), 3);
+
+        for e in events.iter() {
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Timeout);
+        }
+
+        assert!(Event::new_auto_array::<0>(false).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use {
+    crate::waitable::close_handle,
+    std::{
+        fmt::{Debug, Formatter},
+        io,
+        panic::{self, AssertUnwindSafe},
+        ptr,
+        sync::Mutex,
+    },
+    winapi::{
+        shared::{
+            minwindef::FALSE,
+            ntdef::{BOOLEAN, PVOID},
+        },
+        um::{
+            handleapi::INVALID_HANDLE_VALUE,
+            threadpoollegacyapiset::UnregisterWaitEx,
+            winbase::{RegisterWaitForSingleObject, INFINITE},
+            winnt::{HANDLE, WT_EXECUTEDEFAULT},
+        },
+    },
+};
+
+type Callback = Mutex<Box<dyn FnMut() + Send>>;
+
+/// A callback registered on the OS thread pool via [`Event::register_callback`].
+///
+/// Unregisters the wait when dropped, blocking until the callback completes if it is currently running,
+/// so the callback is never called after the registration is dropped.
+///
+/// NOTE - must not be dropped from the registered callback itself, as that would never complete.
+///
+/// [`Event::register_callback`]: struct.Event.html#method.register_callback
+pub struct WaitRegistration {
+    wait_handle: HANDLE,
+    /// Duplicate of the event handle, so the registered wait outlives the [`Event`](struct.Event.html) if necessary.
+    handle: HANDLE,
+    callback: *mut Callback,
+}
+
+impl WaitRegistration {
+    /// Registers the `callback` to be called on the OS thread pool every time the object `handle` is signaled.
+    /// Takes ownership of the `handle`, closing it on error.
+    pub(crate) fn new(
+        handle: HANDLE,
+        callback: Box<dyn FnMut() + Send>,
+    ) -> io::Result<WaitRegistration> {
+        let callback: *mut Callback = Box::into_raw(Box::new(Mutex::new(callback)));
+
+        let mut wait_handle = ptr::null_mut();
+
+        let result = unsafe {
+            RegisterWaitForSingleObject(
+                &mut wait_handle,
+                handle,
+                Some(wait_callback),
+                callback as PVOID,
+                INFINITE,
+                WT_EXECUTEDEFAULT,
+            )
+        };
+
+        if result == FALSE {
+            let err = io::Error::last_os_error();
+
+            unsafe {
+                drop(Box::from_raw(callback));
+            }
+
+            let _ = close_handle(handle);
+
+            Err(err)
+        } else {
+            Ok(WaitRegistration {
+                wait_handle,
+                handle,
+                callback,
+            })
+        }
+    }
+}
+
+unsafe extern "system" fn wait_callback(callback: PVOID, _: BOOLEAN) {
+    let callback = &*(callback as *const Callback);
+
+    // Never propagate a poisoned lock - panics in the callback are ignored.
+    let mut callback = callback.lock().unwrap_or_else(|err| err.into_inner());
+
+    let _ = panic::catch_unwind(AssertUnwindSafe(&mut *callback));
+}
+
+impl Drop for WaitRegistration {
+    fn drop(&mut self) {
+        // Blocks until the callback, if running, completes.
+        let result = unsafe { UnregisterWaitEx(self.wait_handle, INVALID_HANDLE_VALUE) };
+
+        // Never free the callback if it might still be running.
+        if result != FALSE {
+            unsafe {
+                drop(Box::from_raw(self.callback));
+            }
+        }
+
+        let _ = close_handle(self.handle);
+    }
+}
+
+impl Debug for WaitRegistration {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("WaitRegistration")
+            .field("wait_handle", &self.wait_handle)
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+unsafe impl Send for WaitRegistration {}
+unsafe impl Sync for WaitRegistration {}
@@ -0,0 +1,187 @@
+use {
+    crate::{Event, EventError, WaitError, Waitable, WaitableResult},
+    std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        time::Duration,
+    },
+};
+
+/// Auto reset event wrapper which detects coalesced signals.
+///
+/// Setting an auto event which is already set (i.e. before a waiting thread consumed the previous signal)
+/// has no effect, silently losing the signal.
+/// This wrapper tracks whether the event has been set since it was last consumed by [`wait`] / [`wait_infinite`],
+/// and counts the signals lost this way, reported by [`lost_signals`].
+///
+/// The count is approximate - e.g., a signal may be reported lost if the event is set
+/// at the same time a waiting thread is woken up.
+/// On Windows, waiting on the event via [`wait_for_one`] / [`wait_for_all`] waits on its OS handle directly,
+/// so the consumed signals are not tracked. On other platforms these call [`wait`] and are tracked.
+///
+/// [`wait`]: #method.wait
+/// [`wait_infinite`]: #method.wait_infinite
+/// [`lost_signals`]: #method.lost_signals
+/// [`wait_for_one`]: ../fn.wait_for_one.html
+/// [`wait_for_all`]: ../fn.wait_for_all.html
+pub struct CoalescingAutoEvent {
+    event: Event,
+    pending: AtomicBool,
+    lost_signals: AtomicUsize,
+}
+
+impl CoalescingAutoEvent {
+    /// Creates a new unnamed auto reset event.
+    ///
+    /// `set` - gives the initial state of the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed.
+    pub fn new(set: bool) -> Result<CoalescingAutoEvent, EventError> {
+        Ok(CoalescingAutoEvent {
+            event: Event::new_auto(set, None)?,
+            pending: AtomicBool::new(set),
+            lost_signals: AtomicUsize::new(0),
+        })
+    }
+
+    /// Sets / signals the event.
+    ///
+    /// At most one waiting thread will be woken up.
+    /// If the event is already set, the signal is lost and counted in [`lost_signals`].
+    ///
+    /// [`lost_signals`]: #method.lost_signals
+    pub fn set(&self) -> Result<(), EventError> {
+        if self.pending.swap(true, Ordering::AcqRel) {
+            self.lost_signals.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.event.set()
+    }
+
+    /// Returns the number of times the event was [`set`] while it was already set.
+    ///
+    /// [`set`]: #method.set
+    pub fn lost_signals(&self) -> usize {
+        self.lost_signals.load(Ordering::Relaxed)
+    }
+
+    /// Clears the pending flag if the wait `result` consumed the signal.
+    fn consumed(&self, result: WaitableResult) -> WaitableResult {
+        if result == WaitableResult::Signaled {
+            self.pending.store(false, Ordering::Release);
+        }
+
+        result
+    }
+}
+
+impl Waitable for CoalescingAutoEvent {
+    /// Blocks the thread until the event is [`set`] or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.CoalescingAutoEvent.html#method.set
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.event.wait(d).map(|result| self.consumed(result))
+    }
+
+    /// Blocks the thread until the event is [`set`] or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.CoalescingAutoEvent.html#method.set
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.event
+            .wait_raw_ms(ms)
+            .map(|result| self.consumed(result))
+    }
+}
+
+#[cfg(windows)]
+impl crate::WaitableExt for CoalescingAutoEvent {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.event.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{sync::Arc, thread},
+    };
+
+    #[test]
+    fn lost_signals() {
+        let e = CoalescingAutoEvent::new(false).unwrap(); // Not signaled.
+        assert_eq!(e.lost_signals(), 0);
+
+        e.set().unwrap(); // Signaled.
+        assert_eq!(e.lost_signals(), 0);
+
+        e.set().unwrap(); // Still signaled - lost.
+        assert_eq!(e.lost_signals(), 1);
+
+        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        e.set().unwrap(); // Signaled again - not lost.
+        assert_eq!(e.lost_signals(), 1);
+
+        e.wait_infinite().unwrap();
+    }
+
+    #[test]
+    fn lost_signals_thread() {
+        let e = Arc::new(CoalescingAutoEvent::new(false).unwrap()); // Not signaled.
+        let e_clone = e.clone();
+
+        e.set().unwrap();
+        e.set().unwrap(); // Lost.
+
+        let t = thread::spawn(move || {
+            let res = e_clone.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+
+            // Only woken up once.
+            let res = e_clone.wait(Duration::from_millis(100)).unwrap();
+            assert!(res == WaitableResult::Timeout);
+        });
+
+        t.join().unwrap();
+
+        assert_eq!(e.lost_signals(), 1);
+    }
+
+    #[test]
+    fn wait_for_one() {
+        use crate::{wait_for_one, WaitablesResult};
+
+        let e = CoalescingAutoEvent::new(true).unwrap(); // Signaled.
+
+        let res = wait_for_one(&[&e as _], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+
+        e.set().unwrap();
+
+        // Consumed, but only tracked on non-Windows platforms -
+        // on Windows the next signal is reported lost.
+        #[cfg(windows)]
+        assert_eq!(e.lost_signals(), 1);
+
+        #[cfg(not(windows))]
+        assert_eq!(e.lost_signals(), 0);
+
+        let res = wait_for_one(&[&e as _], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+    }
+}
@@ -7,13 +7,104 @@ use std::{
 #[derive(Debug)]
 pub enum EventError {
     FailedToCreate(io::Error),
-    InvalidName,
+    /// The event name contained a nul byte at `nul_position`.
+    /// An empty name is reported as a nul byte at position `0` - i.e. the name's terminator.
+    InvalidName {
+        nul_position: usize,
+    },
+    /// The event name was `len` bytes long, exceeding `MAX_PATH`.
+    NameTooLong {
+        len: usize,
+    },
+    /// The event name already had a namespace prefix different from the requested one.
+    ConflictingNamespace,
     FailedToSet(io::Error),
     FailedToReset(io::Error),
     FailedToWait(io::Error),
+    FailedToClose(io::Error),
+    Closed,
+    InvalidHandle(io::Error),
+    WrongObjectType,
+    InvalidWaitStrategy,
+    FailedToDuplicate(io::Error),
+    FailedToOpen(io::Error),
+    NotFound,
+    FailedToRegister(io::Error),
+    Unsupported,
 }
 
-impl Error for EventError {}
+impl EventError {
+    /// Returns the underlying OS error, if any.
+    pub fn os_error(&self) -> Option<&io::Error> {
+        use EventError::*;
+
+        match self {
+            FailedToCreate(err)
+            | FailedToSet(err)
+            | FailedToReset(err)
+            | FailedToWait(err)
+            | FailedToClose(err)
+            | InvalidHandle(err)
+            | FailedToDuplicate(err)
+            | FailedToOpen(err)
+            | FailedToRegister(err) => Some(err),
+            InvalidName { .. }
+            | NameTooLong { .. }
+            | ConflictingNamespace
+            | Closed
+            | WrongObjectType
+            | InvalidWaitStrategy
+            | NotFound
+            | Unsupported => None,
+        }
+    }
+
+    /// Returns `true` if the event name was invalid, too long or had a conflicting namespace prefix.
+    pub fn is_invalid_name(&self) -> bool {
+        matches!(
+            self,
+            EventError::InvalidName { .. }
+                | EventError::NameTooLong { .. }
+                | EventError::ConflictingNamespace
+        )
+    }
+
+    /// Returns `true` if the event was closed.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, EventError::Closed)
+    }
+
+    /// Returns `true` if the handle was invalid or did not refer to an event.
+    pub fn is_invalid_handle(&self) -> bool {
+        matches!(
+            self,
+            EventError::InvalidHandle(_) | EventError::WrongObjectType
+        )
+    }
+
+    /// Returns `true` if the wait strategy was invalid.
+    pub fn is_invalid_wait_strategy(&self) -> bool {
+        matches!(self, EventError::InvalidWaitStrategy)
+    }
+
+    /// Returns `true` if the named event does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, EventError::NotFound)
+    }
+
+    /// Returns `true` if the OS denied the operation because the event handle lacks the required access rights -
+    /// e.g. setting an event created with only `SYNCHRONIZE` access.
+    pub fn is_access_denied(&self) -> bool {
+        self.os_error()
+            .is_some_and(|err| err.kind() == io::ErrorKind::PermissionDenied)
+    }
+}
+
+impl Error for EventError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.os_error().map(|err| err as _)
+    }
+}
 
 impl Display for EventError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
@@ -21,10 +112,53 @@ impl Display for EventError {
 
         match self {
             FailedToCreate(err) => write!(f, "failed to create the event: {}", err),
-            InvalidName => "invalid event name".fmt(f),
+            InvalidName { nul_position } => write!(
+                f,
+                "invalid event name: nul byte at position {}",
+                nul_position
+            ),
+            NameTooLong { len } => write!(f, "event name is too long: {} bytes", len),
+            ConflictingNamespace => "event name has a conflicting namespace prefix".fmt(f),
             FailedToSet(err) => write!(f, "failed to set the event: {}", err),
             FailedToReset(err) => write!(f, "failed to reset the event: {}", err),
             FailedToWait(err) => write!(f, "failed to wait on the event: {}", err),
+            FailedToClose(err) => write!(f, "failed to close the event: {}", err),
+            Closed => "the event was closed".fmt(f),
+            InvalidHandle(err) => write!(f, "invalid event handle: {}", err),
+            WrongObjectType => "the handle does not refer to an event".fmt(f),
+            InvalidWaitStrategy => "invalid wait strategy".fmt(f),
+            FailedToDuplicate(err) => write!(f, "failed to duplicate the event handle: {}", err),
+            FailedToOpen(err) => write!(f, "failed to open the event: {}", err),
+            NotFound => "no event with the given name exists".fmt(f),
+            FailedToRegister(err) => write!(f, "failed to register a wait on the event: {}", err),
+            Unsupported => "the operation is not supported on this platform".fmt(f),
+        }
+    }
+}
+
+impl From<EventError> for io::Error {
+    /// Unwraps the underlying OS error, if any.
+    fn from(err: EventError) -> Self {
+        use EventError::*;
+
+        match err {
+            FailedToCreate(err)
+            | FailedToSet(err)
+            | FailedToReset(err)
+            | FailedToWait(err)
+            | FailedToClose(err)
+            | InvalidHandle(err)
+            | FailedToDuplicate(err)
+            | FailedToOpen(err)
+            | FailedToRegister(err) => err,
+            err @ (InvalidName { .. }
+            | NameTooLong { .. }
+            | ConflictingNamespace
+            | Closed
+            | WrongObjectType
+            | InvalidWaitStrategy) => io::Error::new(io::ErrorKind::InvalidInput, err),
+            NotFound => io::Error::new(io::ErrorKind::NotFound, NotFound),
+            Unsupported => io::Error::new(io::ErrorKind::Unsupported, Unsupported),
         }
     }
 }
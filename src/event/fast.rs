@@ -0,0 +1,291 @@
+use {
+    crate::{duration_to_timeout_ms, WaitError, Waitable, WaitableResult, INFINITE_MS},
+    std::{
+        fmt::{Debug, Formatter},
+        io,
+        sync::atomic::{AtomicU32, Ordering},
+        time::{Duration, Instant},
+    },
+    winapi::{
+        shared::{minwindef::FALSE, winerror::ERROR_TIMEOUT},
+        um::{
+            synchapi::{WaitOnAddress, WakeByAddressAll, WakeByAddressSingle},
+            winbase::INFINITE,
+        },
+    },
+};
+
+const RESET: u32 = 0;
+const SET: u32 = 1;
+
+/// Lightweight in-process event.
+/// See [`WaitOnAddress`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitonaddress) on MSDN.
+///
+/// Has the same auto / manual reset semantics as an [`Event`], but is implemented over an atomic
+/// in user space, only entering the kernel to block / wake up threads when there are waiters,
+/// so is much cheaper for hot in-process signals. Requires Windows 8+.
+///
+/// NOTE - does not implement `WaitableExt`, as there's no OS object to return the handle of,
+/// so may only be waited on via its [`Waitable`] methods or [`wait_for_one_sequential`] / [`wait_for_all_sequential`],
+/// but not via [`wait_for_one`] / [`wait_for_all`]. Cannot be named or shared with other processes.
+///
+/// [`Event`]: struct.Event.html
+/// [`Waitable`]: trait.Waitable.html
+/// [`wait_for_one_sequential`]: fn.wait_for_one_sequential.html
+/// [`wait_for_all_sequential`]: fn.wait_for_all_sequential.html
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub struct FastEvent {
+    state: AtomicU32,
+    manual: bool,
+}
+
+impl FastEvent {
+    /// Creates a new auto reset event.
+    ///
+    /// `set` - gives the initial state of the event.
+    pub fn new_auto(set: bool) -> FastEvent {
+        FastEvent::new(false, set)
+    }
+
+    /// Creates a new manual reset event.
+    ///
+    /// `set` - gives the initial state of the event.
+    pub fn new_manual(set: bool) -> FastEvent {
+        FastEvent::new(true, set)
+    }
+
+    /// Sets / signals the event.
+    ///
+    /// Auto event: at most one waiting thread will be woken up.
+    /// Manual event: stays set / signaled until it is [`reset`].
+    ///
+    /// [`reset`]: #method.reset
+    pub fn set(&self) {
+        let prev = self.state.swap(SET, Ordering::Release);
+
+        if prev == SET {
+            return;
+        }
+
+        let address = self.address();
+
+        unsafe {
+            if self.manual {
+                WakeByAddressAll(address);
+            } else {
+                WakeByAddressSingle(address);
+            }
+        }
+    }
+
+    /// Resets the event.
+    pub fn reset(&self) {
+        self.state.store(RESET, Ordering::Release);
+    }
+
+    fn new(manual: bool, set: bool) -> FastEvent {
+        FastEvent {
+            state: AtomicU32::new(if set { SET } else { RESET }),
+            manual,
+        }
+    }
+
+    fn address(&self) -> *mut winapi::ctypes::c_void {
+        &self.state as *const AtomicU32 as *mut _
+    }
+
+    /// Consumes the signal of the auto event; returns `true` if the event was set.
+    fn try_consume(&self) -> bool {
+        if self.manual {
+            self.state.load(Ordering::Acquire) == SET
+        } else {
+            self.state
+                .compare_exchange(SET, RESET, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        }
+    }
+
+    /// `deadline` of `None` means "wait forever".
+    fn wait_impl(&self, deadline: Option<Instant>) -> io::Result<WaitableResult> {
+        let reset = RESET;
+
+        loop {
+            if self.try_consume() {
+                return Ok(WaitableResult::Signaled);
+            }
+
+            let ms = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Ok(WaitableResult::Timeout);
+                    }
+
+                    duration_to_timeout_ms(deadline - now)
+                }
+                None => INFINITE,
+            };
+
+            // Returns immediately if the event was set since the check above.
+            // May also wake up spuriously - the state is checked again in any case.
+            let result = unsafe {
+                WaitOnAddress(
+                    self.address(),
+                    &reset as *const u32 as *mut _,
+                    std::mem::size_of::<u32>(),
+                    ms,
+                )
+            };
+
+            if result == FALSE {
+                let err = io::Error::last_os_error();
+
+                if err.raw_os_error() != Some(ERROR_TIMEOUT as i32) {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+impl Debug for FastEvent {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("FastEvent")
+            .field("set", &(self.state.load(Ordering::Relaxed) == SET))
+            .field("manual", &self.manual)
+            .finish()
+    }
+}
+
+impl Waitable for FastEvent {
+    /// Blocks the thread until the event is [`set`] or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.FastEvent.html#method.set
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        let deadline = if ms == INFINITE_MS {
+            None
+        } else {
+            Some(Instant::now() + Duration::from_millis(ms.into()))
+        };
+
+        self.wait_impl(deadline).map_err(WaitError::FailedToWait)
+    }
+
+    /// Blocks the thread until the event is [`set`] or the duration `d` expires.
+    ///
+    /// Waits for the exact duration `d`, not rounded up to whole milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.FastEvent.html#method.set
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        // Overflow - wait forever.
+        let deadline = Instant::now().checked_add(d);
+
+        self.wait_impl(deadline).map_err(WaitError::FailedToWait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::Event,
+        std::{sync::Arc, thread},
+    };
+
+    /// Same sequence of operations as the `auto_reset_*` / `manual_reset_*` tests of `Event`.
+    fn check(e: &dyn Waitable, set: &dyn Fn(), reset: &dyn Fn(), manual: bool) {
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        set();
+
+        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+
+        if manual {
+            assert!(res == WaitableResult::Signaled); // Still signaled.
+        } else {
+            assert!(res == WaitableResult::Timeout); // Reset by the previous wait.
+        }
+
+        reset();
+
+        let res = e.wait(Duration::from_secs(0)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+    }
+
+    #[test]
+    fn same_as_event() {
+        for manual in [false, true].iter().copied() {
+            let e = if manual {
+                Event::new_manual(false, None).unwrap()
+            } else {
+                Event::new_auto(false, None).unwrap()
+            };
+            check(&e, &|| e.set().unwrap(), &|| e.reset().unwrap(), manual);
+
+            let e = if manual {
+                FastEvent::new_manual(false)
+            } else {
+                FastEvent::new_auto(false)
+            };
+            check(&e, &|| e.set(), &|| e.reset(), manual);
+        }
+    }
+
+    #[test]
+    fn wake_waiters() {
+        // Auto - one thread per set.
+
+        let e = Arc::new(FastEvent::new_auto(false));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let e = e.clone();
+                thread::spawn(move || e.wait_infinite().unwrap())
+            })
+            .collect();
+
+        for _ in 0..4 {
+            thread::sleep(Duration::from_millis(10));
+            e.set();
+        }
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Manual - all threads at once.
+
+        let e = Arc::new(FastEvent::new_manual(false));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let e = e.clone();
+                thread::spawn(move || e.wait_infinite().unwrap())
+            })
+            .collect();
+
+        thread::sleep(Duration::from_millis(10));
+        e.set();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use {
+    crate::{Event, EventError},
+    std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    },
+};
+
+/// Future returned by [`Event::wait_async`], which resolves when the event is [`set`].
+///
+/// Implemented via [`Event::poll_wait`] - waits on the OS thread pool instead of blocking a thread,
+/// so may be awaited in any executor (e.g. `tokio`, `async-std`).
+///
+/// Dropping the future before it resolves cancels the wait via [`Event::cancel_poll_wait`].
+/// An auto event's signal, if already consumed by the cancelled wait, is passed on to the next waiter.
+///
+/// [`Event::wait_async`]: struct.Event.html#method.wait_async
+/// [`set`]: struct.Event.html#method.set
+/// [`Event::poll_wait`]: struct.Event.html#method.poll_wait
+/// [`Event::cancel_poll_wait`]: struct.Event.html#method.cancel_poll_wait
+#[must_use = "futures do nothing unless polled"]
+pub struct EventFuture<'e> {
+    event: &'e Event,
+    /// Set when the future resolved - the wait need not be cancelled.
+    done: bool,
+}
+
+impl<'e> EventFuture<'e> {
+    pub(super) fn new(event: &'e Event) -> Self {
+        Self { event, done: false }
+    }
+}
+
+impl<'e> Future for EventFuture<'e> {
+    type Output = Result<(), EventError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.event.poll_wait(cx.waker()) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(res) => {
+                self.done = true;
+                Poll::Ready(res.map(|_| ()))
+            }
+        }
+    }
+}
+
+impl<'e> Drop for EventFuture<'e> {
+    fn drop(&mut self) {
+        // The cancelled wait may have already consumed the auto event's signal - pass it on to the next waiter.
+        if !self.done
+            && matches!(self.event.cancel_poll_wait(), Ok(true))
+            && !self.event.is_manual()
+        {
+            let _ = self.event.set();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+        std::{
+            sync::Arc,
+            task::{Wake, Waker},
+            thread::{self, Thread},
+            time::Duration,
+        },
+    };
+
+    /// Unparks the polling thread when woken.
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn waker() -> Waker {
+        Waker::from(Arc::new(ThreadWaker(thread::current())))
+    }
+
+    fn block_on<F: Future + Unpin>(mut f: F) -> F::Output {
+        let waker = waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(res) = Pin::new(&mut f).poll(&mut cx) {
+                return res;
+            }
+
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn wait_async() {
+        let e = Arc::new(Event::new_auto(false, None).unwrap());
+
+        let t = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                e.set().unwrap();
+            })
+        };
+
+        block_on(e.wait_async()).unwrap();
+
+        t.join().unwrap();
+
+        // The signal was consumed.
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Already signaled.
+        e.set().unwrap();
+        block_on(e.wait_async()).unwrap();
+    }
+
+    #[test]
+    fn cancel() {
+        let e = Event::new_auto(false, None).unwrap();
+
+        let waker = waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // Dropped before the event is set.
+        {
+            let mut f = e.wait_async();
+            assert!(Pin::new(&mut f).poll(&mut cx).is_pending());
+        }
+
+        e.set().unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Dropped after the registered wait consumed the signal.
+        {
+            let mut f = e.wait_async();
+            assert!(Pin::new(&mut f).poll(&mut cx).is_pending());
+
+            e.set().unwrap();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // The signal was passed on.
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+}
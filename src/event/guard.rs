@@ -0,0 +1,103 @@
+use {
+    crate::{Event, EventError},
+    std::fmt::{Debug, Formatter},
+};
+
+impl Event {
+    /// Sets the event and returns a guard which [`reset`]s it when dropped,
+    /// i.e. keeps the event signaled for the duration of the guard's scope, including early returns and panics.
+    ///
+    /// NOTE - only meaningful for manual reset events: an auto reset event is reset by the first released waiter,
+    /// so the guard does not reset it (which would discard a signal not consumed yet).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`reset`]: #method.reset
+    pub fn set_scoped(&self) -> Result<EventSetGuard<'_>, EventError> {
+        self.set()?;
+
+        Ok(EventSetGuard { event: self })
+    }
+}
+
+/// Scoped event signal returned by [`set_scoped`].
+///
+/// [`reset`]s the manual reset event when dropped.
+/// Reset errors are ignored, as the guard may be dropped while unwinding.
+///
+/// [`set_scoped`]: struct.Event.html#method.set_scoped
+/// [`reset`]: struct.Event.html#method.reset
+#[must_use = "the event is reset immediately if the guard is not held"]
+pub struct EventSetGuard<'a> {
+    event: &'a Event,
+}
+
+impl<'a> EventSetGuard<'a> {
+    /// Returns the signaled event.
+    pub fn event(&self) -> &'a Event {
+        self.event
+    }
+}
+
+impl<'a> Drop for EventSetGuard<'a> {
+    fn drop(&mut self) {
+        if self.event.is_manual() {
+            let _ = self.event.reset();
+        }
+    }
+}
+
+impl<'a> Debug for EventSetGuard<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("EventSetGuard")
+            .field("event", self.event)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+        std::{panic, time::Duration},
+    };
+
+    #[test]
+    fn set_scoped() {
+        let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+        {
+            let _guard = e.set_scoped().unwrap();
+
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+
+            // Still signaled.
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Reset while unwinding.
+        panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _guard = e.set_scoped().unwrap();
+            panic!("early exit");
+        }))
+        .unwrap_err();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Auto reset events are not reset by the guard.
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+        drop(e.set_scoped().unwrap());
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+}
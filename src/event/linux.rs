@@ -0,0 +1,533 @@
+use {
+    crate::{EventError, WaitError, Waitable, WaitableResult, INFINITE_MS},
+    std::{
+        fmt::{Debug, Formatter},
+        io,
+        os::unix::io::RawFd,
+        ptr,
+        sync::{Condvar, Mutex, MutexGuard},
+        time::{Duration, Instant},
+    },
+};
+
+/// Waitable event wrapper.
+///
+/// Auto event: gets reset when one awaiting thread is woken up.
+/// You must call [`set`] once for each awaiting thread.
+/// Backed by an [`eventfd`](https://man7.org/linux/man-pages/man2/eventfd.2.html).
+///
+/// Manual event: stays set/reset when [`set`] / [`reset`] is called on it.
+/// Backed by a mutex-protected flag and a condition variable.
+///
+/// Unlike on Windows, events may not be named / shared with other processes.
+///
+/// Closes the owned OS `eventfd`, if any, when dropped, unless it was already [`close`]d.
+///
+/// [`set`]: #method.set
+/// [`reset`]: #method.reset
+/// [`close`]: #method.close
+pub struct Event {
+    /// `None` if the event was [`close`](#method.close)d.
+    inner: Option<Inner>,
+    /// Kept separately from `inner`, as the event's kind outlives its [`close`](#method.close).
+    manual: bool,
+    label: Option<&'static str>,
+    #[cfg(feature = "deterministic")]
+    scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+}
+
+enum Inner {
+    Auto(RawFd),
+    /// The flag is boxed, so its address identifies the event even if the event is moved.
+    Manual {
+        set: Box<Mutex<bool>>,
+        condvar: Condvar,
+    },
+}
+
+impl Event {
+    /// Creates a new auto reset event.
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - must be `None` or empty - named events are not supported on this platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed, or if a `name` was given.
+    pub fn new_auto<'n, N: Into<Option<&'n str>>>(set: bool, name: N) -> Result<Event, EventError> {
+        Event::new(false, set, name.into())
+    }
+
+    /// Creates a new manual reset event.
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - must be `None` or empty - named events are not supported on this platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a `name` was given.
+    pub fn new_manual<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<Event, EventError> {
+        Event::new(true, set, name.into())
+    }
+
+    /// Sets / signals the event.
+    ///
+    /// Auto event: at most one waiting thread will be woken up.
+    /// Manual event: stays set / signaled until it is [`reset`].
+    ///
+    /// [`reset`]: #method.reset
+    pub fn set(&self) -> Result<(), EventError> {
+        let inner = self.inner()?;
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_signal(inner.object());
+
+        match inner {
+            Inner::Auto(fd) => {
+                // Increments the counter - repeated sets coalesce until the counter is read.
+                let value = 1u64;
+
+                let result = unsafe {
+                    libc::write(
+                        *fd,
+                        &value as *const u64 as *const _,
+                        std::mem::size_of::<u64>(),
+                    )
+                };
+
+                if result < 0 {
+                    Err(EventError::FailedToSet(io::Error::last_os_error()))
+                } else {
+                    Ok(())
+                }
+            }
+            Inner::Manual { set, condvar } => {
+                *lock(set) = true;
+                condvar.notify_all();
+                Ok(())
+            }
+        }
+    }
+
+    /// Resets the event.
+    pub fn reset(&self) -> Result<(), EventError> {
+        match self.inner()? {
+            Inner::Auto(fd) => read_counter(*fd)
+                .map(|_| ())
+                .map_err(EventError::FailedToReset),
+            Inner::Manual { set, .. } => {
+                *lock(set) = false;
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks whether the event is [`set`] without blocking -
+    /// same as waiting on the event with a zero timeout.
+    ///
+    /// Returns `Signaled` if the event is set, `Timeout` otherwise.
+    ///
+    /// Auto event: like any other wait, a successful `try_wait` consumes the signal -
+    /// i.e. resets the event, so it is not observed by the next waiter.
+    /// Manual event: the event stays set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    pub fn try_wait(&self) -> Result<WaitableResult, EventError> {
+        self.wait_impl(Some(Instant::now()))
+    }
+
+    /// Consumes and discards the pending signal of the auto event, if any, without blocking.
+    /// Manual event: same as [`reset`].
+    ///
+    /// Returns `true` if the event was set.
+    ///
+    /// Lets the caller clear a stale signal before starting a fresh wait phase,
+    /// which a new waiter would otherwise immediately consume.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`reset`]: #method.reset
+    pub fn drain(&self) -> Result<bool, EventError> {
+        match self.inner()? {
+            Inner::Auto(fd) => read_counter(*fd).map_err(EventError::FailedToWait),
+            Inner::Manual { set, .. } => Ok(std::mem::replace(&mut *lock(set), false)),
+        }
+    }
+
+    /// Closes the owned OS `eventfd`, if any.
+    ///
+    /// All subsequent operations on the event fail with [`EventError::Closed`].
+    /// Closing an already closed event does nothing and succeeds.
+    ///
+    /// [`EventError::Closed`]: enum.EventError.html#variant.Closed
+    pub fn close(&mut self) -> Result<(), EventError> {
+        #[cfg(feature = "diagnostics")]
+        if let Some(inner) = self.inner.as_ref() {
+            crate::diagnostics::deregister(inner.object());
+        }
+
+        match self.inner.take() {
+            Some(Inner::Auto(fd)) => {
+                if unsafe { libc::close(fd) } < 0 {
+                    Err(EventError::FailedToClose(io::Error::last_os_error()))
+                } else {
+                    Ok(())
+                }
+            }
+            Some(Inner::Manual { .. }) | None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the event was [`close`]d.
+    ///
+    /// [`close`]: #method.close
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns `true` if the event is a manual reset event, `false` if it is an auto reset event.
+    pub fn is_manual(&self) -> bool {
+        self.manual
+    }
+
+    /// Binds a human-readable `label` to the event, used in its `Debug` output.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Returns the label bound to the event via [`with_label`], if any.
+    ///
+    /// [`with_label`]: #method.with_label
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// Routes the event's waits / sets through the `scheduler` instead of the default [`OsScheduler`].
+    ///
+    /// [`OsScheduler`]: deterministic/struct.OsScheduler.html
+    #[cfg(feature = "deterministic")]
+    pub fn with_scheduler(
+        mut self,
+        scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    ) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    fn new(manual: bool, set: bool, name: Option<&str>) -> Result<Event, EventError> {
+        if name.is_some_and(|name| !name.is_empty()) {
+            return Err(EventError::Unsupported);
+        }
+
+        let inner = if manual {
+            Inner::Manual {
+                set: Box::new(Mutex::new(set)),
+                condvar: Condvar::new(),
+            }
+        } else {
+            let fd = unsafe {
+                libc::eventfd(
+                    if set { 1 } else { 0 },
+                    libc::EFD_CLOEXEC | libc::EFD_NONBLOCK,
+                )
+            };
+
+            if fd < 0 {
+                return Err(EventError::FailedToCreate(io::Error::last_os_error()));
+            }
+
+            Inner::Auto(fd)
+        };
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            inner.object(),
+            None,
+            if manual {
+                crate::diagnostics::ObjectKind::ManualEvent
+            } else {
+                crate::diagnostics::ObjectKind::AutoEvent
+            },
+        );
+
+        Ok(Event {
+            inner: Some(inner),
+            manual,
+            label: None,
+            #[cfg(feature = "deterministic")]
+            scheduler: crate::deterministic::os_scheduler(),
+        })
+    }
+
+    fn inner(&self) -> Result<&Inner, EventError> {
+        self.inner.as_ref().ok_or(EventError::Closed)
+    }
+
+    /// Waits for the exact duration `d`, not rounded up to whole milliseconds.
+    pub(crate) fn wait_duration_impl(&self, d: Duration) -> Result<WaitableResult, EventError> {
+        // Overflow - wait forever.
+        self.wait_impl(Instant::now().checked_add(d))
+    }
+
+    /// `deadline` of `None` means "wait forever".
+    fn wait_impl(&self, deadline: Option<Instant>) -> Result<WaitableResult, EventError> {
+        let inner = self.inner()?;
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_wait(inner.object());
+
+        let result = inner.wait(deadline)?;
+
+        #[cfg(feature = "deterministic")]
+        if result == WaitableResult::Signaled {
+            self.scheduler.after_wake(inner.object());
+        }
+
+        Ok(result)
+    }
+}
+
+impl Inner {
+    /// Identifies the event to its scheduler / in the diagnostics registry -
+    /// the `eventfd` of the auto event, the address of the manual event's flag.
+    #[cfg(any(feature = "deterministic", feature = "diagnostics"))]
+    fn object(&self) -> *mut () {
+        match self {
+            Inner::Auto(fd) => *fd as usize as *mut (),
+            Inner::Manual { set, .. } => &**set as *const Mutex<bool> as *mut (),
+        }
+    }
+
+    /// `deadline` of `None` means "wait forever".
+    fn wait(&self, deadline: Option<Instant>) -> Result<WaitableResult, EventError> {
+        use EventError::*;
+
+        match self {
+            Inner::Auto(fd) => loop {
+                // Only one of the woken up threads reads the non-zero counter and consumes the signal,
+                // the rest go back to waiting.
+                if read_counter(*fd).map_err(FailedToWait)? {
+                    return Ok(WaitableResult::Signaled);
+                }
+
+                let timeout = match deadline {
+                    Some(deadline) => {
+                        let now = Instant::now();
+
+                        if now >= deadline {
+                            return Ok(WaitableResult::Timeout);
+                        }
+
+                        Some(to_timespec(deadline - now))
+                    }
+                    None => None,
+                };
+
+                let mut poll_fd = libc::pollfd {
+                    fd: *fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                };
+
+                let result = unsafe {
+                    libc::ppoll(
+                        &mut poll_fd,
+                        1,
+                        timeout.as_ref().map_or(ptr::null(), |t| t as *const _),
+                        ptr::null(),
+                    )
+                };
+
+                if result < 0 {
+                    let err = io::Error::last_os_error();
+
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(FailedToWait(err));
+                    }
+                }
+            },
+            Inner::Manual { set, condvar } => {
+                let mut set = lock(set);
+
+                while !*set {
+                    set = match deadline {
+                        Some(deadline) => {
+                            let now = Instant::now();
+
+                            if now >= deadline {
+                                return Ok(WaitableResult::Timeout);
+                            }
+
+                            condvar
+                                .wait_timeout(set, deadline - now)
+                                .unwrap_or_else(|err| err.into_inner())
+                                .0
+                        }
+                        None => condvar.wait(set).unwrap_or_else(|err| err.into_inner()),
+                    };
+                }
+
+                Ok(WaitableResult::Signaled)
+            }
+        }
+    }
+}
+
+/// Reads and zeroes the `eventfd` counter without blocking; returns `true` if it was non-zero.
+fn read_counter(fd: RawFd) -> io::Result<bool> {
+    let mut value = 0u64;
+
+    let result = unsafe {
+        libc::read(
+            fd,
+            &mut value as *mut u64 as *mut _,
+            std::mem::size_of::<u64>(),
+        )
+    };
+
+    if result < 0 {
+        let err = io::Error::last_os_error();
+
+        if err.kind() == io::ErrorKind::WouldBlock {
+            Ok(false)
+        } else {
+            Err(err)
+        }
+    } else {
+        Ok(true)
+    }
+}
+
+fn to_timespec(d: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: d.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: d.subsec_nanos() as _,
+    }
+}
+
+fn lock(set: &Mutex<bool>) -> MutexGuard<'_, bool> {
+    // The flag is always valid, so never propagate a poisoned lock.
+    set.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        // Failing to close the file descriptor means it was invalid or already closed - i.e. a bug elsewhere.
+        let res = self.close();
+        debug_assert!(
+            res.is_ok(),
+            "failed to close the event file descriptor: {:?}",
+            res
+        );
+    }
+}
+
+impl Debug for Event {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("Event");
+
+        match self.inner {
+            Some(Inner::Auto(fd)) => s.field("fd", &fd),
+            Some(Inner::Manual { .. }) => &mut s,
+            None => s.field("closed", &true),
+        };
+
+        s.field("manual", &self.manual);
+
+        if let Some(label) = self.label {
+            s.field("label", &label);
+        }
+
+        s.finish()
+    }
+}
+
+impl Waitable for Event {
+    /// Blocks the thread until the event is [`set`] or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.Event.html#method.set
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        let deadline = if ms == INFINITE_MS {
+            None
+        } else {
+            Some(Instant::now() + Duration::from_millis(ms.into()))
+        };
+
+        self.wait_impl(deadline).map_err(WaitError::from)
+    }
+
+    /// Blocks the thread until the event is [`set`] or the duration `d` expires.
+    ///
+    /// Waits for the exact duration `d`, not rounded up to whole milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: struct.Event.html#method.set
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.wait_duration_impl(d).map_err(WaitError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named() {
+        match Event::new_auto(false, "minievent_named") {
+            Err(EventError::Unsupported) => {}
+            _ => panic!(),
+        }
+
+        Event::new_manual(false, "").unwrap();
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn diagnostics() {
+        use crate::diagnostics::{dump, ObjectKind};
+
+        let kind_of = |e: &Event| {
+            let object = e.inner().unwrap().object() as usize;
+
+            dump()
+                .into_iter()
+                .find(|info| info.handle == object)
+                .map(|info| info.kind)
+        };
+
+        let mut auto = Event::new_auto(false, None).unwrap();
+        let manual = Event::new_manual(false, None).unwrap();
+
+        assert_eq!(kind_of(&auto), Some(ObjectKind::AutoEvent));
+        assert_eq!(kind_of(&manual), Some(ObjectKind::ManualEvent));
+
+        // Moving the event does not change its registered identity.
+        let manual = Box::new(manual);
+        assert_eq!(kind_of(&manual), Some(ObjectKind::ManualEvent));
+
+        let object = auto.inner().unwrap().object() as usize;
+        auto.close().unwrap();
+        assert!(dump().iter().all(|info| info.handle != object));
+
+        let object = manual.inner().unwrap().object() as usize;
+        drop(manual);
+        assert!(dump().iter().all(|info| info.handle != object));
+    }
+}
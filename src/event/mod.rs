@@ -1,9 +1,47 @@
+mod bulk;
+#[cfg(windows)]
+mod callback;
+mod coalescing;
 mod error;
+#[cfg(windows)]
+mod fast;
+#[cfg(all(windows, feature = "async"))]
+mod future;
+mod guard;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(windows)]
+mod one_shot;
+#[cfg(windows)]
+mod poll;
+mod predicate;
+#[cfg(test)]
+mod tests;
 
+#[cfg(windows)]
+mod process_exit;
+mod typed;
 #[cfg(windows)]
 mod win;
 
-pub use error::EventError;
+pub use {
+    coalescing::CoalescingAutoEvent,
+    error::EventError,
+    guard::EventSetGuard,
+    typed::{AutoResetEvent, ManualResetEvent},
+};
+
+#[cfg(target_os = "linux")]
+pub use linux::Event;
 
 #[cfg(windows)]
-pub use win::Event;
+pub use {
+    callback::WaitRegistration,
+    fast::FastEvent,
+    one_shot::{OneShotEvent, OneShotResult},
+    process_exit::ProcessExitEvent,
+    win::{wait_then_signal, Event, EventAccess, EventOptions},
+};
+
+#[cfg(all(windows, feature = "async"))]
+pub use future::EventFuture;
@@ -0,0 +1,157 @@
+use {
+    crate::{duration_to_timeout_ms, Event, EventError, WaitableResult},
+    std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    },
+};
+
+/// Result of waiting on a [`OneShotEvent`](struct.OneShotEvent.html).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OneShotResult {
+    /// The event was fired, and this wait consumed it.
+    Signaled,
+    /// The timeout duration elapsed before the event was fired.
+    Timeout,
+    /// The event was fired, but was already consumed by another wait.
+    AlreadyFired,
+}
+
+/// Event which may be fired and consumed exactly once.
+///
+/// Only the first call to [`fire`] has any effect. Only the first wait which observes the event
+/// as fired returns `Signaled`; all other waits, concurrent or later, return `AlreadyFired`.
+/// Prevents accidental re-use in fire-once handshakes.
+///
+/// Implemented with a manual reset event, which stays set once fired,
+/// so that all waiting threads wake up to observe that the event was consumed.
+///
+/// [`fire`]: #method.fire
+pub struct OneShotEvent {
+    event: Event,
+    fired: AtomicBool,
+    consumed: AtomicBool,
+}
+
+impl OneShotEvent {
+    /// Creates a new unfired event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed.
+    pub fn new() -> Result<OneShotEvent, EventError> {
+        Ok(OneShotEvent {
+            event: Event::new_manual(false, None)?,
+            fired: AtomicBool::new(false),
+            consumed: AtomicBool::new(false),
+        })
+    }
+
+    /// Fires the event, if it was not fired before.
+    ///
+    /// Returns `true` if this call fired the event, `false` if it was already fired.
+    pub fn fire(&self) -> Result<bool, EventError> {
+        if self.fired.swap(true, Ordering::AcqRel) {
+            Ok(false)
+        } else {
+            self.event.set()?;
+            Ok(true)
+        }
+    }
+
+    /// Returns `true` if the event was fired.
+    pub fn is_fired(&self) -> bool {
+        self.fired.load(Ordering::Acquire)
+    }
+
+    /// Blocks the thread until the event is [`fire`]d or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`fire`]: #method.fire
+    pub fn wait(&self, d: Duration) -> Result<OneShotResult, EventError> {
+        if self.consumed.load(Ordering::Acquire) {
+            return Ok(OneShotResult::AlreadyFired);
+        }
+
+        match self.event.wait_impl(duration_to_timeout_ms(d))? {
+            // Events are never abandoned.
+            WaitableResult::Signaled | WaitableResult::Abandoned => {
+                if self.consumed.swap(true, Ordering::AcqRel) {
+                    Ok(OneShotResult::AlreadyFired)
+                } else {
+                    Ok(OneShotResult::Signaled)
+                }
+            }
+            // Not an alertable wait.
+            WaitableResult::Timeout | WaitableResult::IoCompletion => Ok(OneShotResult::Timeout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{sync::Arc, thread},
+    };
+
+    #[test]
+    fn fire_once() {
+        let e = OneShotEvent::new().unwrap();
+        assert!(!e.is_fired());
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == OneShotResult::Timeout);
+
+        assert!(e.fire().unwrap());
+        assert!(e.is_fired());
+        assert!(!e.fire().unwrap()); // No-op.
+
+        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == OneShotResult::Signaled);
+
+        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == OneShotResult::AlreadyFired);
+
+        assert!(!e.fire().unwrap()); // Can't be re-armed.
+
+        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == OneShotResult::AlreadyFired);
+    }
+
+    #[test]
+    fn concurrent_waiters() {
+        let e = Arc::new(OneShotEvent::new().unwrap());
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let e = e.clone();
+                thread::spawn(move || e.wait(Duration::from_secs(1_000_000)).unwrap())
+            })
+            .collect();
+
+        thread::sleep(Duration::from_millis(10));
+        e.fire().unwrap();
+
+        let results: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+        // Exactly one waiter consumed the event, all others woke up and saw it consumed.
+        assert_eq!(
+            results
+                .iter()
+                .filter(|res| **res == OneShotResult::Signaled)
+                .count(),
+            1
+        );
+        assert_eq!(
+            results
+                .iter()
+                .filter(|res| **res == OneShotResult::AlreadyFired)
+                .count(),
+            3
+        );
+    }
+}
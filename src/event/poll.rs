@@ -0,0 +1,192 @@
+use {
+    std::{
+        io, ptr,
+        sync::{Arc, Mutex, MutexGuard},
+        task::Waker,
+    },
+    winapi::{
+        shared::{minwindef::FALSE, ntdef::BOOLEAN},
+        um::{
+            handleapi::INVALID_HANDLE_VALUE,
+            threadpoollegacyapiset::UnregisterWaitEx,
+            winbase::{RegisterWaitForSingleObject, INFINITE},
+            winnt::{HANDLE, PVOID, WT_EXECUTEONLYONCE},
+        },
+    },
+};
+
+/// State of the OS thread pool wait registered by `Event::poll_wait`, if any.
+pub(crate) struct PollRegistration {
+    registration: Mutex<Option<Registration>>,
+}
+
+struct Registration {
+    wait_handle: HANDLE,
+    shared: Arc<Mutex<Shared>>,
+}
+
+/// Shared with the thread pool wait callback.
+struct Shared {
+    waker: Waker,
+    /// Set by the callback - the registered wait was satisfied, consuming the object's signal.
+    fired: bool,
+}
+
+/// Result of polling a registered thread pool wait.
+pub(crate) enum PollState {
+    /// No wait is registered.
+    NotRegistered,
+    /// The registered wait is still pending; its waker was updated.
+    Pending,
+    /// The registered wait was satisfied, and is now unregistered.
+    Fired,
+}
+
+impl PollRegistration {
+    pub(crate) fn new() -> Self {
+        Self {
+            registration: Mutex::new(None),
+        }
+    }
+
+    /// Checks the registered wait, if any, replacing its waker with `waker` if it's still pending.
+    pub(crate) fn poll(&self, waker: &Waker) -> io::Result<PollState> {
+        let mut registration = self.registration();
+
+        let fired = match registration.as_ref() {
+            None => return Ok(PollState::NotRegistered),
+            Some(registration) => {
+                let mut shared = lock(&registration.shared);
+
+                if !shared.fired && !shared.waker.will_wake(waker) {
+                    shared.waker = waker.clone();
+                }
+
+                shared.fired
+            }
+        };
+
+        if fired {
+            // The callback has already run, so this blocks at most until it returns.
+            registration.take().unwrap().unregister()?;
+            Ok(PollState::Fired)
+        } else {
+            Ok(PollState::Pending)
+        }
+    }
+
+    /// Registers a one-shot thread pool wait on the OS object `handle` which wakes the `waker` when satisfied.
+    /// If a wait was registered concurrently by another thread, only replaces its waker.
+    pub(crate) fn register(&self, handle: HANDLE, waker: &Waker) -> io::Result<()> {
+        let mut registration = self.registration();
+
+        if let Some(registration) = registration.as_ref() {
+            lock(&registration.shared).waker = waker.clone();
+            return Ok(());
+        }
+
+        let shared = Arc::new(Mutex::new(Shared {
+            waker: waker.clone(),
+            fired: false,
+        }));
+
+        let context = Arc::into_raw(shared.clone()) as PVOID;
+        let mut wait_handle = ptr::null_mut();
+
+        let result = unsafe {
+            RegisterWaitForSingleObject(
+                &mut wait_handle,
+                handle,
+                Some(wait_callback),
+                context,
+                INFINITE,
+                WT_EXECUTEONLYONCE,
+            )
+        };
+
+        if result == FALSE {
+            let err = io::Error::last_os_error();
+
+            unsafe {
+                drop(Arc::from_raw(context as *const Mutex<Shared>));
+            }
+
+            Err(err)
+        } else {
+            *registration = Some(Registration {
+                wait_handle,
+                shared,
+            });
+
+            Ok(())
+        }
+    }
+
+    /// Unregisters the registered wait, if any, blocking until its callback completes if it is currently running.
+    ///
+    /// Returns `true` if the wait was satisfied before it was unregistered, consuming the object's signal.
+    pub(crate) fn cancel(&self) -> io::Result<bool> {
+        if let Some(registration) = self.registration().take() {
+            let fired = registration.unregister()?;
+            Ok(fired)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn registration(&self) -> MutexGuard<'_, Option<Registration>> {
+        // The wakers are not called under the lock, so never propagate a poisoned lock.
+        self.registration
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Registration {
+    /// Returns `true` if the wait was satisfied before it was unregistered.
+    fn unregister(self) -> io::Result<bool> {
+        // Blocks until the callback, if running, completes.
+        let result = unsafe { UnregisterWaitEx(self.wait_handle, INVALID_HANDLE_VALUE) };
+
+        if result == FALSE {
+            return Err(io::Error::last_os_error());
+        }
+
+        // The callback does not run after the wait is unregistered - free its reference to the shared state.
+        let fired = lock(&self.shared).fired;
+
+        if !fired {
+            unsafe {
+                drop(Arc::from_raw(Arc::as_ptr(&self.shared)));
+            }
+        }
+
+        Ok(fired)
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    // The wakers are not called under the lock, so never propagate a poisoned lock.
+    shared.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+unsafe extern "system" fn wait_callback(context: PVOID, _: BOOLEAN) {
+    let shared = Arc::from_raw(context as *const Mutex<Shared>);
+
+    let waker = {
+        let mut shared = lock(&shared);
+        shared.fired = true;
+        shared.waker.clone()
+    };
+
+    waker.wake();
+}
+
+impl Drop for PollRegistration {
+    fn drop(&mut self) {
+        let _ = self.cancel();
+    }
+}
+
+unsafe impl Send for PollRegistration {}
+unsafe impl Sync for PollRegistration {}
@@ -0,0 +1,148 @@
+use {
+    crate::{Event, EventError, WaitableResult},
+    std::time::{Duration, Instant},
+};
+
+impl Event {
+    /// Blocks the thread until the predicate `pred` returns `true` or the duration `d` expires,
+    /// re-checking `pred` every time the event is [`set`] - i.e. condition variable style usage of the event.
+    ///
+    /// Returns `Signaled` if `pred` returned `true`, `Timeout` otherwise.
+    /// `pred` is checked before every wait and once more after the final wait timed out,
+    /// so a state change racing the timeout is not missed.
+    /// Spurious wakeups (the event is [`set`] but `pred` is still `false`) do not extend the total wait duration.
+    ///
+    /// A manual reset event is [`reset`] after each wakeup, before `pred` is re-checked,
+    /// so the notifier must update the state `pred` observes before it [`set`]s the event.
+    /// Other threads waiting on the same manual reset event may thus miss the wakeup -
+    /// they should wait via `wait_until_predicate` too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    /// [`reset`]: #method.reset
+    pub fn wait_until_predicate<F: FnMut() -> bool>(
+        &self,
+        mut pred: F,
+        d: Duration,
+    ) -> Result<WaitableResult, EventError> {
+        // Overflow - wait forever.
+        let deadline = Instant::now().checked_add(d);
+
+        loop {
+            if pred() {
+                return Ok(WaitableResult::Signaled);
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+
+                    // Checked explicitly, as the wait never times out if the event keeps being set.
+                    if remaining == Duration::from_secs(0) {
+                        return Ok(WaitableResult::Timeout);
+                    }
+
+                    remaining
+                }
+                None => d,
+            };
+
+            match self.wait_duration_impl(remaining)? {
+                WaitableResult::Signaled => {
+                    if self.is_manual() {
+                        self.reset()?;
+                    }
+                }
+                WaitableResult::Timeout => {
+                    return Ok(if pred() {
+                        WaitableResult::Signaled
+                    } else {
+                        WaitableResult::Timeout
+                    });
+                }
+                res => return Ok(res),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            thread,
+        },
+    };
+
+    #[test]
+    fn wait_until_predicate() {
+        let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+        // Already true - does not wait.
+        let res = e
+            .wait_until_predicate(|| true, Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e
+            .wait_until_predicate(|| false, Duration::from_millis(1))
+            .unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Spurious wakeups do not extend the wait.
+        e.set().unwrap();
+
+        let start = Instant::now();
+
+        let res = e
+            .wait_until_predicate(
+                || {
+                    e.set().unwrap();
+                    false
+                },
+                Duration::from_millis(50),
+            )
+            .unwrap();
+        assert!(res == WaitableResult::Timeout);
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        for e in [
+            Event::new_manual(false, None).unwrap(),
+            Event::new_auto(false, None).unwrap(),
+        ] {
+            let e = Arc::new(e);
+            let counter = Arc::new(AtomicUsize::new(0));
+
+            let t = {
+                let e = e.clone();
+                let counter = counter.clone();
+
+                thread::spawn(move || {
+                    for _ in 0..3 {
+                        thread::sleep(Duration::from_millis(5));
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        e.set().unwrap();
+                    }
+                })
+            };
+
+            let res = e
+                .wait_until_predicate(
+                    || counter.load(Ordering::SeqCst) == 3,
+                    Duration::from_secs(1_000_000),
+                )
+                .unwrap();
+            assert!(res == WaitableResult::Signaled);
+
+            t.join().unwrap();
+        }
+    }
+}
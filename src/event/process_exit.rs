@@ -0,0 +1,140 @@
+use {
+    crate::{Event, EventError, WaitError, Waitable, WaitableExt, WaitableResult},
+    std::{io, os::raw::c_int, sync::Mutex, time::Duration},
+};
+
+extern "C" {
+    fn atexit(callback: extern "C" fn()) -> c_int;
+}
+
+/// The process-wide exit event, created on first use and never destroyed.
+static EXIT_EVENT: Mutex<Option<&'static Event>> = Mutex::new(None);
+
+/// Manual reset event which is set when the current process begins exiting normally.
+///
+/// Worker threads may include it in their wait loops (e.g. via [`wait_for_one`])
+/// to notice the process exit and wind down gracefully.
+///
+/// Implemented by an `atexit` handler, so the event is set when the C runtime's `exit` is called -
+/// i.e. when `main` returns or on `std::process::exit`, after which the process is terminated
+/// as soon as all `atexit` handlers complete, whether the worker threads are done or not.
+/// Abnormal terminations (e.g. `std::process::abort`, `TerminateProcess`, unhandled exceptions)
+/// bypass the `atexit` handlers, and the event is never set.
+///
+/// All instances refer to the same process-wide event, which is never closed.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[derive(Clone, Copy, Debug)]
+pub struct ProcessExitEvent {
+    event: &'static Event,
+}
+
+impl ProcessExitEvent {
+    /// Returns the process exit event, creating it and registering the `atexit` handler on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed, or if the `atexit` handler could not be registered.
+    pub fn new() -> Result<ProcessExitEvent, EventError> {
+        // Never propagate a poisoned lock - the event is created at most once.
+        let mut exit_event = EXIT_EVENT.lock().unwrap_or_else(|err| err.into_inner());
+
+        let event = if let Some(event) = *exit_event {
+            event
+        } else {
+            let event = Event::new_manual(false, None)?;
+
+            if unsafe { atexit(on_exit) } != 0 {
+                return Err(EventError::FailedToCreate(io::Error::other(
+                    "failed to register the atexit handler",
+                )));
+            }
+
+            let event: &'static Event = Box::leak(Box::new(event));
+            *exit_event = Some(event);
+            event
+        };
+
+        Ok(ProcessExitEvent { event })
+    }
+
+    /// Returns `true` if the process has begun exiting.
+    pub fn is_exiting(&self) -> bool {
+        matches!(
+            self.event.wait(Duration::from_secs(0)),
+            Ok(WaitableResult::Signaled)
+        )
+    }
+}
+
+extern "C" fn on_exit() {
+    let exit_event = EXIT_EVENT.lock().unwrap_or_else(|err| err.into_inner());
+
+    if let Some(event) = *exit_event {
+        let _ = event.set();
+    }
+}
+
+impl Waitable for ProcessExitEvent {
+    /// Blocks the thread until the process begins exiting or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.event.wait_raw_ms(ms)
+    }
+}
+
+impl WaitableExt for ProcessExitEvent {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.event.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{env, io::Write, process::Command},
+    };
+
+    const SIGNALED_MARKER: &str = "minievent_process_exit_signaled";
+
+    extern "C" fn check_signaled() {
+        // Registered before the event's handler, so runs after it.
+        if ProcessExitEvent::new().is_ok_and(|e| e.is_exiting()) {
+            let _ = io::stderr().write_all(SIGNALED_MARKER.as_bytes());
+        }
+    }
+
+    /// Run in a child process by `signaled_on_exit`.
+    #[test]
+    #[ignore]
+    fn signaled_on_exit_child() {
+        assert_eq!(unsafe { atexit(check_signaled) }, 0);
+
+        let e = ProcessExitEvent::new().unwrap();
+        assert!(!e.is_exiting());
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+    }
+
+    #[test]
+    fn signaled_on_exit() {
+        let output = Command::new(env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "event::process_exit::tests::signaled_on_exit_child",
+                "--ignored",
+                "--test-threads=1",
+            ])
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(SIGNALED_MARKER));
+    }
+}
@@ -0,0 +1,566 @@
+use {
+    crate::{
+        wait_for_all, wait_for_all_infinite, wait_for_all_sequential, wait_for_one,
+        wait_for_one_infinite, wait_for_one_sequential, Event, EventError, Waitable,
+        WaitableResult, WaitablesResult,
+    },
+    std::{
+        io,
+        sync::Arc,
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+#[cfg(windows)]
+use crate::WaitableExt as Element;
+
+#[cfg(not(windows))]
+use crate::Waitable as Element;
+
+#[test]
+fn manual_reset_signaled_method() {
+    let e = Event::new_manual(true, None).unwrap(); // Signaled.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    e.reset().unwrap(); // Not anymore.
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Signaled again.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn manual_reset_signaled_free_function() {
+    let e = Event::new_manual(true, None).unwrap(); // Signaled.
+    let w = [&e as _];
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = wait_for_all_infinite(&w).unwrap(); // And still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    e.reset().unwrap(); // Not anymore.
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Signaled again.
+
+    let res = wait_for_all_infinite(&w).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn manual_reset_signaled_free_function_multiple() {
+    let e0 = Event::new_manual(true, None).unwrap(); // Signaled.
+    let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+    let w = [&e0 as _, &e1 as _]; // Signaled.
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = wait_for_one_infinite(&w).unwrap(); // And still signaled.
+    assert!(res == WaitablesResult::OneSignaled(0) || res == WaitablesResult::OneSignaled(1));
+
+    e0.reset().unwrap(); // One not signaled.
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    let res = wait_for_one_infinite(&w).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(1));
+
+    e1.reset().unwrap(); // Both not signaled.
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    let res = wait_for_one(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::Timeout);
+
+    e0.set().unwrap(); // Only one signaled.
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    let res = wait_for_one_infinite(&w).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(0));
+
+    e1.set().unwrap(); // Both signaled again.
+
+    let res = wait_for_all_infinite(&w).unwrap();
+    assert!(res == WaitableResult::Signaled);
+
+    let res = wait_for_one_infinite(&w).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(0) || res == WaitablesResult::OneSignaled(1));
+}
+
+#[test]
+fn manual_reset_sequential_matches_native() {
+    let e = [
+        Event::new_manual(false, None).unwrap(),
+        Event::new_manual(false, None).unwrap(),
+        Event::new_manual(false, None).unwrap(),
+    ];
+    let w = [&e[0] as _, &e[1] as _, &e[2] as _];
+    let ws = [&e[0] as _, &e[1] as _, &e[2] as _];
+
+    // Every combination of signaled / not signaled events.
+    for state in 0..(1 << e.len()) {
+        for (idx, e) in e.iter().enumerate() {
+            if state & (1 << idx) != 0 {
+                e.set().unwrap();
+            } else {
+                e.reset().unwrap();
+            }
+        }
+
+        let native = wait_for_one(&w, Duration::from_millis(1)).unwrap();
+        let sequential = wait_for_one_sequential(&ws, Duration::from_millis(1)).unwrap();
+        assert_eq!(native, sequential);
+
+        let native = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+        let sequential = wait_for_all_sequential(&ws, Duration::from_millis(1)).unwrap();
+        assert_eq!(native, sequential);
+    }
+
+    wait_for_one(&[], Duration::from_millis(1)).err().unwrap();
+    wait_for_one_sequential(&[], Duration::from_millis(1))
+        .err()
+        .unwrap();
+}
+
+#[test]
+fn manual_reset_unsignaled_method() {
+    let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Now signaled.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    e.reset().unwrap(); // Not anymore.
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Signaled again.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn auto_reset_signaled() {
+    let e = Event::new_auto(true, None).unwrap(); // Signaled.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Signaled again.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn auto_reset_unsignaled() {
+    let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    e.set().unwrap(); // Now signaled.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    e.set().unwrap(); // Signaled again.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn label() {
+    let e = Event::new_auto(false, None).unwrap();
+    assert_eq!(e.label(), None);
+    assert!(!format!("{:?}", e).contains("label"));
+    assert!(format!("{:?}", e).contains("manual: false"));
+
+    let e = e.with_label("my_event");
+    assert_eq!(e.label(), Some("my_event"));
+    assert!(format!("{:?}", e).contains("my_event"));
+}
+
+#[test]
+fn wait_until() {
+    let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+    // Deadline has passed.
+
+    let res = e
+        .wait_until(Instant::now() - Duration::from_millis(1))
+        .unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    let now = Instant::now();
+    let res = e.wait_until(now + Duration::from_millis(500)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+    assert!(now.elapsed().as_millis() >= 250);
+
+    e.set().unwrap(); // Now signaled.
+
+    let res = e
+        .wait_until(Instant::now() - Duration::from_millis(1))
+        .unwrap();
+    assert!(res == WaitableResult::Signaled);
+
+    let res = e
+        .wait_until(Instant::now() + Duration::from_secs(1_000_000))
+        .unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn manual_thread_signal() {
+    let e = Arc::new(Event::new_manual(false, None).unwrap());
+    let e_clone_1 = e.clone();
+    let e_clone_2 = e.clone(); // Not signaled.
+
+    let t_1 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = e_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    let t_2 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = e_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    // Wait for a second.
+    thread::sleep(Duration::from_secs(1));
+
+    e.set().unwrap();
+
+    // Both threads must have exited at about the same time.
+
+    let res_1 = t_1.join().unwrap();
+
+    assert!(res_1.0 == WaitableResult::Signaled);
+    assert!(res_1.1.as_millis() >= 500);
+
+    let res_2 = t_2.join().unwrap();
+
+    assert!(res_2.0 == WaitableResult::Signaled);
+    assert!(res_2.1.as_millis() >= 500);
+
+    // Still signaled.
+
+    let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+}
+
+#[test]
+fn auto_thread_signal() {
+    let e = Arc::new(Event::new_auto(false, None).unwrap());
+    let e_clone_1 = e.clone();
+    let e_clone_2 = e.clone(); // Not signaled.
+
+    let t_1 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = e_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    let t_2 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = e_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    // Wait for a second.
+    thread::sleep(Duration::from_secs(1));
+
+    e.set().unwrap();
+
+    // One of the threads has exited, the other is still waiting.
+
+    thread::sleep(Duration::from_millis(1_000));
+
+    e.set().unwrap();
+
+    // Now both have exited.
+
+    let res_1 = t_1.join().unwrap();
+
+    assert!(res_1.0 == WaitableResult::Signaled);
+    assert!(res_1.1.as_millis() >= 500);
+
+    let res_2 = t_2.join().unwrap();
+
+    assert!(res_2.0 == WaitableResult::Signaled);
+    assert!(res_2.1.as_millis() >= 500);
+
+    if res_1.1.as_millis() > res_2.1.as_millis() {
+        assert!(res_1.1.as_millis() - res_2.1.as_millis() >= 500);
+    } else {
+        assert!(res_2.1.as_millis() - res_1.1.as_millis() >= 500);
+    }
+
+    // Not signaled.
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+}
+
+#[test]
+fn wake_waiters() {
+    // Auto - one thread per set.
+
+    let e = Arc::new(Event::new_auto(false, None).unwrap());
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let e = e.clone();
+            thread::spawn(move || e.wait_infinite().unwrap())
+        })
+        .collect();
+
+    for _ in 0..4 {
+        thread::sleep(Duration::from_millis(10));
+        e.set().unwrap();
+    }
+
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    // Manual - all threads at once.
+
+    let e = Arc::new(Event::new_manual(false, None).unwrap());
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let e = e.clone();
+            thread::spawn(move || e.wait_infinite().unwrap())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(10));
+    e.set().unwrap();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn close() {
+    let mut e = Event::new_auto(false, None).unwrap();
+    assert!(!e.is_closed());
+
+    e.close().unwrap();
+    assert!(e.is_closed());
+    e.close().unwrap(); // Already closed.
+
+    assert!(e.set().unwrap_err().is_closed());
+    assert!(e.wait(Duration::from_millis(1)).is_err());
+}
+
+#[test]
+fn long_timeout() {
+    // Over `u32::MAX` milliseconds - saturated, must not wrap to a short timeout.
+    let d = Duration::from_secs(60 * 24 * 60 * 60);
+
+    let e = Arc::new(Event::new_auto(false, None).unwrap());
+
+    let set_later = || {
+        let e = e.clone();
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            e.set().unwrap();
+        })
+    };
+
+    let t = set_later();
+    assert!(e.wait(d).unwrap() == WaitableResult::Signaled);
+    t.join().unwrap();
+
+    let t = set_later();
+    assert!(wait_for_one(&[&*e], d).unwrap() == WaitablesResult::OneSignaled(0));
+    t.join().unwrap();
+
+    let t = set_later();
+    assert!(wait_for_all(&[&*e], d).unwrap() == WaitableResult::Signaled);
+    t.join().unwrap();
+}
+
+#[test]
+fn error_source() {
+    use std::error::Error;
+
+    let err = EventError::FailedToWait(io::Error::from_raw_os_error(6));
+
+    let source = err.source().unwrap();
+    let source = source.downcast_ref::<io::Error>().unwrap();
+    assert_eq!(source.raw_os_error(), Some(6));
+
+    assert!(EventError::InvalidName { nul_position: 0 }
+        .source()
+        .is_none());
+}
+
+#[test]
+fn try_wait() {
+    // Auto.
+
+    let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+    assert!(e.try_wait().unwrap() == WaitableResult::Timeout);
+
+    e.set().unwrap();
+    assert!(e.try_wait().unwrap() == WaitableResult::Signaled);
+    assert!(e.try_wait().unwrap() == WaitableResult::Timeout); // Consumed.
+
+    // Manual.
+
+    let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+    assert!(e.try_wait().unwrap() == WaitableResult::Timeout);
+
+    e.set().unwrap();
+    assert!(e.try_wait().unwrap() == WaitableResult::Signaled);
+    assert!(e.try_wait().unwrap() == WaitableResult::Signaled); // Still signaled.
+
+    e.reset().unwrap();
+    assert!(e.try_wait().unwrap() == WaitableResult::Timeout);
+}
+
+#[test]
+fn drain() {
+    let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+    assert!(!e.drain().unwrap());
+
+    // Repeated sets coalesce.
+    e.set().unwrap();
+    e.set().unwrap();
+    assert!(e.drain().unwrap());
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    // Manual.
+
+    let e = Event::new_manual(true, None).unwrap(); // Signaled.
+    assert!(e.drain().unwrap());
+    assert!(!e.drain().unwrap());
+
+    let res = e.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+}
+
+#[test]
+fn wait_for_one_iter() {
+    let events: Vec<_> = (0..4)
+        .map(|_| Event::new_auto(false, None).unwrap()) // Not signaled.
+        .collect();
+
+    let res = crate::wait_for_one_iter(&events, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::Timeout);
+
+    events[2].set().unwrap();
+
+    let res = crate::wait_for_one_iter(&events, Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(2));
+
+    // The signal was consumed.
+    let res = crate::wait_for_one_iter(events.iter().skip(1), Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::Timeout);
+}
+
+#[test]
+fn wait_for_single() {
+    let mut e = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+    let res = wait_for_one(&[&e], Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::Timeout);
+
+    e.set().unwrap();
+
+    let res = wait_for_one(&[&e], Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(0));
+
+    e.set().unwrap();
+
+    let res = wait_for_all(&[&e], Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+
+    let res = wait_for_all(&[&e], Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    // Same as for multiple waitables.
+    e.close().unwrap();
+    wait_for_one(&[&e], Duration::from_millis(1)).err().unwrap(); // Must have failed - closed.
+}
+
+#[test]
+fn wait_for_one_ref() {
+    let e0 = Event::new_auto(false, None).unwrap(); // Not signaled.
+    let e1 = Event::new_auto(false, None).unwrap(); // Not signaled.
+    let w = [&e0 as &dyn Element, &e1 as _];
+
+    assert!(crate::wait_for_one_ref(&w, Duration::from_millis(1))
+        .unwrap()
+        .is_none());
+
+    e1.set().unwrap();
+
+    let (idx, signaled) = crate::wait_for_one_ref(&w, Duration::from_secs(1_000_000))
+        .unwrap()
+        .unwrap();
+    assert_eq!(idx, 1);
+    assert!(std::ptr::eq(
+        signaled as *const _ as *const (),
+        &e1 as *const _ as *const ()
+    ));
+}
+
+#[test]
+fn is_manual() {
+    assert!(Event::new_manual(false, None).unwrap().is_manual());
+    assert!(!Event::new_auto(false, None).unwrap().is_manual());
+
+    // Outlives the close.
+    let mut e = Event::new_manual(false, None).unwrap();
+    e.close().unwrap();
+    assert!(e.is_manual());
+}
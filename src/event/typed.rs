@@ -0,0 +1,234 @@
+use {
+    crate::{Event, EventError, WaitError, Waitable, WaitableResult},
+    std::time::Duration,
+};
+
+/// Auto reset event - gets reset when one awaiting thread is woken up.
+///
+/// Same as an auto reset [`Event`], but expresses the reset kind in the type,
+/// and does not expose the methods which only make sense for manual reset events.
+///
+/// [`Event`]: ../struct.Event.html
+#[derive(Debug)]
+pub struct AutoResetEvent(Event);
+
+/// Manual reset event - stays set / reset when [`set`] / [`reset`] is called on it.
+///
+/// Same as a manual reset [`Event`], but expresses the reset kind in the type,
+/// so APIs which require a manual reset event may say so in their signature.
+///
+/// [`set`]: #method.set
+/// [`reset`]: #method.reset
+/// [`Event`]: ../struct.Event.html
+#[derive(Debug)]
+pub struct ManualResetEvent(Event);
+
+impl AutoResetEvent {
+    /// Creates a new auto reset event (or tries to reuse based on `name`).
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - see [`Event::new_auto`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Event::new_auto`].
+    ///
+    /// [`Event::new_auto`]: ../struct.Event.html#method.new_auto
+    pub fn new<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<AutoResetEvent, EventError> {
+        Event::new_auto(set, name).map(AutoResetEvent)
+    }
+
+    /// Sets / signals the event.
+    ///
+    /// At most one waiting thread will be woken up.
+    pub fn set(&self) -> Result<(), EventError> {
+        self.0.set()
+    }
+
+    /// Checks whether the event is [`set`] without blocking, consuming the signal if it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    pub fn try_wait(&self) -> Result<WaitableResult, EventError> {
+        self.0.try_wait()
+    }
+
+    /// Returns the underlying [`Event`](../struct.Event.html).
+    pub fn as_event(&self) -> &Event {
+        &self.0
+    }
+
+    /// Unwraps the underlying [`Event`](../struct.Event.html).
+    pub fn into_event(self) -> Event {
+        self.0
+    }
+}
+
+impl ManualResetEvent {
+    /// Creates a new manual reset event (or tries to reuse based on `name`).
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - see [`Event::new_manual`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Event::new_manual`].
+    ///
+    /// [`Event::new_manual`]: ../struct.Event.html#method.new_manual
+    pub fn new<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<ManualResetEvent, EventError> {
+        Event::new_manual(set, name).map(ManualResetEvent)
+    }
+
+    /// Sets / signals the event. The event stays set until it is [`reset`].
+    ///
+    /// [`reset`]: #method.reset
+    pub fn set(&self) -> Result<(), EventError> {
+        self.0.set()
+    }
+
+    /// Resets the event.
+    pub fn reset(&self) -> Result<(), EventError> {
+        self.0.reset()
+    }
+
+    /// Releases all threads currently waiting on the event, which is then left reset.
+    /// See [`Event::pulse`] for the caveats.
+    ///
+    /// [`Event::pulse`]: ../struct.Event.html#method.pulse
+    #[cfg(windows)]
+    pub fn pulse(&self) -> Result<(), EventError> {
+        self.0.pulse()
+    }
+
+    /// Returns `true` if the event is set, without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn is_set(&self) -> Result<bool, EventError> {
+        self.0.try_wait().map(|res| res == WaitableResult::Signaled)
+    }
+
+    /// Returns the underlying [`Event`](../struct.Event.html).
+    pub fn as_event(&self) -> &Event {
+        &self.0
+    }
+
+    /// Unwraps the underlying [`Event`](../struct.Event.html).
+    pub fn into_event(self) -> Event {
+        self.0
+    }
+}
+
+macro_rules! impl_waitable {
+    ($ty:ident) => {
+        impl Waitable for $ty {
+            /// Blocks the thread until the event is [`set`] or the duration `d` expires.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the OS function fails.
+            ///
+            /// [`set`]: #method.set
+            fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+                self.0.wait(d)
+            }
+
+            /// Blocks the thread until the event is [`set`] or the timeout of `ms` milliseconds expires.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the OS function fails.
+            ///
+            /// [`set`]: #method.set
+            fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+                self.0.wait_raw_ms(ms)
+            }
+        }
+
+        #[cfg(windows)]
+        impl crate::WaitableExt for $ty {
+            /// Returns the raw handle / pointer to the waitable's OS object.
+            fn handle(&self) -> *mut () {
+                self.0.handle()
+            }
+        }
+
+        #[cfg(windows)]
+        impl std::os::windows::io::AsRawHandle for $ty {
+            /// Returns the raw handle to the event's OS object.
+            fn as_raw_handle(&self) -> std::os::windows::io::RawHandle {
+                self.0.as_raw_handle()
+            }
+        }
+
+        impl From<$ty> for Event {
+            fn from(event: $ty) -> Event {
+                event.0
+            }
+        }
+    };
+}
+
+impl_waitable!(AutoResetEvent);
+impl_waitable!(ManualResetEvent);
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{sync::Arc, thread},
+    };
+
+    #[test]
+    fn auto_reset() {
+        let e = AutoResetEvent::new(true, None).unwrap(); // Signaled.
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Reset by the previous wait.
+        assert!(e.try_wait().unwrap() == WaitableResult::Timeout);
+
+        e.set().unwrap();
+        assert!(e.try_wait().unwrap() == WaitableResult::Signaled);
+    }
+
+    #[test]
+    fn manual_reset() {
+        let e = Arc::new(ManualResetEvent::new(false, None).unwrap()); // Not signaled.
+        assert!(!e.is_set().unwrap());
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let e = e.clone();
+                thread::spawn(move || e.wait_infinite().unwrap())
+            })
+            .collect();
+
+        thread::sleep(Duration::from_millis(10));
+        e.set().unwrap();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        // Still set.
+        assert!(e.is_set().unwrap());
+
+        e.reset().unwrap();
+        assert!(!e.is_set().unwrap());
+
+        let e: Event = Arc::try_unwrap(e).unwrap().into();
+        assert!(e.wait(Duration::from_millis(1)).unwrap() == WaitableResult::Timeout);
+    }
+}
@@ -1,17 +1,124 @@
 use {
-    crate::{EventError, Waitable, WaitableExt, WaitableResult},
-    std::{ffi::CString, io, ptr, time::Duration},
+    crate::{
+        duration_to_timeout_ms,
+        event::{
+            callback::WaitRegistration,
+            poll::{PollRegistration, PollState},
+        },
+        ntdll::{query_event_is_manual, query_object_type},
+        waitable::{
+            close_handle, create_handle, duplicate_handle, namespaced_name, security_attributes,
+            wait_for_handle_with_strategy, Namespace, DEFAULT_CREATE_RETRIES,
+        },
+        EventError, WaitError, WaitStrategy, Waitable, WaitableExt, WaitableResult, WatchedResult,
+    },
+    std::os::windows::io::{AsRawHandle, RawHandle},
+    std::{
+        ffi::CString,
+        fmt::{Debug, Formatter},
+        io,
+        ops::BitOr,
+        ptr,
+        sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+        task::{Poll, Waker},
+        time::{Duration, Instant},
+    },
     winapi::{
-        shared::{minwindef::FALSE, winerror::WAIT_TIMEOUT},
+        shared::{
+            minwindef::{FALSE, MAX_PATH},
+            winerror::{ERROR_FILE_NOT_FOUND, WAIT_TIMEOUT},
+        },
         um::{
-            handleapi::CloseHandle,
-            synchapi::{CreateEventA, ResetEvent, SetEvent, WaitForSingleObject},
-            winbase::{INFINITE, WAIT_OBJECT_0},
-            winnt::HANDLE,
+            synchapi::{
+                CreateEventExA, OpenEventA, ResetEvent, SetEvent, WaitForSingleObject,
+                CREATE_EVENT_INITIAL_SET, CREATE_EVENT_MANUAL_RESET,
+            },
+            winbase::{PulseEvent, WAIT_OBJECT_0},
+            winnt::{EVENT_ALL_ACCESS, EVENT_MODIFY_STATE, HANDLE, SYNCHRONIZE},
         },
     },
 };
 
+#[cfg(feature = "async")]
+use crate::event::future::EventFuture;
+
+/// Options for creating an [`Event`] via [`new_auto_with_options`] / [`new_manual_with_options`].
+///
+/// Construct via `EventOptions { inheritable: true, ..Default::default() }`,
+/// so that code keeps compiling if more options are added.
+///
+/// [`Event`]: struct.Event.html
+/// [`new_auto_with_options`]: struct.Event.html#method.new_auto_with_options
+/// [`new_manual_with_options`]: struct.Event.html#method.new_manual_with_options
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EventOptions {
+    /// If `true`, the event handle is inherited by child processes created by the current process.
+    /// See [`SECURITY_ATTRIBUTES`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/legacy/aa379560(v=vs.85)) on MSDN.
+    pub inheritable: bool,
+    /// Access rights of the returned event handle. Full access by default.
+    pub access: EventAccess,
+    /// Number of times the event creation is retried after a short sleep if it failed with a transient error -
+    /// i.e. the system was temporarily out of memory / resources. Other errors are never retried.
+    /// `2` by default.
+    pub retries: u32,
+}
+
+impl Default for EventOptions {
+    fn default() -> Self {
+        Self {
+            inheritable: false,
+            access: EventAccess::default(),
+            retries: DEFAULT_CREATE_RETRIES,
+        }
+    }
+}
+
+/// Access rights of an event handle, combined via `|`.
+/// See [`Synchronization Object Security and Access Rights`](https://docs.microsoft.com/en-us/windows/win32/sync/synchronization-object-security-and-access-rights) on MSDN.
+///
+/// Operations the handle lacks the rights for fail with an error for which
+/// [`EventError::is_access_denied`] returns `true`.
+///
+/// [`EventError::is_access_denied`]: enum.EventError.html#method.is_access_denied
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EventAccess(u32);
+
+impl EventAccess {
+    /// `SYNCHRONIZE` - the event may be waited on.
+    pub const SYNCHRONIZE: EventAccess = EventAccess(SYNCHRONIZE);
+    /// `EVENT_MODIFY_STATE` - the event may be [`set`] / [`reset`].
+    ///
+    /// [`set`]: struct.Event.html#method.set
+    /// [`reset`]: struct.Event.html#method.reset
+    pub const MODIFY_STATE: EventAccess = EventAccess(EVENT_MODIFY_STATE);
+    /// `EVENT_ALL_ACCESS` - all access rights.
+    pub const ALL: EventAccess = EventAccess(EVENT_ALL_ACCESS);
+
+    /// Returns `true` if all rights in `other` are included in these rights.
+    pub fn contains(self, other: EventAccess) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the raw OS access mask.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl Default for EventAccess {
+    fn default() -> Self {
+        EventAccess::ALL
+    }
+}
+
+impl BitOr for EventAccess {
+    type Output = EventAccess;
+
+    fn bitor(self, other: EventAccess) -> EventAccess {
+        EventAccess(self.0 | other.0)
+    }
+}
+
 /// Waitable event wrapper.
 /// See [`event`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventa) on MSDN.
 ///
@@ -20,406 +127,2666 @@ use {
 ///
 /// Manual event: stays set/reset when [`set`] / [`reset`] is called on it.
 ///
-/// Closes the owned OS event handle when dropped.
+/// Closes the owned OS event handle when dropped, unless it was already [`close`]d.
 ///
 /// [`set`]: #method.set
 /// [`reset`]: #method.reset
+/// [`close`]: #method.close
 pub struct Event {
-    handle: HANDLE,
+    /// `None` if the event was [`close`](#method.close)d.
+    handle: Option<HANDLE>,
+    manual: bool,
+    label: Option<&'static str>,
+    #[cfg(feature = "deterministic")]
+    scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    /// OS id of the thread which last [`set`](#method.set) the event; `0` if none.
+    #[cfg(feature = "diagnostics")]
+    last_signaler: std::sync::atomic::AtomicU32,
+    /// Number of threads still to be released by [`set_n`](#method.set_n).
+    pending_releases: AtomicUsize,
+    /// OS thread pool wait registered by [`poll_wait`](#method.poll_wait), if any.
+    poll_registration: PollRegistration,
+    wait_strategy: WaitStrategy,
 }
 
-impl Event {
-    /// Creates a new auto reset event (or tries to reuse based on `name`).
-    ///
-    /// `set` - gives the initial state of the event.
-    /// `name` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventw).
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the OS event creation failed, or if `name` was invalid - e.g. contained nul bytes.
-    pub fn new_auto<'n, N: Into<Option<&'n str>>>(set: bool, name: N) -> Result<Event, EventError> {
-        Event::new(false, set, name.into())
-    }
+impl Event {
+    /// Creates a new auto reset event (or tries to reuse based on `name`).
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventw).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed, or if `name` was invalid - e.g. contained nul bytes or exceeded `MAX_PATH`.
+    pub fn new_auto<'n, N: Into<Option<&'n str>>>(set: bool, name: N) -> Result<Event, EventError> {
+        Event::new(false, set, name.into())
+    }
+
+    /// Creates a new manual reset event (or tries to reuse based on `name`).
+    ///
+    /// `set` - gives the initial state of the event.
+    /// `name` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventa).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed, or if `name` was invalid - e.g. contained nul bytes or exceeded `MAX_PATH`.
+    pub fn new_manual<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<Event, EventError> {
+        Event::new(true, set, name.into())
+    }
+
+    /// Same as [`new_auto`], but also returns `true` if an existing named event was reused
+    /// rather than a new one created - e.g. for leader election, where exactly one process must be the creator.
+    ///
+    /// A reused event keeps its current state and kind - the initial state `set` is ignored,
+    /// and [`is_manual`](#method.is_manual) reports the kind the event was originally created with.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_auto`].
+    ///
+    /// [`new_auto`]: #method.new_auto
+    pub fn new_auto_ex<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<(Event, bool), EventError> {
+        Event::new_impl(false, set, name.into(), &EventOptions::default())
+    }
+
+    /// Same as [`new_manual`], but also returns `true` if an existing named event was reused
+    /// rather than a new one created - e.g. for leader election, where exactly one process must be the creator.
+    ///
+    /// A reused event keeps its current state and kind - the initial state `set` is ignored,
+    /// and [`is_manual`](#method.is_manual) reports the kind the event was originally created with.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_manual`].
+    ///
+    /// [`new_manual`]: #method.new_manual
+    pub fn new_manual_ex<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+    ) -> Result<(Event, bool), EventError> {
+        Event::new_impl(true, set, name.into(), &EventOptions::default())
+    }
+
+    /// Same as [`new_auto`], but the event is created with the given `options` -
+    /// e.g. with an inheritable handle for IPC with a child process.
+    ///
+    /// The `options` are ignored if an existing named event is reused.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_auto`].
+    ///
+    /// [`new_auto`]: #method.new_auto
+    pub fn new_auto_with_options<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+        options: &EventOptions,
+    ) -> Result<Event, EventError> {
+        Event::new_impl(false, set, name.into(), options).map(|(event, _)| event)
+    }
+
+    /// Same as [`new_manual`], but the event is created with the given `options` -
+    /// e.g. with an inheritable handle for IPC with a child process.
+    ///
+    /// The `options` are ignored if an existing named event is reused.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_manual`].
+    ///
+    /// [`new_manual`]: #method.new_manual
+    pub fn new_manual_with_options<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+        options: &EventOptions,
+    ) -> Result<Event, EventError> {
+        Event::new_impl(true, set, name.into(), options).map(|(event, _)| event)
+    }
+
+    /// Same as [`new_auto`], but retries the event creation up to `retries` times
+    /// if it failed with a transient error - i.e. the system was temporarily out of memory / resources,
+    /// e.g. while many objects are created in a burst. Other errors are never retried.
+    ///
+    /// [`new_auto`] retries the event creation twice.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_auto`].
+    ///
+    /// [`new_auto`]: #method.new_auto
+    pub fn new_auto_with_retries<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+        retries: u32,
+    ) -> Result<Event, EventError> {
+        let options = EventOptions {
+            retries,
+            ..Default::default()
+        };
+
+        Event::new_auto_with_options(set, name, &options)
+    }
+
+    /// Same as [`new_manual`], but retries the event creation up to `retries` times
+    /// if it failed with a transient error - i.e. the system was temporarily out of memory / resources,
+    /// e.g. while many objects are created in a burst. Other errors are never retried.
+    ///
+    /// [`new_manual`] retries the event creation twice.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_manual`].
+    ///
+    /// [`new_manual`]: #method.new_manual
+    pub fn new_manual_with_retries<'n, N: Into<Option<&'n str>>>(
+        set: bool,
+        name: N,
+        retries: u32,
+    ) -> Result<Event, EventError> {
+        let options = EventOptions {
+            retries,
+            ..Default::default()
+        };
+
+        Event::new_manual_with_options(set, name, &options)
+    }
+
+    /// Creates a new non-signaled manual reset event (or tries to reuse based on `name`).
+    ///
+    /// Unlike [`new_manual`], if an existing named event is reused (which ignores the initial state
+    /// requested at creation), explicitly [`resets`] it, so the caller always gets a non-signaled event.
+    ///
+    /// Note that this races with other threads / processes opening and setting the same named event -
+    /// the event may be reset right after they set it.
+    ///
+    /// `name` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventa).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation or reset failed, or if `name` was invalid - e.g. contained nul bytes or exceeded `MAX_PATH`.
+    ///
+    /// [`new_manual`]: #method.new_manual
+    /// [`resets`]: #method.reset
+    pub fn new_manual_ensure_reset<'n, N: Into<Option<&'n str>>>(
+        name: N,
+    ) -> Result<Event, EventError> {
+        let (event, already_existed) =
+            Event::new_impl(true, false, name.into(), &EventOptions::default())?;
+
+        if already_existed {
+            event.reset()?;
+        }
+
+        Ok(event)
+    }
+
+    /// Same as [`new_auto`], but creates (or reuses) the event named `name` in the global namespace,
+    /// shared by all sessions - e.g. for IPC between a service and user applications.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Global\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_auto`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Local\`.
+    ///
+    /// [`new_auto`]: #method.new_auto
+    /// [`ConflictingNamespace`]: enum.EventError.html#variant.ConflictingNamespace
+    pub fn new_auto_global(set: bool, name: &str) -> Result<Event, EventError> {
+        Event::new_namespaced(false, set, Namespace::Global, name)
+    }
+
+    /// Same as [`new_auto`], but creates (or reuses) the event named `name` in the namespace of the current session.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Local\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_auto`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Global\`.
+    ///
+    /// [`new_auto`]: #method.new_auto
+    /// [`ConflictingNamespace`]: enum.EventError.html#variant.ConflictingNamespace
+    pub fn new_auto_local(set: bool, name: &str) -> Result<Event, EventError> {
+        Event::new_namespaced(false, set, Namespace::Local, name)
+    }
+
+    /// Same as [`new_manual`], but creates (or reuses) the event named `name` in the global namespace,
+    /// shared by all sessions - e.g. for IPC between a service and user applications.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Global\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_manual`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Local\`.
+    ///
+    /// [`new_manual`]: #method.new_manual
+    /// [`ConflictingNamespace`]: enum.EventError.html#variant.ConflictingNamespace
+    pub fn new_manual_global(set: bool, name: &str) -> Result<Event, EventError> {
+        Event::new_namespaced(true, set, Namespace::Global, name)
+    }
+
+    /// Same as [`new_manual`], but creates (or reuses) the event named `name` in the namespace of the current session.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Local\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new_manual`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Global\`.
+    ///
+    /// [`new_manual`]: #method.new_manual
+    /// [`ConflictingNamespace`]: enum.EventError.html#variant.ConflictingNamespace
+    pub fn new_manual_local(set: bool, name: &str) -> Result<Event, EventError> {
+        Event::new_namespaced(true, set, Namespace::Local, name)
+    }
+
+    /// Opens an existing named event.
+    /// See [`OpenEvent`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-openeventa) on MSDN.
+    ///
+    /// Unlike [`new_auto`] / [`new_manual`], never creates the event,
+    /// so a client process may attach to an event published by a server process.
+    ///
+    /// Determines whether it's an auto or manual reset event.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`] if no event named `name` exists.
+    /// Returns an error if the OS function fails, or if `name` was invalid - e.g. empty, contained nul bytes or exceeded `MAX_PATH`.
+    ///
+    /// [`new_auto`]: #method.new_auto
+    /// [`new_manual`]: #method.new_manual
+    /// [`NotFound`]: enum.EventError.html#variant.NotFound
+    pub fn open(name: &str) -> Result<Event, EventError> {
+        use EventError::*;
+
+        if name.is_empty() {
+            return Err(InvalidName { nul_position: 0 });
+        }
+
+        if name.len() > MAX_PATH {
+            return Err(NameTooLong { len: name.len() });
+        }
+
+        let c_name = CString::new(name).map_err(|err| InvalidName {
+            nul_position: err.nul_position(),
+        })?;
+
+        let handle = unsafe { OpenEventA(EVENT_ALL_ACCESS, FALSE, c_name.as_ptr()) };
+
+        if handle.is_null() {
+            let err = io::Error::last_os_error();
+
+            return Err(if err.raw_os_error() == Some(ERROR_FILE_NOT_FOUND as i32) {
+                NotFound
+            } else {
+                FailedToOpen(err)
+            });
+        }
+
+        let manual = match query_event_is_manual(handle) {
+            Ok(manual) => manual,
+            Err(err) => {
+                let _ = close_handle(handle);
+                return Err(FailedToOpen(err));
+            }
+        };
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(handle as *mut (), Some(name), Event::object_kind(manual));
+
+        Ok(Event::from_raw_parts(handle, manual))
+    }
+
+    /// Takes ownership of the raw event `handle` received from another process
+    /// (e.g. duplicated into the current process via `DuplicateHandle` and sent over a pipe).
+    ///
+    /// Validates that `handle` refers to an event, and determines whether it's an auto or manual reset event.
+    ///
+    /// # Safety
+    ///
+    /// If `handle` refers to an event, it must be owned by the caller, and must not be used or closed
+    /// by anyone else afterwards - the returned event closes it when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not a valid handle, or if it does not refer to an event.
+    /// The handle is not closed on error.
+    pub unsafe fn from_ipc_handle(handle: isize) -> Result<Event, EventError> {
+        use EventError::*;
+
+        let handle = handle as HANDLE;
+
+        if query_object_type(handle).map_err(InvalidHandle)? != "Event" {
+            return Err(WrongObjectType);
+        }
+
+        let manual = query_event_is_manual(handle).map_err(InvalidHandle)?;
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(handle as *mut (), None, Event::object_kind(manual));
+
+        Ok(Event::from_raw_parts(handle, manual))
+    }
+
+    /// Takes ownership of the raw event `handle` created elsewhere in the current process
+    /// (e.g. by foreign code, or returned by [`into_raw_handle`]).
+    ///
+    /// The returned event closes the `handle` when dropped - the caller must not close it afterwards.
+    ///
+    /// Determines whether it's an auto or manual reset event.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid event handle owned by the caller.
+    /// Use [`from_ipc_handle`] to validate a handle of unknown provenance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventError::InvalidHandle`] if the OS fails to query the event's reset kind.
+    /// The `handle` is then not taken ownership of - the caller remains responsible for closing it.
+    ///
+    /// [`into_raw_handle`]: #method.into_raw_handle
+    /// [`from_ipc_handle`]: #method.from_ipc_handle
+    /// [`EventError::InvalidHandle`]: enum.EventError.html#variant.InvalidHandle
+    pub unsafe fn from_raw_handle(handle: *mut ()) -> Result<Event, EventError> {
+        let handle = handle as HANDLE;
+
+        let manual = query_event_is_manual(handle).map_err(EventError::InvalidHandle)?;
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(handle as *mut (), None, Event::object_kind(manual));
+
+        Ok(Event::from_raw_parts(handle, manual))
+    }
+
+    /// Releases ownership of the event's raw OS handle without closing it, and returns it.
+    /// The caller becomes responsible for closing the handle (e.g. by passing it to [`from_raw_handle`]).
+    ///
+    /// Cancels the thread pool wait registered by [`poll_wait`], if any.
+    /// Returns a null handle if the event was [`close`]d.
+    ///
+    /// [`from_raw_handle`]: #method.from_raw_handle
+    /// [`poll_wait`]: #method.poll_wait
+    /// [`close`]: #method.close
+    pub fn into_raw_handle(mut self) -> *mut () {
+        // The thread pool must not wait on the handle once it's no longer owned.
+        let _ = self.cancel_poll_wait();
+
+        match self.handle.take() {
+            Some(handle) => {
+                #[cfg(feature = "diagnostics")]
+                crate::diagnostics::deregister(handle as *mut ());
+
+                handle as *mut ()
+            }
+            // `Drop` does not close the handle which was taken.
+            None => ptr::null_mut(),
+        }
+    }
+
+    /// Returns a new event owning a duplicate of the event's OS handle.
+    /// See [`DuplicateHandle`](https://docs.microsoft.com/en-us/windows/win32/api/handleapi/nf-handleapi-duplicatehandle) on MSDN.
+    ///
+    /// Both events refer to the same OS event, with the same access rights,
+    /// and each closes its own handle when dropped.
+    ///
+    /// The label and wait strategy are copied. Releases buffered by [`set_n`] are not shared with the duplicate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event was [`close`]d, or if the OS function fails.
+    ///
+    /// [`set_n`]: #method.set_n
+    /// [`close`]: #method.close
+    pub fn try_clone(&self) -> Result<Event, EventError> {
+        let handle =
+            duplicate_handle(self.live_handle()?).map_err(EventError::FailedToDuplicate)?;
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            crate::diagnostics::name_of(self.live_handle()? as *mut ()).as_deref(),
+            Event::object_kind(self.manual),
+        );
+
+        let mut event = Event::from_raw_parts(handle, self.manual);
+
+        event.label = self.label;
+        event.wait_strategy = self.wait_strategy;
+
+        #[cfg(feature = "deterministic")]
+        {
+            event.scheduler = self.scheduler.clone();
+        }
+
+        Ok(event)
+    }
+
+    /// Sets / signals the event.
+    ///
+    /// Auto event: at most one waiting thread will be woken up.
+    /// Manual event: stays set / signaled until it is [`reset`].
+    ///
+    /// [`reset`]: #method.reset
+    pub fn set(&self) -> Result<(), EventError> {
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_signal(self.live_handle()? as *mut ());
+
+        self.set_impl()?;
+
+        #[cfg(feature = "diagnostics")]
+        self.last_signaler.store(
+            unsafe { winapi::um::processthreadsapi::GetCurrentThreadId() },
+            Ordering::Relaxed,
+        );
+
+        Ok(())
+    }
+
+    /// Auto event: releases exactly `n` waiting threads, one at a time.
+    /// If fewer than `n` threads are currently waiting, the remaining releases are buffered,
+    /// and are consumed by the threads which wait on the event later.
+    /// Manual event: same as [`set`].
+    ///
+    /// Implemented by each thread released from [`wait`] / [`wait_infinite`] / [`poll_wait`] on this `Event`
+    /// setting the event again while there are releases remaining. The remaining releases are counted
+    /// by this `Event` object alone, so only its own wait methods drain them. A thread woken up via
+    /// any other path - [`wait_for_one`] / [`wait_for_all`], [`register_callback`], a [`try_clone`]d handle
+    /// or another process opening the named event - consumes the event's signal without setting it again,
+    /// so the remaining releases stay buffered until a later wait on this `Event` is woken up by a [`set`].
+    /// Threads woken up by a concurrent call to [`set`] also consume a release.
+    ///
+    /// Always sets the event, so releases left buffered this way never block later calls to `set_n`.
+    ///
+    /// [`set`]: #method.set
+    /// [`wait`]: #method.wait
+    /// [`wait_infinite`]: #method.wait_infinite
+    /// [`poll_wait`]: #method.poll_wait
+    /// [`wait_for_one`]: fn.wait_for_one.html
+    /// [`wait_for_all`]: fn.wait_for_all.html
+    /// [`register_callback`]: #method.register_callback
+    /// [`try_clone`]: #method.try_clone
+    pub fn set_n(&self, n: usize) -> Result<(), EventError> {
+        if self.manual {
+            return self.set();
+        }
+
+        if n == 0 {
+            return Ok(());
+        }
+
+        // Set even if there are outstanding releases -
+        // their signal may have been consumed without setting the event again.
+        self.pending_releases.fetch_add(n, Ordering::AcqRel);
+        self.set()
+    }
+
+    /// Resets the manual reset event.
+    pub fn reset(&self) -> Result<(), EventError> {
+        let result = unsafe { ResetEvent(self.live_handle()?) };
+
+        if result == FALSE {
+            Err(EventError::FailedToReset(io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Atomically sets and resets the event.
+    /// See [`PulseEvent`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-pulseevent) on MSDN.
+    ///
+    /// Manual event: releases all threads currently waiting on the event, which is then left reset.
+    /// Auto event: releases at most one thread currently waiting on the event, which is then left reset.
+    ///
+    /// NOTE - unreliable by design: only threads which are blocked in the wait at the moment of the pulse
+    /// are released. Threads which are about to wait, or which are temporarily removed from the wait state
+    /// by the OS (e.g. by a kernel-mode APC), miss the pulse. Prefer [`set`] / [`reset`] or a [`Semaphore`]
+    /// where every waiter must be released.
+    ///
+    /// [`set`]: #method.set
+    /// [`reset`]: #method.reset
+    /// [`Semaphore`]: struct.Semaphore.html
+    pub fn pulse(&self) -> Result<(), EventError> {
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_signal(self.live_handle()? as *mut ());
+
+        let result = unsafe { PulseEvent(self.live_handle()?) };
+
+        if result == FALSE {
+            return Err(EventError::FailedToSet(io::Error::last_os_error()));
+        }
+
+        #[cfg(feature = "diagnostics")]
+        self.last_signaler.store(
+            unsafe { winapi::um::processthreadsapi::GetCurrentThreadId() },
+            Ordering::Relaxed,
+        );
+
+        Ok(())
+    }
+
+    /// Checks whether the event is [`set`] without blocking -
+    /// same as waiting on the event with a zero timeout.
+    ///
+    /// Returns `Signaled` if the event is set, `Timeout` otherwise.
+    ///
+    /// Auto event: like any other wait, a successful `try_wait` consumes the signal -
+    /// i.e. resets the event, so it is not observed by the next waiter.
+    /// Manual event: the event stays set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    pub fn try_wait(&self) -> Result<WaitableResult, EventError> {
+        self.wait_impl(0)
+    }
+
+    /// Consumes and discards the pending signal of the auto event, if any, without blocking.
+    /// Also discards the releases still buffered by [`set_n`].
+    /// Manual event: same as [`reset`].
+    ///
+    /// Returns `true` if the event was set.
+    ///
+    /// Lets the caller clear a stale signal before starting a fresh wait phase,
+    /// which a new waiter would otherwise immediately consume.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set_n`]: #method.set_n
+    /// [`reset`]: #method.reset
+    pub fn drain(&self) -> Result<bool, EventError> {
+        let handle = self.live_handle()?;
+
+        self.pending_releases.store(0, Ordering::Release);
+
+        // Bypasses `wait_impl` - the signal is discarded, not consumed by a waiter.
+        let was_set = match unsafe { WaitForSingleObject(handle, 0) } {
+            WAIT_OBJECT_0 => true,
+            WAIT_TIMEOUT => false,
+            _ => return Err(EventError::FailedToWait(io::Error::last_os_error())),
+        };
+
+        if self.manual && was_set {
+            self.reset()?;
+        }
+
+        Ok(was_set)
+    }
+
+    /// Closes the owned OS event handle.
+    ///
+    /// All subsequent operations on the event fail with [`EventError::Closed`];
+    /// the event's [`handle`] becomes null.
+    /// Closing an already closed event does nothing and succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cancelling the wait registered by [`poll_wait`] or closing the handle failed;
+    /// the event is closed regardless.
+    ///
+    /// [`EventError::Closed`]: enum.EventError.html#variant.Closed
+    /// [`handle`]: trait.WaitableExt.html#tymethod.handle
+    /// [`poll_wait`]: #method.poll_wait
+    pub fn close(&mut self) -> Result<(), EventError> {
+        let handle = match self.handle {
+            Some(handle) => handle,
+            None => return Ok(()),
+        };
+
+        // The thread pool must not wait on the handle once it's closed.
+        // The handle is closed even if this fails, so it's never leaked.
+        let cancelled = self.cancel_poll_wait();
+        self.handle = None;
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::deregister(handle as *mut ());
+
+        let closed = close_handle(handle).map_err(EventError::FailedToClose);
+
+        // Reports the first error.
+        cancelled.and(closed)
+    }
+
+    /// Returns `true` if the event was [`close`]d.
+    ///
+    /// [`close`]: #method.close
+    pub fn is_closed(&self) -> bool {
+        self.handle.is_none()
+    }
+
+    /// Returns the event's raw OS handle value as an integer, e.g. for use as a `HashMap` key identifying the event.
+    /// `0` if the event was [`close`]d.
+    ///
+    /// Unique among the open events of the process, as the OS only reuses a handle value after the handle is closed.
+    /// Duplicates of the event (e.g. via [`try_clone`]) and the same named event opened again
+    /// have different handle values, hence different ids, despite referring to the same OS object.
+    ///
+    /// NOTE - this is why the event does not implement `PartialEq` / `Hash` itself:
+    /// equality of handle values is not equality of the OS objects.
+    ///
+    /// [`close`]: #method.close
+    /// [`try_clone`]: #method.try_clone
+    pub fn id(&self) -> usize {
+        self.handle.unwrap_or(ptr::null_mut()) as usize
+    }
+
+    /// Returns `true` if the event is a manual reset event, `false` if it is an auto reset event.
+    ///
+    /// For a reused / opened named event, this is the kind the event was originally created with,
+    /// which may differ from the requested one.
+    pub fn is_manual(&self) -> bool {
+        self.manual
+    }
+
+    /// Binds a human-readable `label` to the event, used in its `Debug` output.
+    ///
+    /// Unlike the OS object name, the label is purely for the user's own logging / debugging,
+    /// and does not affect the OS object in any way.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Returns the label bound to the event via [`with_label`], if any.
+    ///
+    /// [`with_label`]: #method.with_label
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// Makes the event's [`wait`] / [`wait_infinite`] methods wait using the `strategy`
+    /// instead of the default [`WaitStrategy::Blocking`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `strategy`'s parameters are invalid - e.g. the spin count is out of range.
+    ///
+    /// [`wait`]: #method.wait
+    /// [`wait_infinite`]: #method.wait_infinite
+    /// [`WaitStrategy::Blocking`]: enum.WaitStrategy.html#variant.Blocking
+    pub fn with_wait_strategy(mut self, strategy: WaitStrategy) -> Result<Self, EventError> {
+        if !strategy.is_valid() {
+            return Err(EventError::InvalidWaitStrategy);
+        }
+
+        self.wait_strategy = strategy;
+        Ok(self)
+    }
+
+    /// Returns the strategy the event's [`wait`] / [`wait_infinite`] methods wait with.
+    ///
+    /// [`wait`]: #method.wait
+    /// [`wait_infinite`]: #method.wait_infinite
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait_strategy
+    }
+
+    /// Blocks the thread until the event is [`set`], the duration `d` expires,
+    /// or the `watched` value no longer equals `expected`.
+    ///
+    /// The event is waited on in short bounded intervals, re-checking the `watched` value in between,
+    /// so a change may be noticed up to a few milliseconds late.
+    /// The `watched` value is checked before the first wait and takes priority - if it changed,
+    /// `Changed` is returned even if the event is also set, and the event is left as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    pub fn wait_unless_changed(
+        &self,
+        watched: &AtomicU64,
+        expected: u64,
+        d: Duration,
+    ) -> Result<WatchedResult, EventError> {
+        const MIN_INTERVAL: Duration = Duration::from_millis(1);
+        const MAX_INTERVAL: Duration = Duration::from_millis(10);
+
+        let deadline = Instant::now().checked_add(d);
+        let mut interval = MIN_INTERVAL;
+
+        loop {
+            if watched.load(Ordering::Acquire) != expected {
+                return Ok(WatchedResult::Changed);
+            }
+
+            // `None` if the deadline overflows - i.e. the wait never times out.
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+            let ms = duration_to_timeout_ms(remaining.map_or(interval, |r| interval.min(r)));
+
+            if self.wait_impl(ms)? == WaitableResult::Signaled {
+                return Ok(WatchedResult::Signaled);
+            }
+
+            if matches!(remaining, Some(remaining) if remaining <= interval) {
+                return Ok(WatchedResult::Timeout);
+            }
+
+            interval = (interval * 2).min(MAX_INTERVAL);
+        }
+    }
+
+    /// Checks whether the event is [`set`] without blocking; if it's not,
+    /// arranges for the `waker` to be woken when it is.
+    ///
+    /// A lower-level building block for custom executors than a `Future`:
+    ///
+    /// - returns `Ready(Signaled)` if the event is set (and, for an auto event, resets it),
+    ///   cancelling the previous registration, if any - i.e. the signal is consumed exactly once;
+    /// - otherwise registers a one-shot OS thread pool wait on the event
+    ///   (see [`RegisterWaitForSingleObject`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-registerwaitforsingleobject) on MSDN)
+    ///   which wakes the `waker` when the event is set, and returns `Pending`.
+    ///
+    /// Registration is idempotent: while a registered wait is pending, subsequent calls only replace its waker
+    /// (unless it [`will_wake`] the same task). Once woken, the caller polls again to get `Ready`.
+    ///
+    /// NOTE - the registered wait itself consumes the auto event's signal when it's set,
+    /// so the event must be polled again after the waker is woken for the signal not to be lost.
+    /// Call [`cancel_poll_wait`] to stop polling the event (e.g. when the polling task is dropped);
+    /// the registration is also cancelled when the event is [`close`]d / dropped.
+    ///
+    /// Meant to be polled by one task at a time - concurrent polls share a single registered wait,
+    /// so only the waker of the last one is woken.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    /// [`will_wake`]: https://doc.rust-lang.org/std/task/struct.Waker.html#method.will_wake
+    /// [`cancel_poll_wait`]: #method.cancel_poll_wait
+    /// [`close`]: #method.close
+    pub fn poll_wait(&self, waker: &Waker) -> Poll<Result<WaitableResult, EventError>> {
+        match self.poll_wait_impl(waker) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(res)) => Poll::Ready(Ok(res)),
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+
+    /// Cancels the OS thread pool wait registered by [`poll_wait`], if any,
+    /// blocking until the `waker` call completes if it is currently running.
+    ///
+    /// Returns `true` if the registered wait had already consumed the event's signal -
+    /// the caller may then treat the event as signaled, or [`set`] it again for other waiters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`poll_wait`]: #method.poll_wait
+    /// [`set`]: #method.set
+    pub fn cancel_poll_wait(&self) -> Result<bool, EventError> {
+        let fired = self
+            .poll_registration
+            .cancel()
+            .map_err(EventError::FailedToWait)?;
+
+        if fired && !self.manual {
+            self.release_next()?;
+        }
+
+        Ok(fired)
+    }
+
+    /// Returns a future which resolves when the event is [`set`] (and, for an auto event, resets it).
+    ///
+    /// Waits on the OS thread pool via [`poll_wait`] instead of blocking a thread,
+    /// so may be awaited in any executor (e.g. `tokio`, `async-std`).
+    /// Dropping the future before it resolves cancels the wait - see [`EventFuture`].
+    ///
+    /// Meant to be awaited by one task at a time - see [`poll_wait`].
+    ///
+    /// # Errors
+    ///
+    /// The future resolves to an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    /// [`poll_wait`]: #method.poll_wait
+    /// [`EventFuture`]: struct.EventFuture.html
+    #[cfg(feature = "async")]
+    pub fn wait_async(&self) -> EventFuture<'_> {
+        EventFuture::new(self)
+    }
+
+    /// Registers the `callback` to be called on the OS thread pool every time the event is [`set`],
+    /// instead of blocking a thread on the event.
+    /// See [`RegisterWaitForSingleObject`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-registerwaitforsingleobject) on MSDN.
+    ///
+    /// Scales to many events, as the OS thread pool waits for many objects per thread.
+    ///
+    /// Auto event: each [`set`] consumes the event's signal and calls the `callback` once.
+    /// Manual event: the `callback` is called repeatedly for as long as the event stays set,
+    /// so the `callback` should [`reset`] it.
+    ///
+    /// Calls to the `callback` are serialized. Panics in the `callback` are caught and ignored.
+    ///
+    /// The callback is called until the returned [`WaitRegistration`] is dropped.
+    /// The registration holds its own duplicate of the event handle, so may outlive the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`set`]: #method.set
+    /// [`reset`]: #method.reset
+    /// [`WaitRegistration`]: struct.WaitRegistration.html
+    pub fn register_callback(
+        &self,
+        callback: Box<dyn FnMut() + Send>,
+    ) -> Result<WaitRegistration, EventError> {
+        let handle =
+            duplicate_handle(self.live_handle()?).map_err(EventError::FailedToDuplicate)?;
+
+        WaitRegistration::new(handle, callback).map_err(EventError::FailedToRegister)
+    }
+
+    /// Returns the OS id of the thread which last [`set`] the event, if any.
+    ///
+    /// NOTE - inherently racy if the event is set by multiple threads: by the time a woken up thread
+    /// calls this, another thread may have set the event again, so the returned thread
+    /// is not necessarily the one which woke up the caller.
+    ///
+    /// [`set`]: #method.set
+    #[cfg(feature = "diagnostics")]
+    pub fn last_signaler_thread(&self) -> Option<u32> {
+        match self.last_signaler.load(Ordering::Relaxed) {
+            0 => None,
+            thread_id => Some(thread_id),
+        }
+    }
+
+    /// Routes the event's waits / sets through the `scheduler` instead of the default [`OsScheduler`].
+    ///
+    /// [`OsScheduler`]: deterministic/struct.OsScheduler.html
+    #[cfg(feature = "deterministic")]
+    pub fn with_scheduler(
+        mut self,
+        scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    ) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    fn new(manual: bool, set: bool, name: Option<&str>) -> Result<Event, EventError> {
+        Event::new_impl(manual, set, name, &EventOptions::default()).map(|(event, _)| event)
+    }
+
+    fn new_namespaced(
+        manual: bool,
+        set: bool,
+        namespace: Namespace,
+        name: &str,
+    ) -> Result<Event, EventError> {
+        use EventError::*;
+
+        // An unnamed event has no namespace.
+        if name.is_empty() {
+            return Err(InvalidName { nul_position: 0 });
+        }
+
+        let name = namespaced_name(namespace, name).ok_or(ConflictingNamespace)?;
+
+        Event::new(manual, set, Some(&name))
+    }
+
+    /// Also returns `true` if the named event already existed.
+    fn new_impl(
+        manual: bool,
+        set: bool,
+        name: Option<&str>,
+        options: &EventOptions,
+    ) -> Result<(Event, bool), EventError> {
+        use EventError::*;
+
+        let mut flags = 0;
+
+        if manual {
+            flags |= CREATE_EVENT_MANUAL_RESET;
+        }
+
+        if set {
+            flags |= CREATE_EVENT_INITIAL_SET;
+        }
+
+        let name = name.filter(|name| !name.is_empty());
+
+        #[cfg(feature = "diagnostics")]
+        let diagnostics_name = name;
+
+        let name = if let Some(name) = name {
+            if name.len() > MAX_PATH {
+                return Err(NameTooLong { len: name.len() });
+            }
+
+            Some(CString::new(name).map_err(|err| InvalidName {
+                nul_position: err.nul_position(),
+            })?)
+        } else {
+            None
+        };
+
+        let name = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+
+        let mut security_attributes = security_attributes(options.inheritable);
+
+        let (handle, already_existed) = create_handle(options.retries, || unsafe {
+            CreateEventExA(&mut security_attributes, name, flags, options.access.bits())
+        })
+        .map_err(FailedToCreate)?;
+
+        // A reused named event keeps the kind it was originally created with.
+        let manual = if already_existed {
+            match query_event_is_manual(handle) {
+                Ok(manual) => manual,
+                Err(err) => {
+                    let _ = close_handle(handle);
+                    return Err(FailedToCreate(err));
+                }
+            }
+        } else {
+            manual
+        };
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            diagnostics_name,
+            Event::object_kind(manual),
+        );
+
+        Ok((Event::from_raw_parts(handle, manual), already_existed))
+    }
+
+    fn from_raw_parts(handle: HANDLE, manual: bool) -> Event {
+        Event {
+            handle: Some(handle),
+            manual,
+            label: None,
+            #[cfg(feature = "deterministic")]
+            scheduler: crate::deterministic::os_scheduler(),
+            #[cfg(feature = "diagnostics")]
+            last_signaler: std::sync::atomic::AtomicU32::new(0),
+            pending_releases: AtomicUsize::new(0),
+            poll_registration: PollRegistration::new(),
+            wait_strategy: WaitStrategy::Blocking,
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn object_kind(manual: bool) -> crate::diagnostics::ObjectKind {
+        if manual {
+            crate::diagnostics::ObjectKind::ManualEvent
+        } else {
+            crate::diagnostics::ObjectKind::AutoEvent
+        }
+    }
+
+    fn set_impl(&self) -> Result<(), EventError> {
+        let result = unsafe { SetEvent(self.live_handle()?) };
+
+        if result == FALSE {
+            Err(EventError::FailedToSet(io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn live_handle(&self) -> Result<HANDLE, EventError> {
+        self.handle.ok_or(EventError::Closed)
+    }
+
+    pub(crate) fn wait_duration_impl(&self, d: Duration) -> Result<WaitableResult, EventError> {
+        self.wait_impl(duration_to_timeout_ms(d))
+    }
+
+    pub(crate) fn wait_impl(&self, ms: u32) -> Result<WaitableResult, EventError> {
+        let handle = self.live_handle()?;
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_wait(handle as *mut ());
+
+        let result = wait_for_handle_with_strategy(handle, ms, self.wait_strategy);
+
+        match result {
+            WAIT_OBJECT_0 => {
+                if !self.manual {
+                    self.release_next()?;
+                }
+
+                #[cfg(feature = "deterministic")]
+                self.scheduler.after_wake(handle as *mut ());
+
+                Ok(WaitableResult::Signaled)
+            }
+            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+            _ => Err(EventError::FailedToWait(io::Error::last_os_error())),
+        }
+    }
+
+    fn poll_wait_impl(&self, waker: &Waker) -> Result<Poll<WaitableResult>, EventError> {
+        use EventError::*;
+
+        let handle = self.live_handle()?;
+
+        match self.poll_registration.poll(waker).map_err(FailedToWait)? {
+            PollState::Pending => return Ok(Poll::Pending),
+            PollState::Fired => {
+                // The registered wait consumed the auto event's signal in place of `wait_impl`.
+                if !self.manual {
+                    self.release_next()?;
+                }
+
+                return Ok(Poll::Ready(WaitableResult::Signaled));
+            }
+            PollState::NotRegistered => {}
+        }
+
+        if self.wait_impl(0)? == WaitableResult::Signaled {
+            return Ok(Poll::Ready(WaitableResult::Signaled));
+        }
+
+        // Catches the event being set since the check above.
+        self.poll_registration
+            .register(handle, waker)
+            .map_err(FailedToWait)?;
+
+        Ok(Poll::Pending)
+    }
+
+    /// Consumes one of the releases requested by [`set_n`], setting the event again if there are any left.
+    ///
+    /// [`set_n`]: #method.set_n
+    fn release_next(&self) -> Result<(), EventError> {
+        let prev =
+            self.pending_releases
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                    pending.checked_sub(1)
+                });
+
+        match prev {
+            // Not a user signal - bypasses the scheduler / signaler bookkeeping.
+            Ok(prev) if prev > 1 => self.set_impl(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Blocks the thread until the `wait_on` waitable is signaled or the duration `d` expires,
+/// and, only if it was signaled, [`set`]s the `signal` event before returning.
+///
+/// Encodes the "when A completes, notify B" hand-off step:
+/// on timeout, the `signal` event is left as is.
+///
+/// # Errors
+///
+/// Returns an error if waiting on the `wait_on` waitable or setting the `signal` event fails.
+/// If the wait fails, the `signal` event is not set.
+///
+/// [`set`]: struct.Event.html#method.set
+pub fn wait_then_signal(
+    wait_on: &dyn Waitable,
+    signal: &Event,
+    d: Duration,
+) -> Result<WaitableResult, EventError> {
+    let res = wait_on
+        .wait(d)
+        .map_err(|err| EventError::FailedToWait(err.into()))?;
+
+    if res == WaitableResult::Signaled {
+        signal.set()?;
+    }
+
+    Ok(res)
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        // Failing to close the handle means it was invalid or already closed - i.e. a bug elsewhere.
+        let res = self.close();
+        debug_assert!(res.is_ok(), "failed to close the event handle: {:?}", res);
+    }
+}
+
+impl Debug for Event {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("Event");
+
+        s.field("handle", &self.handle.unwrap_or(ptr::null_mut()));
+        s.field("manual", &self.manual);
+
+        if let Some(label) = self.label {
+            s.field("label", &label);
+        }
+
+        s.finish()
+    }
+}
+
+unsafe impl Send for Event {}
+unsafe impl Sync for Event {}
+
+impl Waitable for Event {
+    /// Blocks the thread until the event is [`set`] or the timeout of `ms` milliseconds expires.
+    ///
+    /// Auto event: because at most one thread is woken up when the event is [`set`],
+    /// there's no guarantee any given thread will wake up when there are multiple
+    /// threads waiting for one event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails or if the event was abandoned.
+    ///
+    /// [`set`]: struct.Event.html#method.set
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(ms).map_err(WaitError::from)
+    }
+}
+
+impl WaitableExt for Event {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    /// Null if the event was [`close`]d.
+    ///
+    /// [`close`]: struct.Event.html#method.close
+    fn handle(&self) -> *mut () {
+        self.handle.unwrap_or(ptr::null_mut()) as *mut ()
+    }
+}
+
+impl AsRawHandle for Event {
+    /// Returns the raw handle to the event's OS object.
+    /// Null if the event was [`close`]d.
+    ///
+    /// [`close`]: struct.Event.html#method.close
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle.unwrap_or(ptr::null_mut()) as RawHandle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            all_signaled, wait_for_all, wait_for_all_infinite, wait_for_all_typed, wait_for_one,
+            wait_for_one_infinite, wait_for_one_typed, WaitablesResult,
+        },
+        std::{sync::Arc, thread, time::Instant},
+        winapi::{shared::winerror::ERROR_INVALID_HANDLE, um::handleapi::INVALID_HANDLE_VALUE},
+    };
+
+    #[test]
+    fn manual_reset_typed_matches_dyn() {
+        let e = [
+            Event::new_manual(false, None).unwrap(),
+            Event::new_manual(false, None).unwrap(),
+            Event::new_manual(false, None).unwrap(),
+        ];
+        let w = [&e[0] as _, &e[1] as _, &e[2] as _];
+
+        // Every combination of signaled / not signaled events.
+        for state in 0..(1 << e.len()) {
+            for (idx, e) in e.iter().enumerate() {
+                if state & (1 << idx) != 0 {
+                    e.set().unwrap();
+                } else {
+                    e.reset().unwrap();
+                }
+            }
+
+            let dyn_ = wait_for_one(&w, Duration::from_millis(1)).unwrap();
+            let typed = wait_for_one_typed(&e, Duration::from_millis(1)).unwrap();
+            assert_eq!(dyn_, typed);
+
+            let dyn_ = wait_for_all(&w, Duration::from_millis(1)).unwrap();
+            let typed = wait_for_all_typed(&e, Duration::from_millis(1)).unwrap();
+            assert_eq!(dyn_, typed);
+        }
+    }
+
+    #[test]
+    fn manual_reset_all_signaled() {
+        let e0 = Event::new_manual(true, None).unwrap(); // Signaled.
+        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+        let w = [&e0 as _, &e1 as _];
+
+        assert!(all_signaled(&w).unwrap());
+        assert!(all_signaled(&w).unwrap()); // Still signaled.
+
+        e0.reset().unwrap(); // One not signaled.
+
+        assert!(!all_signaled(&w).unwrap());
+
+        e1.reset().unwrap(); // Both not signaled.
+
+        assert!(!all_signaled(&w).unwrap());
+
+        e0.set().unwrap();
+        e1.set().unwrap(); // Both signaled again.
+
+        assert!(all_signaled(&w).unwrap());
+    }
+
+    #[test]
+    fn auto_set_n() {
+        let e = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
+        let num_woken = Arc::new(AtomicUsize::new(0));
+
+        let threads: Vec<_> = (0..3)
+            .map(|_| {
+                let e = e.clone();
+                let num_woken = num_woken.clone();
+
+                thread::spawn(move || {
+                    e.wait_infinite().unwrap();
+                    num_woken.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        // Wait for the threads to start waiting.
+        thread::sleep(Duration::from_millis(500));
+
+        e.set_n(2).unwrap();
+
+        thread::sleep(Duration::from_millis(500));
+
+        // Exactly two threads have exited, the other is still waiting.
+        assert_eq!(num_woken.load(Ordering::SeqCst), 2);
+
+        e.set().unwrap();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(num_woken.load(Ordering::SeqCst), 3);
+
+        // Not signaled.
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Buffered releases.
+
+        e.set_n(2).unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+    }
+
+    #[test]
+    fn auto_set_n_other_waits() {
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+        // Consumed by `wait_for_one` - the remaining release stays buffered.
+        e.set_n(2).unwrap();
+
+        let res = wait_for_one(&[&e], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Must not be blocked by the buffered release.
+        e.set_n(1).unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        e.drain().unwrap(); // Discards the buffered releases.
+
+        // Consumed via a cloned handle.
+        let c = e.try_clone().unwrap();
+
+        e.set_n(2).unwrap();
+
+        let res = c.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        e.set_n(1).unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+
+    #[test]
+    fn is_manual_reused() {
+        let e = Event::new_manual(false, "minievent_is_manual_reused").unwrap();
+        assert!(e.is_manual());
+
+        // Keeps the original kind.
+        let (a, already_existed) = Event::new_auto_ex(false, "minievent_is_manual_reused").unwrap();
+        assert!(already_existed);
+        assert!(a.is_manual());
+
+        let o = Event::open("minievent_is_manual_reused").unwrap();
+        assert!(o.is_manual());
+
+        let e = Event::new_auto(false, "minievent_is_auto_reused").unwrap();
+        assert!(!e.is_manual());
+
+        let m = Event::new_manual(false, "minievent_is_auto_reused").unwrap();
+        assert!(!m.is_manual());
+    }
+
+    #[test]
+    fn manual_ensure_reset() {
+        let e0 = Event::new_manual(true, "minievent_manual_ensure_reset").unwrap(); // Signaled.
+
+        // Reused, initial state ignored.
+
+        let e1 = Event::new_manual(false, "minievent_manual_ensure_reset").unwrap();
+
+        let res = e1.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Reused and reset.
+
+        let e2 = Event::new_manual_ensure_reset("minievent_manual_ensure_reset").unwrap();
+
+        let res = e2.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let res = e0.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        e0.set().unwrap(); // Signaled again.
+
+        let res = e2.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+
+    #[test]
+    fn close_handle_once() {
+        let num_closed = || crate::waitable::NUM_CLOSED_HANDLES.with(|num| num.get());
+        let before = num_closed();
+
+        let mut e = Event::new_auto(true, None).unwrap();
+        assert!(!e.is_closed());
+
+        e.close().unwrap();
+        assert!(e.is_closed());
+        assert_eq!(num_closed(), before + 1);
+
+        e.close().unwrap(); // Already closed - does nothing.
+        assert_eq!(num_closed(), before + 1);
+
+        e.set().err().unwrap(); // Must have failed - closed.
+        e.wait(Duration::from_millis(1)).err().unwrap(); // Must have failed - closed.
+        assert!(e.handle().is_null());
+
+        drop(e); // Must not close the handle again.
+        assert_eq!(num_closed(), before + 1);
+    }
+
+    #[test]
+    fn create_retries() {
+        use {
+            crate::waitable::INJECTED_CREATE_ERRORS,
+            winapi::shared::winerror::ERROR_NOT_ENOUGH_MEMORY,
+        };
+
+        let inject = |errs: &[u32]| INJECTED_CREATE_ERRORS.with(|e| e.borrow_mut().extend(errs));
+        let num_injected = || INJECTED_CREATE_ERRORS.with(|e| e.borrow().len());
+
+        // Transient errors are retried up to `retries` times.
+        inject(&[ERROR_NOT_ENOUGH_MEMORY; 2]);
+        Event::new_auto_with_retries(false, None, 2).unwrap();
+        assert_eq!(num_injected(), 0);
+
+        inject(&[ERROR_NOT_ENOUGH_MEMORY; 3]);
+
+        match Event::new_auto_with_retries(false, None, 2) {
+            Err(EventError::FailedToCreate(err)) => {
+                assert_eq!(err.raw_os_error(), Some(ERROR_NOT_ENOUGH_MEMORY as i32))
+            }
+            _ => panic!("expected an error"),
+        }
+
+        assert_eq!(num_injected(), 0);
+
+        // Other errors are not retried.
+        inject(&[ERROR_INVALID_HANDLE, ERROR_NOT_ENOUGH_MEMORY]);
+
+        match Event::new_manual_with_retries(false, None, 2) {
+            Err(EventError::FailedToCreate(err)) => {
+                assert_eq!(err.raw_os_error(), Some(ERROR_INVALID_HANDLE as i32))
+            }
+            _ => panic!("expected an error"),
+        }
+
+        assert_eq!(num_injected(), 1);
+
+        // Retried by default.
+        Event::new_manual(false, None).unwrap();
+        assert_eq!(num_injected(), 0);
+    }
+
+    #[test]
+    fn id() {
+        use std::collections::HashMap;
+
+        let mut e0 = Event::new_auto(false, None).unwrap();
+        let e1 = Event::new_auto(false, None).unwrap();
+        assert_ne!(e0.id(), e1.id());
+        assert_eq!(e0.id(), e0.handle() as usize);
+
+        // Same OS object, different handle.
+        let e2 = e0.try_clone().unwrap();
+        assert_ne!(e0.id(), e2.id());
+
+        let mut routes = HashMap::new();
+        routes.insert(e0.id(), 0);
+        routes.insert(e1.id(), 1);
+
+        assert_eq!(routes[&e0.id()], 0);
+        assert_eq!(routes[&e1.id()], 1);
+        assert!(!routes.contains_key(&e2.id()));
+
+        e0.close().unwrap();
+        assert_eq!(e0.id(), 0);
+    }
+
+    #[test]
+    fn close_invalid_handle() {
+        let num_closed = || crate::waitable::NUM_CLOSED_HANDLES.with(|num| num.get());
+        let before = num_closed();
+
+        for handle in [ptr::null_mut(), INVALID_HANDLE_VALUE] {
+            let mut e = Event::from_raw_parts(handle, false);
+
+            match e.close() {
+                Err(EventError::FailedToClose(err)) => {
+                    assert_eq!(err.raw_os_error(), Some(ERROR_INVALID_HANDLE as i32))
+                }
+                _ => panic!("expected an error"),
+            }
+
+            assert!(e.is_closed());
+        }
+
+        // `CloseHandle` was never called.
+        assert_eq!(num_closed(), before);
+    }
+
+    #[test]
+    fn wait_unless_changed() {
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let watched = Arc::new(AtomicU64::new(7));
+
+        let res = e
+            .wait_unless_changed(&watched, 7, Duration::from_millis(10))
+            .unwrap();
+        assert!(res == WatchedResult::Timeout);
+
+        e.set().unwrap();
+
+        let res = e
+            .wait_unless_changed(&watched, 7, Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == WatchedResult::Signaled);
+
+        let t = {
+            let watched = watched.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                watched.store(8, Ordering::Release);
+            })
+        };
+
+        let res = e
+            .wait_unless_changed(&watched, 7, Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == WatchedResult::Changed);
+
+        t.join().unwrap();
+
+        // Changed value takes priority over the set event, which stays set.
+        e.set().unwrap();
+
+        let res = e
+            .wait_unless_changed(&watched, 7, Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == WatchedResult::Changed);
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Infinite wait outlasts the polling interval.
+        let e = Arc::new(e);
+
+        let t = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                e.set().unwrap();
+            })
+        };
+
+        let res = e.wait_unless_changed(&watched, 8, Duration::MAX).unwrap();
+        assert!(res == WatchedResult::Signaled);
+
+        t.join().unwrap();
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn try_clone_diagnostics_name() {
+        let e = Event::new_manual(false, "minievent_try_clone_diagnostics").unwrap();
+        let clone = e.try_clone().unwrap();
+
+        let info = crate::diagnostics::dump()
+            .into_iter()
+            .find(|info| info.handle == clone.id())
+            .unwrap();
+        assert_eq!(
+            info.name.as_deref(),
+            Some("minievent_try_clone_diagnostics")
+        );
+        assert_eq!(info.kind, crate::diagnostics::ObjectKind::ManualEvent);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn last_signaler_thread() {
+        use winapi::um::processthreadsapi::GetCurrentThreadId;
+
+        let e = Arc::new(Event::new_auto(false, None).unwrap());
+        assert_eq!(e.last_signaler_thread(), None);
+
+        let signaler = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                e.set().unwrap();
+                unsafe { GetCurrentThreadId() }
+            })
+        };
+
+        e.wait_infinite().unwrap();
+
+        let signaler = signaler.join().unwrap();
+        assert_eq!(e.last_signaler_thread(), Some(signaler));
+        assert_ne!(signaler, unsafe { GetCurrentThreadId() });
+    }
+
+    #[test]
+    fn wait_priority_boost() {
+        use {
+            crate::ThreadPriority,
+            winapi::um::processthreadsapi::{GetCurrentThread, GetThreadPriority},
+        };
+
+        let priority = || unsafe { GetThreadPriority(GetCurrentThread()) };
+        let before = priority();
+
+        let e = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
+
+        // Timeout.
+
+        let res = e
+            .wait_priority_boost(Duration::from_millis(1), ThreadPriority::Highest)
+            .unwrap();
+        assert!(res == WaitableResult::Timeout);
+        assert_eq!(priority(), before);
+
+        // Signaled.
+
+        let t = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                e.set().unwrap();
+            })
+        };
+
+        let res = e
+            .wait_priority_boost(Duration::from_secs(1_000_000), ThreadPriority::Highest)
+            .unwrap();
+        assert!(res == WaitableResult::Signaled);
+        assert_eq!(priority(), before);
+
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn wait_servicing_apcs() {
+        use {
+            std::sync::atomic::AtomicUsize,
+            winapi::{
+                shared::basetsd::ULONG_PTR,
+                um::{
+                    handleapi::CloseHandle,
+                    processthreadsapi::{GetCurrentThreadId, OpenThread, QueueUserAPC},
+                    winnt::THREAD_SET_CONTEXT,
+                },
+            },
+        };
+
+        static NUM_APCS: AtomicUsize = AtomicUsize::new(0);
+
+        unsafe extern "system" fn apc(_: ULONG_PTR) {
+            NUM_APCS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let e = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
+
+        let thread = unsafe { OpenThread(THREAD_SET_CONTEXT, FALSE, GetCurrentThreadId()) };
+        assert!(!thread.is_null());
+        let thread = thread as usize;
+
+        let t = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                for _ in 0..3 {
+                    thread::sleep(Duration::from_millis(10));
+                    assert!(unsafe { QueueUserAPC(Some(apc), thread as HANDLE, 0) } != 0);
+                }
+
+                thread::sleep(Duration::from_millis(10));
+                e.set().unwrap();
+            })
+        };
+
+        // Not interrupted by the APCs.
+        let res = e
+            .wait_servicing_apcs(Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        t.join().unwrap();
+
+        assert_eq!(NUM_APCS.load(Ordering::SeqCst), 3);
+
+        unsafe {
+            CloseHandle(thread as HANDLE);
+        }
+    }
+
+    #[test]
+    fn wait_alertable() {
+        use {
+            crate::{wait_for_all_alertable, wait_for_one_alertable},
+            winapi::{
+                shared::basetsd::ULONG_PTR,
+                um::{
+                    handleapi::CloseHandle,
+                    processthreadsapi::{GetCurrentThreadId, OpenThread, QueueUserAPC},
+                    winnt::THREAD_SET_CONTEXT,
+                },
+            },
+        };
+
+        unsafe extern "system" fn apc(_: ULONG_PTR) {}
+
+        let e0 = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+
+        let thread = unsafe { OpenThread(THREAD_SET_CONTEXT, FALSE, GetCurrentThreadId()) };
+        assert!(!thread.is_null());
+
+        // Cancels the next alertable wait of this thread.
+        let cancel = || {
+            let thread = thread as usize;
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                assert!(unsafe { QueueUserAPC(Some(apc), thread as HANDLE, 0) } != 0);
+            })
+        };
+
+        let t = cancel();
+        let res = e0.wait_alertable(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::IoCompletion);
+        t.join().unwrap();
+
+        let t = cancel();
+        let res = wait_for_one_alertable(&[&e0], Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::IoCompletion);
+        t.join().unwrap();
+
+        let t = cancel();
+        let res = wait_for_all_alertable(&[&e0, &e1], Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::IoCompletion);
+        t.join().unwrap();
+
+        // Signaled waitables are not affected.
+
+        let res = e1.wait_alertable(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = wait_for_one_alertable(&[&e0, &e1], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        unsafe {
+            CloseHandle(thread);
+        }
+    }
+
+    #[test]
+    fn inheritable() {
+        use winapi::um::{handleapi::GetHandleInformation, winbase::HANDLE_FLAG_INHERIT};
+
+        let is_inheritable = |e: &Event| {
+            let mut flags = 0;
+            assert!(unsafe { GetHandleInformation(e.handle() as HANDLE, &mut flags) } != 0);
+            flags & HANDLE_FLAG_INHERIT != 0
+        };
+
+        let options = EventOptions {
+            inheritable: true,
+            ..Default::default()
+        };
+
+        let e = Event::new_auto_with_options(false, None, &options).unwrap();
+        assert!(is_inheritable(&e));
+
+        let e = Event::new_manual_with_options(true, None, &options).unwrap();
+        assert!(is_inheritable(&e));
+        assert!(e.wait(Duration::from_secs(0)).unwrap() == WaitableResult::Signaled);
+
+        let e = Event::new_auto(false, None).unwrap();
+        assert!(!is_inheritable(&e));
+    }
+
+    #[test]
+    fn access() {
+        let options = EventOptions {
+            access: EventAccess::SYNCHRONIZE,
+            ..Default::default()
+        };
+
+        // May be waited on, but not set / reset.
+        let e = Event::new_manual_with_options(true, None, &options).unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        assert!(e.set().unwrap_err().is_access_denied());
+        assert!(e.reset().unwrap_err().is_access_denied());
+
+        let res = e.wait(Duration::from_millis(1)).unwrap(); // Still signaled.
+        assert!(res == WaitableResult::Signaled);
+
+        let options = EventOptions {
+            access: EventAccess::SYNCHRONIZE | EventAccess::MODIFY_STATE,
+            ..Default::default()
+        };
+        assert!(options.access.contains(EventAccess::MODIFY_STATE));
+        assert!(EventAccess::default().contains(options.access));
+
+        let e = Event::new_auto_with_options(false, None, &options).unwrap();
+        e.set().unwrap();
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+
+    #[test]
+    fn register_callback() {
+        use crate::Semaphore;
+
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let done = Arc::new(Semaphore::new(0, 3, None).unwrap());
+
+        let mut num_calls = 0;
+
+        let registration = {
+            let done = done.clone();
+
+            e.register_callback(Box::new(move || {
+                num_calls += 1;
+                assert!(num_calls <= 3);
+                done.increment_one().unwrap();
+            }))
+            .unwrap()
+        };
+
+        for _ in 0..3 {
+            e.set().unwrap();
+
+            let res = done.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        drop(registration);
+
+        // No more callbacks - the signal is not consumed.
+        e.set().unwrap();
+
+        let res = e.wait(Duration::from_millis(100)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+        assert_eq!(done.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn signal_and_wait() {
+        use {crate::signal_and_wait, std::sync::atomic::AtomicUsize};
+
+        const N: usize = 100;
+
+        let ping = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
+        let pong = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
+        let num_bounces = Arc::new(AtomicUsize::new(0));
+
+        let t = {
+            let ping = ping.clone();
+            let pong = pong.clone();
+            let num_bounces = num_bounces.clone();
+
+            thread::spawn(move || {
+                ping.wait_infinite().unwrap();
+
+                for i in 0..N {
+                    num_bounces.fetch_add(1, Ordering::SeqCst);
+
+                    if i + 1 < N {
+                        let res =
+                            signal_and_wait(&*pong, &*ping, Duration::from_secs(1_000_000), false)
+                                .unwrap();
+                        assert!(res == WaitableResult::Signaled);
+                    } else {
+                        pong.set().unwrap();
+                    }
+                }
+            })
+        };
+
+        for i in 0..N {
+            let res =
+                signal_and_wait(&*ping, &*pong, Duration::from_secs(1_000_000), false).unwrap();
+            assert!(res == WaitableResult::Signaled);
+
+            // Exactly one bounce per hand-off.
+            assert_eq!(num_bounces.load(Ordering::SeqCst), i + 1);
+        }
+
+        t.join().unwrap();
+
+        // Both signals were consumed.
+        assert!(ping.wait(Duration::from_secs(0)).unwrap() == WaitableResult::Timeout);
+        assert!(pong.wait(Duration::from_secs(0)).unwrap() == WaitableResult::Timeout);
+
+        // Signals, but times out.
+        let res = signal_and_wait(&*ping, &*pong, Duration::from_millis(1), false).unwrap();
+        assert!(res == WaitableResult::Timeout);
+        assert!(ping.wait(Duration::from_secs(0)).unwrap() == WaitableResult::Signaled);
+    }
+
+    /// Run in a child process by `from_ipc_handle`.
+    #[test]
+    #[ignore]
+    fn from_ipc_handle_child() {
+        let mut handle = String::new();
+        std::io::stdin().read_line(&mut handle).unwrap();
+
+        let e = unsafe { Event::from_ipc_handle(handle.trim().parse().unwrap()) }.unwrap();
+
+        let res = e.wait(Duration::from_secs(10)).unwrap(); // Set by the parent.
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e.wait(Duration::from_secs(10)).unwrap(); // Manual - still signaled.
+        assert!(res == WaitableResult::Signaled);
+    }
+
+    #[test]
+    fn from_ipc_handle() {
+        use {
+            crate::{Semaphore, SemaphoreError},
+            std::os::windows::io::AsRawHandle,
+            std::{
+                env,
+                io::Write,
+                process::{Command, Stdio},
+            },
+            winapi::um::{
+                handleapi::DuplicateHandle, processthreadsapi::GetCurrentProcess,
+                winnt::DUPLICATE_SAME_ACCESS,
+            },
+        };
+
+        let duplicate = |handle: *mut (), process: HANDLE| {
+            let mut duplicated = ptr::null_mut();
+
+            let result = unsafe {
+                DuplicateHandle(
+                    GetCurrentProcess(),
+                    handle as HANDLE,
+                    process,
+                    &mut duplicated,
+                    0,
+                    FALSE,
+                    DUPLICATE_SAME_ACCESS,
+                )
+            };
+            assert!(result != FALSE);
+
+            duplicated as isize
+        };
+
+        // Wrong object type / invalid handle.
+
+        let s = Semaphore::new(0, 1, None).unwrap();
+        let handle = duplicate(s.handle(), unsafe { GetCurrentProcess() });
+
+        match unsafe { Event::from_ipc_handle(handle) } {
+            Err(EventError::WrongObjectType) => {}
+            _ => panic!(),
+        }
+
+        match unsafe { Semaphore::from_ipc_handle(handle) } {
+            Ok(s) => drop(s),
+            _ => panic!(),
+        }
+
+        match unsafe { Event::from_ipc_handle(0) } {
+            Err(EventError::InvalidHandle(_)) => {}
+            _ => panic!(),
+        }
+
+        let e = Event::new_manual(true, None).unwrap(); // Signaled.
+
+        let handle = duplicate(e.handle(), unsafe { GetCurrentProcess() });
+
+        match unsafe { Semaphore::from_ipc_handle(handle) } {
+            Err(SemaphoreError::WrongObjectType) => {}
+            _ => panic!(),
+        }
+
+        // Child process.
+
+        let mut child = Command::new(env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "event::win::tests::from_ipc_handle_child",
+                "--ignored",
+                "--test-threads=1",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let handle = duplicate(e.handle(), child.as_raw_handle() as HANDLE);
+
+        writeln!(child.stdin.take().unwrap(), "{}", handle).unwrap();
+
+        assert!(child.wait().unwrap().success());
+    }
+
+    #[test]
+    fn wait_for_one_profiled() {
+        use crate::wait_for_one_profiled;
+
+        let e = Arc::new(Event::new_auto(true, None).unwrap()); // Signaled.
+
+        // Already signaled.
+
+        let (res, elapsed) = wait_for_one_profiled(&[&*e], Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+        assert!(elapsed < Duration::from_millis(50));
+
+        // Signaled after a delay.
+
+        let t = {
+            let e = e.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                e.set().unwrap();
+            })
+        };
+
+        let (res, elapsed) = wait_for_one_profiled(&[&*e], Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+        assert!(elapsed >= Duration::from_millis(50));
+
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn wait_for_until() {
+        use crate::{wait_for_all_until, wait_for_one_until};
+
+        let e0 = Event::new_manual(false, None).unwrap(); // Not signaled.
+        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+
+        // Deadline has passed.
+
+        let deadline = Instant::now() - Duration::from_millis(1);
+
+        let res = wait_for_one_until(&[&e0, &e1], deadline).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        let res = wait_for_all_until(&[&e0, &e1], deadline).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let now = Instant::now();
+        let res = wait_for_all_until(&[&e0, &e1], now + Duration::from_millis(500)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+        assert!(now.elapsed().as_millis() >= 250);
+
+        e0.set().unwrap(); // Now signaled.
 
-    /// Creates a new manual reset event (or tries to reuse based on `name`).
-    ///
-    /// `set` - gives the initial state of the event.
-    /// `name` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventa).
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the OS event creation failed, or if `name` was invalid - e.g. contained nul bytes.
-    pub fn new_manual<'n, N: Into<Option<&'n str>>>(
-        set: bool,
-        name: N,
-    ) -> Result<Event, EventError> {
-        Event::new(true, set, name.into())
+        let res = wait_for_all_until(&[&e0, &e1], deadline).unwrap();
+        assert!(res == WaitableResult::Signaled);
     }
 
-    /// Sets / signals the event.
-    ///
-    /// Auto event: at most one waiting thread will be woken up.
-    /// Manual event: stays set / signaled until it is [`reset`].
-    ///
-    /// [`reset`]: #method.reset
-    pub fn set(&self) -> Result<(), EventError> {
-        let result = unsafe { SetEvent(self.handle) };
+    #[test]
+    fn abandoned() {
+        use winapi::um::{synchapi::CreateMutexA, winbase::INFINITE, winnt::HANDLE};
+
+        /// Raw OS mutex - abandoned if the owning thread exits without releasing it.
+        struct Mutex(HANDLE);
+
+        impl Waitable for Mutex {
+            fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+                use winapi::um::winbase::{WAIT_ABANDONED_0, WAIT_OBJECT_0};
+
+                match unsafe { WaitForSingleObject(self.0, ms) } {
+                    WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+                    WAIT_ABANDONED_0 => Ok(WaitableResult::Abandoned),
+                    WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+                    _ => Err(WaitError::FailedToWait(io::Error::last_os_error())),
+                }
+            }
+        }
 
-        if result == FALSE {
-            Err(EventError::FailedToSet(io::Error::last_os_error()))
-        } else {
-            Ok(())
+        impl WaitableExt for Mutex {
+            fn handle(&self) -> *mut () {
+                self.0 as _
+            }
         }
+
+        impl Drop for Mutex {
+            fn drop(&mut self) {
+                close_handle(self.0).unwrap();
+            }
+        }
+
+        let new_abandoned_mutex = || {
+            let mutex = unsafe { CreateMutexA(ptr::null_mut(), FALSE, ptr::null()) };
+            assert!(!mutex.is_null());
+
+            // Acquired by the thread, never released.
+            let mutex = mutex as usize;
+            thread::spawn(move || unsafe { WaitForSingleObject(mutex as HANDLE, INFINITE) })
+                .join()
+                .unwrap();
+
+            Mutex(mutex as HANDLE)
+        };
+
+        let e = Event::new_manual(false, None).unwrap();
+
+        let m = new_abandoned_mutex();
+        let res = wait_for_one_infinite(&[&e, &m]).unwrap();
+        assert!(res == WaitablesResult::Abandoned(1));
+
+        e.set().unwrap();
+
+        let m = new_abandoned_mutex();
+        let res = wait_for_all_infinite(&[&e, &m]).unwrap();
+        assert!(res == WaitableResult::Abandoned);
+
+        // Acquired by this thread now.
+        let res = wait_for_all_infinite(&[&e, &m]).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Single waitable fast path.
+        let m = new_abandoned_mutex();
+        let res = wait_for_one_infinite(&[&m]).unwrap();
+        assert!(res == WaitablesResult::Abandoned(0));
+
+        let m = new_abandoned_mutex();
+        let res = wait_for_all_infinite(&[&m]).unwrap();
+        assert!(res == WaitableResult::Abandoned);
+
+        // Waited on directly.
+        let m = new_abandoned_mutex();
+        let res = m.wait_raw_ms(INFINITE).unwrap();
+        assert!(res == WaitableResult::Abandoned);
+
+        // Portable fallbacks.
+        e.reset().unwrap();
+
+        let m = new_abandoned_mutex();
+        let res = crate::wait_for_one_sequential(&[&e, &m], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Abandoned(1));
+
+        e.set().unwrap();
+
+        let m = new_abandoned_mutex();
+        let res = crate::wait_for_all_sequential(&[&e, &m], Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Abandoned);
     }
 
-    /// Resets the manual reset event.
-    pub fn reset(&self) -> Result<(), EventError> {
-        let result = unsafe { ResetEvent(self.handle) };
+    #[test]
+    fn new_ex() {
+        let (e0, already_existed) = Event::new_manual_ex(true, "minievent_new_ex").unwrap();
+        assert!(!already_existed);
 
-        if result == FALSE {
-            Err(EventError::FailedToReset(io::Error::last_os_error()))
-        } else {
-            Ok(())
+        let (e1, already_existed) = Event::new_auto_ex(false, "minievent_new_ex").unwrap();
+        assert!(already_existed);
+
+        // Reused - keeps the original state.
+        assert!(e1.try_wait().unwrap() == WaitableResult::Signaled);
+
+        drop(e0);
+        drop(e1);
+
+        let (_, already_existed) = Event::new_auto_ex(false, None).unwrap();
+        assert!(!already_existed);
+    }
+
+    #[test]
+    fn open() {
+        let e = Event::new_manual(false, "minievent_open").unwrap();
+        let o = Event::open("minievent_open").unwrap();
+
+        e.set().unwrap();
+
+        // Manual - still signaled.
+        for _ in 0..2 {
+            let res = o.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Signaled);
         }
     }
 
-    fn new(manual: bool, set: bool, name: Option<&str>) -> Result<Event, EventError> {
-        use EventError::*;
+    #[test]
+    fn namespaces() {
+        let e = Event::new_manual_local(false, "minievent_event_namespaces").unwrap();
 
-        let manual = if manual { 1 } else { 0 };
-        let set = if set { 1 } else { 0 };
+        // Same object, whether the prefix is given or not.
+        let o = Event::open("Local\\minievent_event_namespaces").unwrap();
+        let o2 = Event::new_auto_local(false, "Local\\minievent_event_namespaces").unwrap();
+        assert!(o2.is_manual());
 
-        let name = if let Some(name) = name {
-            if name.len() > 0 {
-                CString::new(name).map_err(|_| InvalidName)?.as_ptr()
-            } else {
-                ptr::null_mut()
-            }
-        } else {
-            ptr::null_mut()
-        };
+        e.set().unwrap();
 
-        let handle = unsafe { CreateEventA(ptr::null_mut(), manual, set, name) };
+        let res = o.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
 
-        if handle.is_null() {
-            Err(FailedToCreate(io::Error::last_os_error()))
-        } else {
-            Ok(Event { handle })
+        match Event::new_auto_global(false, "Local\\minievent_event_namespaces") {
+            Err(EventError::ConflictingNamespace) => {}
+            _ => panic!(),
         }
-    }
 
-    fn wait_impl(&self, ms: u32) -> Result<WaitableResult, EventError> {
-        let result = unsafe { WaitForSingleObject(self.handle, ms) };
+        match Event::new_manual_local(false, "session\\1\\minievent_event_namespaces") {
+            Err(err) => assert!(err.is_invalid_name()),
+            _ => panic!(),
+        }
 
-        match result {
-            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
-            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
-            _ => Err(EventError::FailedToWait(io::Error::last_os_error())),
+        match Event::new_auto_global(false, "") {
+            Err(EventError::InvalidName { nul_position: 0 }) => {}
+            _ => panic!(),
         }
     }
-}
 
-impl Drop for Event {
-    fn drop(&mut self) {
-        unsafe {
-            CloseHandle(self.handle);
+    #[test]
+    fn open_not_found() {
+        match Event::open("minievent_open_not_found") {
+            Err(EventError::NotFound) => {}
+            _ => panic!(),
         }
-    }
-}
 
-unsafe impl Send for Event {}
-unsafe impl Sync for Event {}
+        match Event::open("") {
+            Err(EventError::InvalidName { nul_position: 0 }) => {}
+            _ => panic!(),
+        }
 
-impl Waitable for Event {
-    /// Blocks the thread until the event is [`set`] or the duration `d` expires.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the OS function fails or if the event was abandoned.
-    ///
-    /// [`set`]: struct.Event.html#method.set
-    fn wait(&self, d: Duration) -> Result<WaitableResult, ()> {
-        let ms = d.as_millis();
-        debug_assert!(ms <= std::u32::MAX as u128);
-        let ms = ms as u32;
+        match Event::open("foo\0bar") {
+            Err(EventError::InvalidName { nul_position }) => assert_eq!(nul_position, 3),
+            _ => panic!(),
+        }
 
-        self.wait_impl(ms).map_err(|_| ())
-    }
+        match Event::new_auto(false, Some("foo\0bar")) {
+            Err(err) => {
+                assert!(err.is_invalid_name());
+                assert_eq!(
+                    err.to_string(),
+                    "invalid event name: nul byte at position 3"
+                );
+            }
+            _ => panic!(),
+        }
 
-    /// Blocks the thread until the event is [`set`].
-    ///
-    /// Auto event: because at most one thread is woken up when the event is [`set`],
-    /// there's no guarantee any given thread will wake up when there are multiple
-    /// threads waiting for one event.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the OS function fails or if the event was abandoned.
-    ///
-    /// [`set`]: struct.Event.html#method.set
-    fn wait_infinite(&self) -> Result<(), ()> {
-        self.wait_impl(INFINITE).map(|_| ()).map_err(|_| ())
-    }
-}
+        let name = "a".repeat(MAX_PATH + 1);
 
-impl WaitableExt for Event {
-    /// Returns the raw handle / pointer to the waitable's OS object.
-    fn handle(&self) -> *mut () {
-        self.handle as *mut ()
+        match Event::new_auto(false, Some(name.as_str())) {
+            Err(EventError::NameTooLong { len }) => assert_eq!(len, MAX_PATH + 1),
+            _ => panic!(),
+        }
+
+        assert!(Event::open(&name).unwrap_err().is_invalid_name());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use {
-        super::*,
-        crate::{wait_for_all, wait_for_one, WaitablesResult},
-        std::{sync::Arc, thread, time::Instant},
-    };
+    #[test]
+    fn error_predicates() {
+        let err = Event::open("minievent_error_predicates").unwrap_err();
+        assert!(err.is_not_found());
+        assert!(err.os_error().is_none());
+
+        let err = Event::new_auto(false, "minievent\0error_predicates").unwrap_err();
+        assert!(err.is_invalid_name());
+
+        let err = unsafe { Event::from_ipc_handle(0) }.unwrap_err();
+        assert!(err.is_invalid_handle());
+        assert!(err.os_error().is_some());
+
+        let mut e = Event::new_auto(false, None).unwrap();
+        e.close().unwrap();
+
+        let err = e.set().unwrap_err();
+        assert!(err.is_closed());
+        assert!(!err.is_not_found());
+    }
 
     #[test]
-    fn manual_reset_signaled_method() {
-        let e = Event::new_manual(true, None).unwrap(); // Signaled.
+    fn try_clone() {
+        let e = Event::new_manual(false, None).unwrap();
+        let c = e.try_clone().unwrap();
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
-        assert!(res == WaitableResult::Signaled);
+        assert_ne!(e.handle(), c.handle());
+
+        let res = c.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        e.set().unwrap();
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
+        let res = c.wait(Duration::from_secs(1_000_000)).unwrap();
         assert!(res == WaitableResult::Signaled);
 
-        e.reset().unwrap(); // Not anymore.
+        // Closes independently.
+        drop(e);
 
-        let res = e.wait(Duration::from_millis(1)).unwrap();
+        c.reset().unwrap();
+
+        let res = c.wait(Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Timeout);
+    }
+
+    #[test]
+    fn raw_handle_round_trip() {
+        let e = Event::new_manual(true, None).unwrap();
 
-        e.set().unwrap(); // Signaled again.
+        let handle = e.into_raw_handle();
+        assert!(!handle.is_null());
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitableResult::Signaled);
+        // Not closed by `into_raw_handle`.
+        let mut e = unsafe { Event::from_raw_handle(handle) }.unwrap();
+        assert!(e.is_manual());
+        assert_eq!(e.handle(), handle);
+        assert_eq!(e.as_raw_handle(), handle as RawHandle);
+
+        // Manual - still signaled.
+        assert!(e.try_wait().unwrap() == WaitableResult::Signaled);
+        assert!(e.try_wait().unwrap() == WaitableResult::Signaled);
+
+        e.close().unwrap();
+        assert!(e.into_raw_handle().is_null());
+
+        // Not an event.
+        match unsafe { Event::from_raw_handle(ptr::null_mut()) } {
+            Err(EventError::InvalidHandle(_)) => {}
+            _ => panic!("expected an error"),
+        }
     }
 
     #[test]
-    fn manual_reset_signaled_free_function() {
-        let e = Event::new_manual(true, None).unwrap(); // Signaled.
-        let w = [&e as _];
+    fn pulse() {
+        const NUM_WAITERS: usize = 4;
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
-        assert!(res == WaitableResult::Signaled);
+        let e = Arc::new(Event::new_manual(false, None).unwrap());
+        let num_woken = Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
-        assert!(res == WaitableResult::Signaled);
+        let threads: Vec<_> = (0..NUM_WAITERS)
+            .map(|_| {
+                let e = e.clone();
+                let num_woken = num_woken.clone();
 
-        e.reset().unwrap(); // Not anymore.
+                thread::spawn(move || {
+                    e.wait_infinite().unwrap();
+                    num_woken.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+        // Let all waiters block.
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(num_woken.load(Ordering::SeqCst), 0);
 
-        e.set().unwrap(); // Signaled again.
+        e.pulse().unwrap();
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitableResult::Signaled);
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(num_woken.load(Ordering::SeqCst), NUM_WAITERS);
+
+        // Left reset.
+        assert!(e.try_wait().unwrap() == WaitableResult::Timeout);
     }
 
     #[test]
-    fn manual_reset_signaled_free_function_multiple() {
-        let e0 = Event::new_manual(true, None).unwrap(); // Signaled.
-        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
-        let w = [&e0 as _, &e1 as _]; // Signaled.
-
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
-        assert!(res == WaitableResult::Signaled);
+    fn wait_strategies() {
+        let strategies = [
+            WaitStrategy::Blocking,
+            WaitStrategy::SpinThenBlock { spin: 100 },
+            WaitStrategy::Yielding,
+        ];
 
-        let res = wait_for_one(&w, Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
-        assert!(res == WaitablesResult::OneSignaled(0) || res == WaitablesResult::OneSignaled(1));
+        for strategy in strategies.iter().copied() {
+            let e = Arc::new(
+                Event::new_auto(false, None)
+                    .unwrap()
+                    .with_wait_strategy(strategy)
+                    .unwrap(),
+            );
+            assert_eq!(e.wait_strategy(), strategy);
 
-        e0.reset().unwrap(); // One not signaled.
+            let res = e.wait(Duration::from_secs(0)).unwrap();
+            assert!(res == WaitableResult::Timeout);
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+            let res = e.wait(Duration::from_millis(10)).unwrap();
+            assert!(res == WaitableResult::Timeout);
 
-        let res = wait_for_one(&w, Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitablesResult::OneSignaled(1));
+            e.set().unwrap();
 
-        e1.reset().unwrap(); // Both not signaled.
+            let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+            // Set after a delay.
 
-        let res = wait_for_one(&w, Duration::from_millis(1)).unwrap();
-        assert!(res == WaitablesResult::Timeout);
+            let t = {
+                let e = e.clone();
 
-        e0.set().unwrap(); // Only one signaled.
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(10));
+                    e.set().unwrap();
+                })
+            };
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+            e.wait_infinite().unwrap();
 
-        let res = wait_for_one(&w, Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitablesResult::OneSignaled(0));
+            t.join().unwrap();
 
-        e1.set().unwrap(); // Both signaled again.
+            let res = e.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Timeout);
+        }
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitableResult::Signaled);
+        // Invalid spin counts.
 
-        let res = wait_for_one(&w, Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitablesResult::OneSignaled(0) || res == WaitablesResult::OneSignaled(1));
+        for spin in [0, WaitStrategy::MAX_SPIN + 1].iter().copied() {
+            match Event::new_auto(false, None)
+                .unwrap()
+                .with_wait_strategy(WaitStrategy::SpinThenBlock { spin })
+            {
+                Err(EventError::InvalidWaitStrategy) => {}
+                _ => panic!("expected an error"),
+            }
+        }
     }
 
     #[test]
-    fn manual_reset_unsignaled_method() {
-        let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+    fn drain_set_n() {
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+        // Buffered releases are discarded.
+
+        e.set_n(3).unwrap();
+        assert!(e.drain().unwrap());
 
         let res = e.wait(Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Timeout);
+    }
 
-        e.set().unwrap(); // Now signaled.
-
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Still signaled.
-        assert!(res == WaitableResult::Signaled);
+    #[test]
+    fn wait_for_one_with_buf() {
+        let e0 = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let e1 = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let w = [&e0 as &dyn WaitableExt, &e1 as _];
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // And still signaled.
-        assert!(res == WaitableResult::Signaled);
+        let mut buf = [ptr::null_mut(); 2];
 
-        e.reset().unwrap(); // Not anymore.
+        let res = crate::wait_for_one_with_buf(&w, Duration::from_millis(1), &mut buf).unwrap();
+        assert!(res == WaitablesResult::Timeout);
 
-        let res = e.wait(Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+        e1.set().unwrap();
 
-        e.set().unwrap(); // Signaled again.
+        let res = crate::wait_for_one_with_buf(&w, Duration::from_millis(1), &mut buf).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitableResult::Signaled);
+        match crate::wait_for_one_with_buf(&w, Duration::from_millis(1), &mut buf[..1]) {
+            Err(crate::WaitError::BufferTooSmall { len, required }) => {
+                assert_eq!((len, required), (1, 2))
+            }
+            _ => panic!("expected an error"),
+        }
     }
 
     #[test]
-    fn auto_reset_signaled() {
-        let e = Event::new_auto(true, None).unwrap(); // Signaled.
+    fn too_many_waitables() {
+        use crate::{max_num_waitables, WaitError};
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        let e = Event::new_manual(true, None).unwrap(); // Signaled.
+        let w = vec![&e as &dyn WaitableExt; 1000];
 
-        let res = e.wait(Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+        match wait_for_one_infinite(&w) {
+            Err(WaitError::TooManyWaitables { given, max }) => {
+                assert_eq!(given, 1000);
+                assert_eq!(max, max_num_waitables());
+            }
+            _ => panic!("expected an error"),
+        }
+
+        match wait_for_all_infinite(&w) {
+            Err(WaitError::TooManyWaitables { given, .. }) => assert_eq!(given, 1000),
+            _ => panic!("expected an error"),
+        }
+
+        match crate::wait_for_one_iter(w.iter().copied(), Duration::from_millis(1)) {
+            Err(WaitError::TooManyWaitables { given, .. }) => assert_eq!(given, 1000),
+            _ => panic!("expected an error"),
+        }
+
+        // Maximum number is fine (the OS does not allow duplicate handles).
 
-        e.set().unwrap(); // Signaled again.
+        let events: Vec<_> = (0..max_num_waitables())
+            .map(|_| Event::new_manual(true, None).unwrap())
+            .collect();
+        let w: Vec<_> = events.iter().map(|e| e as &dyn WaitableExt).collect();
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        let res = wait_for_all_infinite(&w).unwrap();
         assert!(res == WaitableResult::Signaled);
     }
 
     #[test]
-    fn auto_reset_unsignaled() {
-        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+    fn wait_for_all_or_partial() {
+        use crate::{wait_for_all_or_partial, PartialResult, Semaphore};
 
-        let res = e.wait(Duration::from_millis(1)).unwrap();
+        let e0 = Event::new_manual(true, None).unwrap(); // Signaled.
+        let e1 = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let e2 = Event::new_auto(true, None).unwrap(); // Signaled.
+        let s = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
+
+        let w: [&dyn WaitableExt; 4] = [&e0, &e1, &e2, &s];
+
+        let res = wait_for_all_or_partial(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == PartialResult::Partial(vec![0, 2]));
+        assert_eq!(res.signaled_mask(w.len()), [true, false, true, false]);
+
+        // The auto event's signal was consumed by the check, the manual event's was not.
+
+        let res = wait_for_all_or_partial(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == PartialResult::Partial(vec![0]));
+
+        e1.set().unwrap();
+        e2.set().unwrap();
+        s.increment_one().unwrap();
+
+        let res = wait_for_all_or_partial(&w, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == PartialResult::AllSignaled);
+        assert_eq!(res.signaled_mask(w.len()), [true; 4]);
+
+        // Auto events and the semaphore are not signaled anymore.
+        let (res, mask) = crate::wait_for_all_detailed(&w, Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Timeout);
+        assert_eq!(mask, [true, false, false, false]);
 
-        e.set().unwrap(); // Now signaled.
+        e1.set().unwrap();
+        e2.set().unwrap();
+        s.increment_one().unwrap();
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+        let (res, mask) = crate::wait_for_all_detailed(&w, Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Signaled);
+        assert_eq!(mask, [true; 4]);
 
-        e.set().unwrap(); // Signaled again.
+        e0.reset().unwrap();
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
-        assert!(res == WaitableResult::Signaled);
+        let res = wait_for_all_or_partial(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == PartialResult::Partial(Vec::new()));
     }
 
     #[test]
-    fn manual_thread_signal() {
-        let e = Arc::new(Event::new_manual(false, None).unwrap());
-        let e_clone_1 = e.clone();
-        let e_clone_2 = e.clone(); // Not signaled.
-
-        let t_1 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = e_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
-
-        let t_2 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = e_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
-
-        // Wait for a second.
-        thread::sleep(Duration::from_secs(1));
+    fn wait_then_signal() {
+        use crate::Semaphore;
 
-        e.set().unwrap();
+        let a = Arc::new(Semaphore::new(0, 1, None).unwrap()); // Not signaled.
+        let b = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+        // Timeout - not signaled.
 
-        // Both threads must have exited at about the same time.
+        let res = super::wait_then_signal(&*a, &b, Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
 
-        let res_1 = t_1.join().unwrap();
+        let res = b.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
 
-        assert!(res_1.0 == WaitableResult::Signaled);
-        assert!(res_1.1.as_millis() >= 500);
+        // Signaled.
 
-        let res_2 = t_2.join().unwrap();
+        let t = {
+            let a = a.clone();
 
-        assert!(res_2.0 == WaitableResult::Signaled);
-        assert!(res_2.1.as_millis() >= 500);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                a.increment_one().unwrap();
+            })
+        };
 
-        // Still signaled.
+        let res = super::wait_then_signal(&*a, &b, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
 
-        let res = e.wait(Duration::from_secs(1_000_000)).unwrap();
+        let res = b.wait(Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Signaled);
+
+        t.join().unwrap();
     }
 
     #[test]
-    fn auto_thread_signal() {
-        let e = Arc::new(Event::new_auto(false, None).unwrap());
-        let e_clone_1 = e.clone();
-        let e_clone_2 = e.clone(); // Not signaled.
-
-        let t_1 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = e_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
-
-        let t_2 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = e_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
-
-        // Wait for a second.
-        thread::sleep(Duration::from_secs(1));
+    fn poll_wait() {
+        use {
+            crate::Semaphore,
+            std::task::{Poll, Wake, Waker},
+        };
 
-        e.set().unwrap();
+        /// Increments the semaphore when woken.
+        struct SemaphoreWaker(Semaphore);
 
-        // One of the threads has exited, the other is still waiting.
+        impl Wake for SemaphoreWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.increment_one().unwrap();
+            }
+        }
 
-        thread::sleep(Duration::from_millis(1_000));
+        let woken = Arc::new(SemaphoreWaker(Semaphore::new(0, 8, None).unwrap()));
+        let waker = Waker::from(woken.clone());
 
-        e.set().unwrap();
+        let e = Arc::new(Event::new_auto(false, None).unwrap()); // Not signaled.
 
-        // Now both have exited.
+        assert!(e.poll_wait(&waker).is_pending());
+        assert!(e.poll_wait(&waker).is_pending()); // Already registered.
 
-        let res_1 = t_1.join().unwrap();
+        let t = {
+            let e = e.clone();
 
-        assert!(res_1.0 == WaitableResult::Signaled);
-        assert!(res_1.1.as_millis() >= 500);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                e.set().unwrap();
+            })
+        };
 
-        let res_2 = t_2.join().unwrap();
+        let res = woken.0.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
 
-        assert!(res_2.0 == WaitableResult::Signaled);
-        assert!(res_2.1.as_millis() >= 500);
+        // Woken once.
+        let res = woken.0.wait(Duration::from_millis(10)).unwrap();
+        assert!(res == WaitableResult::Timeout);
 
-        if res_1.1.as_millis() > res_2.1.as_millis() {
-            assert!(res_1.1.as_millis() - res_2.1.as_millis() >= 500);
-        } else {
-            assert!(res_2.1.as_millis() - res_1.1.as_millis() >= 500);
-        }
+        t.join().unwrap();
 
-        // Not signaled.
+        assert!(matches!(
+            e.poll_wait(&waker),
+            Poll::Ready(Ok(WaitableResult::Signaled))
+        ));
 
+        // The signal was consumed exactly once.
         let res = e.wait(Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Timeout);
+
+        // Already signaled.
+
+        e.set().unwrap();
+
+        assert!(matches!(
+            e.poll_wait(&waker),
+            Poll::Ready(Ok(WaitableResult::Signaled))
+        ));
+
+        // Cancelled.
+
+        assert!(e.poll_wait(&waker).is_pending());
+        assert!(!e.cancel_poll_wait().unwrap());
+
+        e.set().unwrap();
+
+        let res = woken.0.wait(Duration::from_millis(10)).unwrap();
+        assert!(res == WaitableResult::Timeout); // Not woken.
+
+        let res = e.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled); // Signal not consumed by the cancelled wait.
     }
 }
@@ -0,0 +1,225 @@
+use {
+    crate::{Event, EventError, WaitError, Waitable, WaitableResult},
+    std::{
+        sync::atomic::{AtomicU8, Ordering},
+        time::Duration,
+    },
+};
+
+const CLOSED: u8 = 0;
+const OPENING: u8 = 1;
+const OPEN: u8 = 2;
+const POISONED: u8 = 3;
+
+/// One-time initialization gate - a `std::sync::Once` which may also be waited on like a manual event.
+///
+/// The first caller of [`open_with`] runs the initialization closure and then opens the gate.
+/// All callers of [`open_with`], including the first, return once the gate is open.
+/// Threads which don't take part in the initialization may instead wait for the gate
+/// to open via its [`Waitable`] methods, or alongside other waitables.
+///
+/// [`open_with`]: #method.open_with
+/// [`Waitable`]: trait.Waitable.html
+pub struct Gate {
+    event: Event,
+    state: AtomicU8,
+}
+
+impl Gate {
+    /// Creates a new closed gate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS event creation failed.
+    pub fn new() -> Result<Gate, EventError> {
+        Ok(Gate {
+            event: Event::new_manual(false, None)?,
+            state: AtomicU8::new(CLOSED),
+        })
+    }
+
+    /// Runs the closure `f` if this is the first call, then opens the gate.
+    /// Blocks the thread until the gate is open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails - including, for the first call, if opening the gate fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the closure `f` of the first call panicked.
+    /// The gate is opened regardless, so that waiting threads are not blocked forever.
+    pub fn open_with<F: FnOnce()>(&self, f: F) -> Result<(), EventError> {
+        if self
+            .state
+            .compare_exchange(CLOSED, OPENING, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            let mut guard = OpenGuard {
+                gate: self,
+                completed: false,
+            };
+
+            f();
+
+            guard.completed = true;
+
+            self.state.store(OPEN, Ordering::Release);
+            self.event.set()?;
+        }
+
+        self.event
+            .wait_infinite()
+            .map_err(|err| EventError::FailedToWait(err.into()))?;
+
+        if self.state.load(Ordering::Acquire) == POISONED {
+            panic!("the gate initialization closure panicked");
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the gate is open.
+    pub fn is_open(&self) -> bool {
+        matches!(self.state.load(Ordering::Acquire), OPEN | POISONED)
+    }
+}
+
+/// Poisons and opens the gate if the initialization closure panics.
+struct OpenGuard<'a> {
+    gate: &'a Gate,
+    /// Set when the initialization closure returned.
+    completed: bool,
+}
+
+impl<'a> Drop for OpenGuard<'a> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.gate.state.store(POISONED, Ordering::Release);
+
+            let res = self.gate.event.set();
+            debug_assert!(res.is_ok(), "failed to open the poisoned gate");
+        }
+    }
+}
+
+impl Waitable for Gate {
+    /// Blocks the thread until the gate is open or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.event.wait(d)
+    }
+
+    /// Blocks the thread until the gate is open or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.event.wait_raw_ms(ms)
+    }
+}
+
+#[cfg(windows)]
+impl crate::WaitableExt for Gate {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.event.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            sync::{atomic::AtomicUsize, Arc},
+            thread,
+        },
+    };
+
+    #[test]
+    fn init_once() {
+        let gate = Arc::new(Gate::new().unwrap());
+        let num_inits = Arc::new(AtomicUsize::new(0));
+
+        assert!(!gate.is_open());
+
+        let res = gate.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let gate = gate.clone();
+                let num_inits = num_inits.clone();
+
+                thread::spawn(move || {
+                    gate.open_with(|| {
+                        thread::sleep(Duration::from_millis(50));
+                        num_inits.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .unwrap();
+
+                    // Initialization is complete for every thread.
+                    assert_eq!(num_inits.load(Ordering::SeqCst), 1);
+                })
+            })
+            .collect();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(num_inits.load(Ordering::SeqCst), 1);
+        assert!(gate.is_open());
+
+        let res = gate.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        gate.open_with(|| panic!()).unwrap(); // Already open.
+    }
+
+    #[test]
+    fn poisoned() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let gate = Gate::new().unwrap();
+
+        catch_unwind(AssertUnwindSafe(|| {
+            gate.open_with(|| panic!("init failed"))
+        }))
+        .unwrap_err();
+        assert!(gate.is_open());
+
+        catch_unwind(AssertUnwindSafe(|| gate.open_with(|| {}))).unwrap_err();
+    }
+
+    #[test]
+    fn open_while_unwinding() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        /// Opens the gate when dropped - e.g. during an unrelated unwind.
+        struct OpenOnDrop<'g>(&'g Gate);
+
+        impl<'g> Drop for OpenOnDrop<'g> {
+            fn drop(&mut self) {
+                self.0.open_with(|| {}).unwrap();
+            }
+        }
+
+        let gate = Gate::new().unwrap();
+
+        catch_unwind(AssertUnwindSafe(|| {
+            let _open = OpenOnDrop(&gate);
+            panic!("unrelated");
+        }))
+        .unwrap_err();
+
+        // The closure completed - not poisoned.
+        assert!(gate.is_open());
+        gate.open_with(|| {}).unwrap();
+    }
+}
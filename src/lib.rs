@@ -2,7 +2,9 @@
 //!
 //! Thin Rust wrapper around the Windows manual-, auto-reset events and semaphores.
 //!
-//! Technically provides a portable API, but implemented only for Windows at the moment.
+//! Technically provides a portable API, but implemented only for Windows at the moment,
+//! except for [`Event`](struct.Event.html) and [`Semaphore`](struct.Semaphore.html),
+//! which are also implemented for Linux.
 //!
 //! See [`event`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-createeventa),
 //! [`semaphore`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-createsemaphorea),
@@ -10,19 +12,69 @@
 //!
 //! Run `cargo --doc` for documentation.
 //!
-//! Uses [`winapi`](https://docs.rs/winapi/*/winapi/) on Windows.
+//! Uses [`winapi`](https://docs.rs/winapi/*/winapi/) on Windows, [`libc`](https://docs.rs/libc/*/libc/) on Linux.
 
+mod any_of;
+mod barrier;
+mod countdown;
+#[cfg(feature = "deterministic")]
+pub mod deterministic;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
 pub mod event;
+mod gate;
+#[cfg(windows)]
+mod ntdll;
+#[cfg(feature = "io")]
+pub mod overlapped;
 pub mod semaphore;
+mod stop_handle;
+pub mod timer;
+pub mod timer_queue;
 pub mod waitable;
 
 pub use {
-    event::{Event, EventError},
-    semaphore::{Semaphore, SemaphoreError},
+    any_of::AnyOf,
+    barrier::{Barrier, BarrierArrival, BarrierWaitResult},
+    countdown::CountdownEvent,
+    event::{AutoResetEvent, CoalescingAutoEvent, Event, EventError, EventSetGuard, ManualResetEvent},
+    gate::Gate,
+    semaphore::{IncrementReport, Semaphore, SemaphoreError, SemaphoreGuard},
+    stop_handle::{spawn_with_stop, StopHandle},
+    timer::TimerError,
+    timer_queue::TimerQueueError,
     waitable::{
-        wait_for_one, Waitable, WaitableResult, WaitablesResult,
+        duration_to_timeout_ms, wait_for_all, wait_for_all_boxed, wait_for_all_infinite, wait_for_all_of, wait_for_all_sequential,
+        wait_for_all_until, wait_for_one, wait_for_one_boxed, wait_for_one_infinite, wait_for_one_iter, wait_for_one_of,
+        wait_for_one_ref, wait_for_one_sequential, wait_for_one_until, IntoIoResult, PartialResult, WaitError, WaitGroup,
+        WaitGroupResult, WaitStrategy, Waitable, WaitableResult, WaitableTuple, WaitablesResult, WatchedResult, INFINITE_MS,
     },
 };
 
 #[cfg(windows)]
-pub use waitable::{max_num_waitables, wait_for_all, WaitableExt};
+pub use waitable::{
+    all_signaled, clear_interrupt, interrupt_all, max_num_waitables, signal_and_wait, wait_for_all_alertable, wait_for_all_detailed,
+    wait_for_all_or_partial, wait_for_all_typed, wait_for_one_alertable, wait_for_one_chunked, wait_for_one_msg, wait_for_one_profiled,
+    wait_for_one_typed, wait_for_one_with_buf, FairWaitSet, InterruptibleResult, MsgWaitResult, ProcessWaitable, ReusableWaitSet,
+    ThreadPriority, WaitSet, WaitableExt,
+};
+
+#[cfg(windows)]
+pub use event::{
+    wait_then_signal, EventAccess, EventOptions, FastEvent, OneShotEvent, OneShotResult, ProcessExitEvent, WaitRegistration,
+};
+
+#[cfg(all(windows, feature = "async"))]
+pub use event::EventFuture;
+
+#[cfg(windows)]
+pub use semaphore::SemaphoreOptions;
+
+#[cfg(windows)]
+pub use timer::WaitableTimer;
+
+#[cfg(windows)]
+pub use timer_queue::{TimerHandle, TimerQueue};
+
+#[cfg(all(windows, feature = "io"))]
+pub use overlapped::OverlappedWaitable;
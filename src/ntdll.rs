@@ -0,0 +1,150 @@
+//! Native API functions not exposed by `winapi`.
+//! See [`Windows Native API`](https://docs.microsoft.com/en-us/windows/win32/api/winternl/) on MSDN.
+
+#![allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
+
+use {
+    std::{io, mem, slice},
+    winapi::shared::{
+        minwindef::ULONG,
+        ntdef::{HANDLE, LONG, NTSTATUS, NT_SUCCESS, PVOID, UNICODE_STRING},
+    },
+};
+
+const SemaphoreBasicInformation: ULONG = 0;
+const EventBasicInformation: ULONG = 0;
+const ObjectTypeInformation: ULONG = 2;
+
+/// `EVENT_TYPE::NotificationEvent` - i.e. a manual reset event.
+const NotificationEvent: LONG = 0;
+
+#[repr(C)]
+struct SEMAPHORE_BASIC_INFORMATION {
+    CurrentCount: LONG,
+    MaximumCount: LONG,
+}
+
+#[repr(C)]
+struct EVENT_BASIC_INFORMATION {
+    EventType: LONG,
+    EventState: LONG,
+}
+
+#[repr(C)]
+struct PUBLIC_OBJECT_TYPE_INFORMATION {
+    TypeName: UNICODE_STRING,
+    Reserved: [ULONG; 22],
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQuerySemaphore(
+        SemaphoreHandle: HANDLE,
+        SemaphoreInformationClass: ULONG,
+        SemaphoreInformation: PVOID,
+        SemaphoreInformationLength: ULONG,
+        ReturnLength: *mut ULONG,
+    ) -> NTSTATUS;
+
+    fn NtQueryEvent(
+        EventHandle: HANDLE,
+        EventInformationClass: ULONG,
+        EventInformation: PVOID,
+        EventInformationLength: ULONG,
+        ReturnLength: *mut ULONG,
+    ) -> NTSTATUS;
+
+    fn NtQueryObject(
+        Handle: HANDLE,
+        ObjectInformationClass: ULONG,
+        ObjectInformation: PVOID,
+        ObjectInformationLength: ULONG,
+        ReturnLength: *mut ULONG,
+    ) -> NTSTATUS;
+
+    fn RtlNtStatusToDosError(Status: NTSTATUS) -> ULONG;
+}
+
+/// Returns the `(current, maximum)` counts of the semaphore `handle`.
+pub(crate) fn query_semaphore(handle: HANDLE) -> io::Result<(usize, usize)> {
+    let mut info = SEMAPHORE_BASIC_INFORMATION {
+        CurrentCount: 0,
+        MaximumCount: 0,
+    };
+
+    let status = unsafe {
+        NtQuerySemaphore(
+            handle,
+            SemaphoreBasicInformation,
+            &mut info as *mut _ as PVOID,
+            mem::size_of::<SEMAPHORE_BASIC_INFORMATION>() as ULONG,
+            std::ptr::null_mut(),
+        )
+    };
+
+    if NT_SUCCESS(status) {
+        Ok((info.CurrentCount as usize, info.MaximumCount as usize))
+    } else {
+        Err(status_to_error(status))
+    }
+}
+
+/// Returns `true` if the event `handle` is a manual reset event.
+pub(crate) fn query_event_is_manual(handle: HANDLE) -> io::Result<bool> {
+    let mut info = EVENT_BASIC_INFORMATION {
+        EventType: 0,
+        EventState: 0,
+    };
+
+    let status = unsafe {
+        NtQueryEvent(
+            handle,
+            EventBasicInformation,
+            &mut info as *mut _ as PVOID,
+            mem::size_of::<EVENT_BASIC_INFORMATION>() as ULONG,
+            std::ptr::null_mut(),
+        )
+    };
+
+    if NT_SUCCESS(status) {
+        Ok(info.EventType == NotificationEvent)
+    } else {
+        Err(status_to_error(status))
+    }
+}
+
+/// Returns the type name of the kernel object `handle` refers to - e.g. `"Event"`, `"Semaphore"`.
+pub(crate) fn query_object_type(handle: HANDLE) -> io::Result<String> {
+    // Type name is written to the buffer right after the structure.
+    let mut buffer = [0u64; 128];
+
+    let status = unsafe {
+        NtQueryObject(
+            handle,
+            ObjectTypeInformation,
+            buffer.as_mut_ptr() as PVOID,
+            mem::size_of_val(&buffer) as ULONG,
+            std::ptr::null_mut(),
+        )
+    };
+
+    if !NT_SUCCESS(status) {
+        return Err(status_to_error(status));
+    }
+
+    let info = unsafe { &*(buffer.as_ptr() as *const PUBLIC_OBJECT_TYPE_INFORMATION) };
+
+    // `Length` is in bytes.
+    let name = unsafe {
+        slice::from_raw_parts(
+            info.TypeName.Buffer,
+            info.TypeName.Length as usize / mem::size_of::<u16>(),
+        )
+    };
+
+    Ok(String::from_utf16_lossy(name))
+}
+
+fn status_to_error(status: NTSTATUS) -> io::Error {
+    io::Error::from_raw_os_error(unsafe { RtlNtStatusToDosError(status) } as i32)
+}
@@ -0,0 +1,5 @@
+#[cfg(windows)]
+mod win;
+
+#[cfg(windows)]
+pub use win::OverlappedWaitable;
@@ -0,0 +1,204 @@
+use {
+    crate::{WaitError, Waitable, WaitableExt, WaitableResult},
+    std::io,
+    winapi::{
+        shared::{minwindef::FALSE, winerror::WAIT_TIMEOUT},
+        um::{
+            ioapiset::GetOverlappedResult, minwinbase::OVERLAPPED, synchapi::WaitForSingleObject,
+            winbase::WAIT_OBJECT_0, winnt::HANDLE,
+        },
+    },
+};
+
+/// Waitable wrapper around the completion event of an overlapped I/O operation.
+/// See [`Synchronous and Asynchronous I/O`](https://docs.microsoft.com/en-us/windows/win32/fileio/synchronous-and-asynchronous-i-o) on MSDN.
+///
+/// Borrows an `OVERLAPPED` structure whose `hEvent` member is signaled by the OS
+/// when the I/O operation completes, allowing the operation to be waited on
+/// alongside other waitables in [`wait_for_one`] / [`wait_for_all`].
+///
+/// The caller owns both the `OVERLAPPED` structure and its `hEvent`,
+/// and must keep them alive and in place until the I/O operation completes.
+/// Does not close any handles when dropped.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub struct OverlappedWaitable<'a> {
+    file: HANDLE,
+    overlapped: &'a OVERLAPPED,
+}
+
+impl<'a> OverlappedWaitable<'a> {
+    /// Creates a new waitable for the overlapped I/O operation described by `overlapped`.
+    ///
+    /// `file` - the raw handle of the file / pipe / device the operation was issued on.
+    /// `overlapped` - the structure passed to the I/O function. Its `hEvent` member must be a valid event handle.
+    pub fn new(file: *mut (), overlapped: &'a OVERLAPPED) -> Self {
+        Self {
+            file: file as HANDLE,
+            overlapped,
+        }
+    }
+
+    /// Returns the number of bytes transferred by the completed I/O operation.
+    ///
+    /// Call after the waitable was signaled. Does not block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the I/O operation failed,
+    /// or with `ERROR_IO_INCOMPLETE` if it has not completed yet.
+    pub fn result(&self) -> io::Result<usize> {
+        let mut num_bytes = 0;
+
+        let result = unsafe {
+            GetOverlappedResult(
+                self.file,
+                self.overlapped as *const _ as *mut _,
+                &mut num_bytes,
+                FALSE,
+            )
+        };
+
+        if result == FALSE {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(num_bytes as usize)
+        }
+    }
+
+    fn wait_impl(&self, ms: u32) -> Result<WaitableResult, io::Error> {
+        let result = unsafe { WaitForSingleObject(self.overlapped.hEvent, ms) };
+
+        match result {
+            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+impl<'a> Waitable for OverlappedWaitable<'a> {
+    /// Blocks the thread until the I/O operation completes or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(ms).map_err(WaitError::FailedToWait)
+    }
+}
+
+impl<'a> WaitableExt for OverlappedWaitable<'a> {
+    /// Returns the raw handle / pointer to the `OVERLAPPED` structure's `hEvent`.
+    fn handle(&self) -> *mut () {
+        self.overlapped.hEvent as *mut ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{wait_for_one_infinite, Event, WaitablesResult},
+        std::{ffi::CString, mem, ptr, time::Duration},
+        winapi::{
+            shared::winerror::ERROR_IO_PENDING,
+            um::{
+                fileapi::{CreateFileA, ReadFile, WriteFile, OPEN_EXISTING},
+                handleapi::{CloseHandle, INVALID_HANDLE_VALUE},
+                winbase::{
+                    CreateNamedPipeA, FILE_FLAG_OVERLAPPED, PIPE_ACCESS_INBOUND, PIPE_TYPE_BYTE,
+                },
+                winnt::GENERIC_WRITE,
+            },
+        },
+    };
+
+    #[test]
+    fn overlapped_pipe_read() {
+        let name = CString::new(r"\\.\pipe\minievent_overlapped_pipe_read").unwrap();
+
+        let server = unsafe {
+            CreateNamedPipeA(
+                name.as_ptr(),
+                PIPE_ACCESS_INBOUND | FILE_FLAG_OVERLAPPED,
+                PIPE_TYPE_BYTE,
+                1,
+                0,
+                64,
+                0,
+                ptr::null_mut(),
+            )
+        };
+        assert!(server != INVALID_HANDLE_VALUE);
+
+        let client = unsafe {
+            CreateFileA(
+                name.as_ptr(),
+                GENERIC_WRITE,
+                0,
+                ptr::null_mut(),
+                OPEN_EXISTING,
+                0,
+                ptr::null_mut(),
+            )
+        };
+        assert!(client != INVALID_HANDLE_VALUE);
+
+        let e = Event::new_manual(false, None).unwrap();
+
+        let mut overlapped: OVERLAPPED = unsafe { mem::zeroed() };
+        overlapped.hEvent = e.handle() as HANDLE;
+
+        let mut buf = [0u8; 16];
+
+        let result = unsafe {
+            ReadFile(
+                server,
+                buf.as_mut_ptr() as _,
+                buf.len() as u32,
+                ptr::null_mut(),
+                &mut overlapped,
+            )
+        };
+        assert!(result == FALSE);
+        assert!(io::Error::last_os_error().raw_os_error() == Some(ERROR_IO_PENDING as i32));
+
+        let o = OverlappedWaitable::new(server as _, &overlapped);
+
+        // Nothing written yet.
+
+        let res = o.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        o.result().err().unwrap(); // Must have failed - still pending.
+
+        let data = b"hello";
+        let mut written = 0;
+
+        let result = unsafe {
+            WriteFile(
+                client,
+                data.as_ptr() as _,
+                data.len() as u32,
+                &mut written,
+                ptr::null_mut(),
+            )
+        };
+        assert!(result != FALSE);
+
+        let w = [&o as _];
+
+        let res = wait_for_one_infinite(&w).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+
+        assert_eq!(o.result().unwrap(), data.len());
+        assert_eq!(&buf[..data.len()], data);
+
+        unsafe {
+            CloseHandle(client);
+            CloseHandle(server);
+        }
+    }
+}
@@ -7,12 +7,109 @@ use std::{
 #[derive(Debug)]
 pub enum SemaphoreError {
     FailedToCreate(io::Error),
-    InvalidName,
+    /// The semaphore name contained a nul byte at `nul_position`.
+    /// An empty name is reported as a nul byte at position `0` - i.e. the name's terminator.
+    InvalidName {
+        nul_position: usize,
+    },
+    /// The semaphore name was `len` bytes long, exceeding `MAX_PATH`.
+    NameTooLong {
+        len: usize,
+    },
+    /// The semaphore name already had a namespace prefix different from the requested one.
+    ConflictingNamespace,
+    InvalidMaxCount,
     FailedToIncrement(io::Error),
+    /// Incrementing the semaphore's internal counter failed partway -
+    /// it was only incremented by `incremented` (less than the requested count).
+    ///
+    /// Only returned on Linux, where the counter is incremented one at a time.
+    PartiallyIncremented {
+        incremented: usize,
+        err: io::Error,
+    },
     FailedToWait(io::Error),
+    FailedToQuery(io::Error),
+    FailedToClose(io::Error),
+    Closed,
+    InvalidHandle(io::Error),
+    WrongObjectType,
+    InvalidWaitStrategy,
+    Unsupported,
+    FailedToOpen(io::Error),
+    NotFound,
 }
 
-impl Error for SemaphoreError {}
+impl SemaphoreError {
+    /// Returns the underlying OS error, if any.
+    pub fn os_error(&self) -> Option<&io::Error> {
+        use SemaphoreError::*;
+
+        match self {
+            FailedToCreate(err)
+            | FailedToIncrement(err)
+            | FailedToWait(err)
+            | FailedToQuery(err)
+            | FailedToClose(err)
+            | InvalidHandle(err)
+            | FailedToOpen(err)
+            | PartiallyIncremented { err, .. } => Some(err),
+            InvalidName { .. }
+            | NameTooLong { .. }
+            | ConflictingNamespace
+            | InvalidMaxCount
+            | Closed
+            | WrongObjectType
+            | InvalidWaitStrategy
+            | Unsupported
+            | NotFound => None,
+        }
+    }
+
+    /// Returns `true` if the semaphore name was invalid, too long or had a conflicting namespace prefix.
+    pub fn is_invalid_name(&self) -> bool {
+        matches!(
+            self,
+            SemaphoreError::InvalidName { .. }
+                | SemaphoreError::NameTooLong { .. }
+                | SemaphoreError::ConflictingNamespace
+        )
+    }
+
+    /// Returns `true` if the semaphore maximum count was invalid.
+    pub fn is_invalid_max_count(&self) -> bool {
+        matches!(self, SemaphoreError::InvalidMaxCount)
+    }
+
+    /// Returns `true` if the semaphore was closed.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, SemaphoreError::Closed)
+    }
+
+    /// Returns `true` if the handle was invalid or did not refer to a semaphore.
+    pub fn is_invalid_handle(&self) -> bool {
+        matches!(
+            self,
+            SemaphoreError::InvalidHandle(_) | SemaphoreError::WrongObjectType
+        )
+    }
+
+    /// Returns `true` if the wait strategy was invalid.
+    pub fn is_invalid_wait_strategy(&self) -> bool {
+        matches!(self, SemaphoreError::InvalidWaitStrategy)
+    }
+
+    /// Returns `true` if the named semaphore does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, SemaphoreError::NotFound)
+    }
+}
+
+impl Error for SemaphoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.os_error().map(|err| err as _)
+    }
+}
 
 impl Display for SemaphoreError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
@@ -20,9 +117,57 @@ impl Display for SemaphoreError {
 
         match self {
             FailedToCreate(err) => write!(f, "failed to create the semaphore: {}", err),
-            InvalidName => "invalid semaphore name".fmt(f),
+            InvalidName { nul_position } => write!(
+                f,
+                "invalid semaphore name: nul byte at position {}",
+                nul_position
+            ),
+            NameTooLong { len } => write!(f, "semaphore name is too long: {} bytes", len),
+            ConflictingNamespace => "semaphore name has a conflicting namespace prefix".fmt(f),
+            InvalidMaxCount => "semaphore maximum count must be at least 1".fmt(f),
             FailedToIncrement(err) => write!(f, "failed to increment the semaphore: {}", err),
+            PartiallyIncremented { incremented, err } => write!(
+                f,
+                "failed to increment the semaphore: {} (only incremented by {})",
+                err, incremented
+            ),
             FailedToWait(err) => write!(f, "failed to wait on the semaphore: {}", err),
+            FailedToQuery(err) => write!(f, "failed to query the semaphore: {}", err),
+            FailedToClose(err) => write!(f, "failed to close the semaphore: {}", err),
+            Closed => "the semaphore was closed".fmt(f),
+            InvalidHandle(err) => write!(f, "invalid semaphore handle: {}", err),
+            WrongObjectType => "the handle does not refer to a semaphore".fmt(f),
+            InvalidWaitStrategy => "invalid wait strategy".fmt(f),
+            Unsupported => "the operation is not supported on this platform".fmt(f),
+            FailedToOpen(err) => write!(f, "failed to open the semaphore: {}", err),
+            NotFound => "no semaphore with the given name exists".fmt(f),
+        }
+    }
+}
+
+impl From<SemaphoreError> for io::Error {
+    /// Unwraps the underlying OS error, if any.
+    fn from(err: SemaphoreError) -> Self {
+        use SemaphoreError::*;
+
+        match err {
+            FailedToCreate(err)
+            | FailedToIncrement(err)
+            | FailedToWait(err)
+            | FailedToQuery(err)
+            | FailedToClose(err)
+            | InvalidHandle(err)
+            | FailedToOpen(err)
+            | PartiallyIncremented { err, .. } => err,
+            err @ (InvalidName { .. }
+            | NameTooLong { .. }
+            | ConflictingNamespace
+            | InvalidMaxCount
+            | Closed
+            | WrongObjectType
+            | InvalidWaitStrategy) => io::Error::new(io::ErrorKind::InvalidInput, err),
+            Unsupported => io::Error::new(io::ErrorKind::Unsupported, Unsupported),
+            NotFound => io::Error::new(io::ErrorKind::NotFound, NotFound),
         }
     }
 }
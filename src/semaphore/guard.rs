@@ -0,0 +1,170 @@
+use {
+    crate::{Semaphore, SemaphoreError},
+    std::{
+        fmt::{Debug, Formatter},
+        time::Duration,
+    },
+};
+
+#[cfg(windows)]
+use crate::{wait_for_all, WaitableExt, WaitableResult};
+
+#[cfg(not(windows))]
+use std::time::Instant;
+
+impl Semaphore {
+    /// Blocks the thread until all `semaphores` are signaled or the duration `d` expires.
+    ///
+    /// If all semaphores were signaled, decrements each one's internal counter and returns the guards
+    /// which [`increment`] them back by `1` when dropped. Returns `None` on timeout, having acquired none of them.
+    ///
+    /// On Windows, implemented via [`wait_for_all`], which decrements all semaphores atomically -
+    /// so, unlike acquiring the semaphores one by one, this cannot deadlock with another thread
+    /// acquiring (some of) the same semaphores in a different order.
+    /// On other platforms, acquires the semaphores in turn within the duration `d`,
+    /// and rolls back (i.e. increments) the ones already acquired on timeout or error;
+    /// two threads acquiring overlapping sets of semaphores in different orders may thus both time out.
+    ///
+    /// An empty `semaphores` slice is trivially acquired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    /// On Windows, the len of `semaphores` may not exceed the value returned by [`max_num_waitables`],
+    /// and the same semaphore may not be passed more than once.
+    ///
+    /// [`increment`]: #method.increment
+    /// [`wait_for_all`]: fn.wait_for_all.html
+    /// [`max_num_waitables`]: fn.max_num_waitables.html
+    pub fn acquire_all<'a>(
+        semaphores: &[&'a Semaphore],
+        d: Duration,
+    ) -> Result<Option<Vec<SemaphoreGuard<'a>>>, SemaphoreError> {
+        if semaphores.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        #[cfg(windows)]
+        {
+            let waitables: Vec<_> = semaphores.iter().map(|&s| s as &dyn WaitableExt).collect();
+
+            match wait_for_all(&waitables, d)
+                .map_err(|err| SemaphoreError::FailedToWait(err.into()))?
+            {
+                WaitableResult::Signaled => Ok(Some(
+                    semaphores.iter().map(|&s| SemaphoreGuard::new(s)).collect(),
+                )),
+                // Timeout. Semaphores are never abandoned, and not an alertable wait.
+                _ => Ok(None),
+            }
+        }
+
+        #[cfg(not(windows))]
+        {
+            // Overflow - wait forever.
+            let deadline = Instant::now().checked_add(d);
+            let mut guards = Vec::with_capacity(semaphores.len());
+
+            for s in semaphores {
+                let remaining = match deadline {
+                    Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                    None => d,
+                };
+
+                // Dropping the `guards` on early return rolls back the semaphores acquired so far.
+                match s.acquire_timeout(remaining)? {
+                    Some(guard) => guards.push(guard),
+                    None => return Ok(None),
+                }
+            }
+
+            Ok(Some(guards))
+        }
+    }
+}
+
+/// Scoped semaphore acquisition returned by [`acquire`] / [`acquire_timeout`].
+///
+/// [`increment`]s the semaphore's internal counter by `1` when dropped.
+/// Increment errors are ignored, as the guard may be dropped while unwinding.
+///
+/// [`acquire`]: struct.Semaphore.html#method.acquire
+/// [`acquire_timeout`]: struct.Semaphore.html#method.acquire_timeout
+/// [`increment`]: struct.Semaphore.html#method.increment
+#[must_use = "the semaphore is released immediately if the guard is not held"]
+pub struct SemaphoreGuard<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl<'a> SemaphoreGuard<'a> {
+    pub(super) fn new(semaphore: &'a Semaphore) -> Self {
+        Self { semaphore }
+    }
+
+    /// Returns the acquired semaphore.
+    pub fn semaphore(&self) -> &'a Semaphore {
+        self.semaphore
+    }
+}
+
+impl<'a> Drop for SemaphoreGuard<'a> {
+    fn drop(&mut self) {
+        let _ = self.semaphore.increment_one();
+    }
+}
+
+impl<'a> Debug for SemaphoreGuard<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("SemaphoreGuard")
+            .field("semaphore", self.semaphore)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acquire_all() {
+        let a = Semaphore::new(1, 1, None).unwrap();
+        let b = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
+
+        // Acquired none of them.
+        assert!(Semaphore::acquire_all(&[&a, &b], Duration::from_millis(1))
+            .unwrap()
+            .is_none());
+        assert_eq!(a.count().unwrap(), 1);
+        assert_eq!(b.count().unwrap(), 0);
+
+        b.increment(1).unwrap();
+
+        {
+            let guards = Semaphore::acquire_all(&[&a, &b], Duration::from_millis(1))
+                .unwrap()
+                .unwrap();
+            assert_eq!(guards.len(), 2);
+
+            assert_eq!(a.count().unwrap(), 0);
+            assert_eq!(b.count().unwrap(), 0);
+        }
+
+        // Released by the guards.
+        assert_eq!(a.count().unwrap(), 1);
+        assert_eq!(b.count().unwrap(), 1);
+
+        // Infinite wait.
+        assert_eq!(
+            Semaphore::acquire_all(&[&a, &b], Duration::MAX)
+                .unwrap()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        assert!(Semaphore::acquire_all(&[], Duration::from_millis(1))
+            .unwrap()
+            .unwrap()
+            .is_empty());
+    }
+}
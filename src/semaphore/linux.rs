@@ -0,0 +1,553 @@
+use {
+    super::SemaphoreGuard,
+    crate::{SemaphoreError, WaitError, Waitable, WaitableResult, INFINITE_MS},
+    std::{
+        cell::UnsafeCell,
+        convert::TryFrom,
+        fmt::{Debug, Formatter},
+        io, mem,
+        sync::Mutex,
+        time::Duration,
+    },
+};
+
+/// Waitable semaphore wrapper.
+/// See [`sem_overview`](https://man7.org/linux/man-pages/man7/sem_overview.7.html).
+///
+/// The semaphore is signaled when the internal counter is above `0`.
+/// The internal counter is initialized to `init_count` by [`new`].
+/// When [`increment`] is called with `count` argument, at most `count` threads
+/// will wake up and the counter will be decremented for each woken up thread.
+///
+/// Backed by an unnamed POSIX semaphore. POSIX semaphores have no maximum count,
+/// so the wrapper enforces the `max_count` passed to [`new`] itself.
+/// Unlike on Windows, semaphores may not be named / shared with other processes.
+///
+/// Destroys the owned POSIX semaphore when dropped, unless it was already [`close`]d.
+///
+/// [`new`]: #method.new
+/// [`increment`]: #method.increment
+/// [`close`]: #method.close
+pub struct Semaphore {
+    /// `None` if the semaphore was [`close`](#method.close)d.
+    /// Boxed, as the POSIX semaphore may not be moved once initialized.
+    sem: Option<Box<UnsafeCell<libc::sem_t>>>,
+    label: Option<&'static str>,
+    #[cfg(feature = "deterministic")]
+    scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    max_count: usize,
+    /// Serializes the increments, so the internal counter never exceeds `max_count`.
+    /// Concurrent waits only ever decrement the counter.
+    increment_lock: Mutex<()>,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore.
+    ///
+    /// `init_count` - initializes the internal counter value. Clamped to be less or equal to `max_count`.
+    /// `max_count` - determines the maximum value the internal counter may be incremented to
+    /// before the call to [`increment`] fails.
+    /// `name` - must be `None` or empty - named semaphores are not supported on this platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS semaphore creation failed, if a `name` was given,
+    /// or if `max_count` is `0` or exceeds `SEM_VALUE_MAX`.
+    ///
+    /// [`increment`]: #method.increment
+    pub fn new(
+        init_count: usize,
+        max_count: usize,
+        name: Option<&str>,
+    ) -> Result<Semaphore, SemaphoreError> {
+        use SemaphoreError::*;
+
+        if max_count == 0 || max_count > i32::MAX as usize {
+            return Err(InvalidMaxCount);
+        }
+
+        if name.is_some_and(|name| !name.is_empty()) {
+            return Err(Unsupported);
+        }
+
+        let init_count = init_count.min(max_count);
+
+        let sem = Box::new(UnsafeCell::new(unsafe { mem::zeroed() }));
+
+        if unsafe { libc::sem_init(sem.get(), 0, init_count as u32) } < 0 {
+            return Err(FailedToCreate(io::Error::last_os_error()));
+        }
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            sem.get() as *mut (),
+            None,
+            crate::diagnostics::ObjectKind::Semaphore,
+        );
+
+        Ok(Semaphore {
+            sem: Some(sem),
+            label: None,
+            #[cfg(feature = "deterministic")]
+            scheduler: crate::deterministic::os_scheduler(),
+            max_count,
+            increment_lock: Mutex::new(()),
+        })
+    }
+
+    /// Increments the semaphore's internal counter by `count`.
+    /// Up to `count` waiting threads may be woken up.
+    ///
+    /// Fails if the internal counter value would overflow its maximum value
+    /// as determined by `max_count` in [`new`] if `count` was to be added to it,
+    /// or if `count` is `0`.
+    ///
+    /// On success returns the previous counter value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SemaphoreError::PartiallyIncremented`] if the OS function fails
+    /// after the counter was already incremented by less than `count`.
+    ///
+    /// [`new`]: #method.new
+    /// [`SemaphoreError::PartiallyIncremented`]: enum.SemaphoreError.html#variant.PartiallyIncremented
+    pub fn increment(&self, count: usize) -> Result<usize, SemaphoreError> {
+        use SemaphoreError::*;
+
+        let sem = self.live_sem()?;
+
+        if count == 0 {
+            return Err(FailedToIncrement(io::Error::from_raw_os_error(
+                libc::EINVAL,
+            )));
+        }
+
+        // Never propagate a poisoned lock - it protects no data.
+        let _lock = self
+            .increment_lock
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        let prev_count = get_value(sem).map_err(FailedToIncrement)?;
+
+        if prev_count.saturating_add(count) > self.max_count {
+            return Err(FailedToIncrement(io::Error::from_raw_os_error(
+                libc::EOVERFLOW,
+            )));
+        }
+
+        // Only once the increment is known to be valid.
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_signal(sem as *mut ());
+
+        for incremented in 0..count {
+            if unsafe { libc::sem_post(sem) } < 0 {
+                let err = io::Error::last_os_error();
+
+                return Err(if incremented == 0 {
+                    FailedToIncrement(err)
+                } else {
+                    PartiallyIncremented { incremented, err }
+                });
+            }
+        }
+
+        Ok(prev_count)
+    }
+
+    /// Increments the semaphore's internal counter by up to `count`, never exceeding its maximum value
+    /// as determined by `max_count` in [`new`].
+    /// Up to that many waiting threads may be woken up.
+    ///
+    /// Unlike [`increment`], does not fail if `count` would overflow the maximum value -
+    /// instead releases as much as there is headroom for.
+    ///
+    /// On success returns the number actually added to the counter - `0` if it was already at its maximum value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`new`]: #method.new
+    /// [`increment`]: #method.increment
+    pub fn increment_saturating(&self, count: usize) -> Result<usize, SemaphoreError> {
+        loop {
+            let allowed = self.max_count.saturating_sub(self.count()?).min(count);
+
+            if allowed == 0 {
+                return Ok(0);
+            }
+
+            match self.increment(allowed) {
+                Ok(_) => return Ok(allowed),
+                // Raced with another increment - retry with the up-to-date count.
+                Err(SemaphoreError::FailedToIncrement(err))
+                    if err.raw_os_error() == Some(libc::EOVERFLOW) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Same as [`increment`], but on success returns both the previous and the new counter value.
+    ///
+    /// The new counter value is the one right after the increment, before any woken up threads decrement it,
+    /// and never exceeds [`max_count`] - if it equals [`max_count`], no more releases are possible
+    /// until some thread waits on the semaphore.
+    ///
+    /// [`increment`]: #method.increment
+    /// [`max_count`]: #method.max_count
+    pub fn increment_reporting(&self, count: usize) -> Result<(usize, usize), SemaphoreError> {
+        let prev_count = self.increment(count)?;
+
+        Ok((
+            prev_count,
+            prev_count.saturating_add(count).min(self.max_count),
+        ))
+    }
+
+    /// Returns the current value of the semaphore's internal counter.
+    /// See [`sem_getvalue`](https://man7.org/linux/man-pages/man3/sem_getvalue.3.html).
+    ///
+    /// NOTE - the value is a racy snapshot, meant for debugging / diagnostics:
+    /// by the time this method returns, other threads may have already incremented / decremented the counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn count(&self) -> Result<usize, SemaphoreError> {
+        get_value(self.live_sem()?).map_err(SemaphoreError::FailedToQuery)
+    }
+
+    /// Returns the maximum value of the semaphore's internal counter,
+    /// as determined by `max_count` in [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn max_count(&self) -> usize {
+        self.max_count
+    }
+
+    /// Increments the semaphore's internal counter by `1`.
+    /// At most one waiting thread may be woken up.
+    ///
+    /// Fails if the internal counter value would overflow its maximum value
+    /// as determined by `max_count` in [`new`] if `1` was to be added to it.
+    ///
+    /// On success returns the previous counter value.
+    ///
+    /// [`new`]: #method.new
+    pub fn increment_one(&self) -> Result<usize, SemaphoreError> {
+        self.increment(1)
+    }
+
+    /// Decrements the semaphore's internal counter if it is above `0`, without blocking -
+    /// same as waiting on the semaphore with a zero timeout.
+    ///
+    /// Returns `Signaled` if the counter was above `0` and was decremented,
+    /// `Timeout` if it was `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn try_acquire(&self) -> Result<WaitableResult, SemaphoreError> {
+        self.wait_impl(Some(Duration::from_secs(0)))
+    }
+
+    /// Blocks the thread until the semaphore is signaled, decrementing its internal counter,
+    /// and returns a guard which [`increment`]s it back by `1` when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`increment`]: #method.increment
+    pub fn acquire(&self) -> Result<SemaphoreGuard<'_>, SemaphoreError> {
+        self.wait_impl(None)?;
+
+        Ok(SemaphoreGuard::new(self))
+    }
+
+    /// Blocks the thread until the semaphore is signaled or the duration `d` expires.
+    ///
+    /// If the semaphore was signaled, decrements its internal counter and returns a guard
+    /// which [`increment`]s it back by `1` when dropped. Returns `None` on timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`increment`]: #method.increment
+    pub fn acquire_timeout(
+        &self,
+        d: Duration,
+    ) -> Result<Option<SemaphoreGuard<'_>>, SemaphoreError> {
+        Ok(match self.wait_impl(Some(d))? {
+            // Not an alertable wait.
+            WaitableResult::Timeout | WaitableResult::IoCompletion => None,
+            // Semaphores are never abandoned.
+            WaitableResult::Signaled | WaitableResult::Abandoned => Some(SemaphoreGuard::new(self)),
+        })
+    }
+
+    /// Destroys the owned POSIX semaphore.
+    ///
+    /// All subsequent operations on the semaphore fail with [`SemaphoreError::Closed`].
+    /// Closing an already closed semaphore does nothing and succeeds.
+    ///
+    /// [`SemaphoreError::Closed`]: enum.SemaphoreError.html#variant.Closed
+    pub fn close(&mut self) -> Result<(), SemaphoreError> {
+        if let Some(sem) = self.sem.take() {
+            #[cfg(feature = "diagnostics")]
+            crate::diagnostics::deregister(sem.get() as *mut ());
+
+            // No other thread may be waiting on the semaphore, as it's exclusively borrowed.
+            if unsafe { libc::sem_destroy(sem.get()) } < 0 {
+                return Err(SemaphoreError::FailedToClose(io::Error::last_os_error()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the semaphore was [`close`]d.
+    ///
+    /// [`close`]: #method.close
+    pub fn is_closed(&self) -> bool {
+        self.sem.is_none()
+    }
+
+    /// Binds a human-readable `label` to the semaphore, used in its `Debug` output.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Returns the label bound to the semaphore via [`with_label`], if any.
+    ///
+    /// [`with_label`]: #method.with_label
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// Routes the semaphore's waits / increments through the `scheduler` instead of the default [`OsScheduler`].
+    ///
+    /// [`OsScheduler`]: deterministic/struct.OsScheduler.html
+    #[cfg(feature = "deterministic")]
+    pub fn with_scheduler(
+        mut self,
+        scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    ) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    fn live_sem(&self) -> Result<*mut libc::sem_t, SemaphoreError> {
+        self.sem
+            .as_ref()
+            .map(|sem| sem.get())
+            .ok_or(SemaphoreError::Closed)
+    }
+
+    /// `d` of `None` means "wait forever".
+    fn wait_impl(&self, d: Option<Duration>) -> Result<WaitableResult, SemaphoreError> {
+        let sem = self.live_sem()?;
+
+        // `sem_timedwait` takes an absolute `CLOCK_REALTIME` deadline.
+        let deadline = match d {
+            Some(d) if d > Duration::from_secs(0) => Some(realtime_deadline(d)?),
+            _ => None,
+        };
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_wait(sem as *mut ());
+
+        loop {
+            let result = unsafe {
+                match (d, deadline.as_ref()) {
+                    (_, Some(deadline)) => libc::sem_timedwait(sem, deadline),
+                    (Some(_), None) => libc::sem_trywait(sem),
+                    (None, None) => libc::sem_wait(sem),
+                }
+            };
+
+            if result == 0 {
+                #[cfg(feature = "deterministic")]
+                self.scheduler.after_wake(sem as *mut ());
+
+                return Ok(WaitableResult::Signaled);
+            }
+
+            let err = io::Error::last_os_error();
+
+            match err.raw_os_error() {
+                Some(libc::EINTR) => continue,
+                Some(libc::ETIMEDOUT) | Some(libc::EAGAIN) => return Ok(WaitableResult::Timeout),
+                _ => return Err(SemaphoreError::FailedToWait(err)),
+            }
+        }
+    }
+}
+
+fn get_value(sem: *mut libc::sem_t) -> io::Result<usize> {
+    let mut value = 0;
+
+    if unsafe { libc::sem_getvalue(sem, &mut value) } < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        // May be negative if there are waiters on some platforms.
+        Ok(value.max(0) as usize)
+    }
+}
+
+/// Returns the `CLOCK_REALTIME` time the duration `d` from now, saturated on overflow.
+fn realtime_deadline(d: Duration) -> Result<libc::timespec, SemaphoreError> {
+    let mut now: libc::timespec = unsafe { mem::zeroed() };
+
+    if unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut now) } < 0 {
+        return Err(SemaphoreError::FailedToWait(io::Error::last_os_error()));
+    }
+
+    let mut nsec = now.tv_nsec as i64 + d.subsec_nanos() as i64;
+    let mut carry = 0;
+
+    if nsec >= 1_000_000_000 {
+        nsec -= 1_000_000_000;
+        carry = 1;
+    }
+
+    let secs = libc::time_t::try_from(d.as_secs()).unwrap_or(libc::time_t::MAX);
+
+    Ok(libc::timespec {
+        tv_sec: now.tv_sec.saturating_add(secs).saturating_add(carry),
+        tv_nsec: nsec as _,
+    })
+}
+
+impl Drop for Semaphore {
+    fn drop(&mut self) {
+        // Failing to destroy the semaphore means it was invalid or waited on - i.e. a bug elsewhere.
+        let res = self.close();
+        debug_assert!(res.is_ok(), "failed to close the semaphore: {:?}", res);
+    }
+}
+
+impl Debug for Semaphore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("Semaphore");
+
+        s.field("max_count", &self.max_count);
+
+        if self.is_closed() {
+            s.field("closed", &true);
+        }
+
+        if let Some(label) = self.label {
+            s.field("label", &label);
+        }
+
+        s.finish()
+    }
+}
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+impl Waitable for Semaphore {
+    /// Blocks the thread until the semaphore is [`incremented`] or the timeout of `ms` milliseconds expires.
+    ///
+    /// Because at most one thread is woken up when the semaphore is [`incremented`],
+    /// there's no guarantee any given thread will wake up when there are multiple
+    /// threads waiting for one semaphore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`incremented`]: struct.Semaphore.html#method.increment
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        let d = if ms == INFINITE_MS {
+            None
+        } else {
+            Some(Duration::from_millis(ms.into()))
+        };
+
+        self.wait_impl(d).map_err(WaitError::from)
+    }
+
+    /// Blocks the thread until the semaphore is [`incremented`] or the duration `d` expires.
+    ///
+    /// Waits for the exact duration `d`, not rounded up to whole milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`incremented`]: struct.Semaphore.html#method.increment
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(Some(d)).map_err(WaitError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named() {
+        match Semaphore::new(0, 1, Some("minievent_named")) {
+            Err(SemaphoreError::Unsupported) => {}
+            _ => panic!(),
+        }
+
+        Semaphore::new(0, 1, Some("")).unwrap();
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn diagnostics() {
+        use crate::diagnostics::{dump, ObjectKind};
+
+        let find = |object: usize| dump().into_iter().find(|info| info.handle == object);
+
+        let mut s = Semaphore::new(0, 1, None).unwrap();
+        let object = s.live_sem().unwrap() as usize;
+        assert_eq!(find(object).unwrap().kind, ObjectKind::Semaphore);
+
+        s.close().unwrap();
+        assert!(find(object).is_none());
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn scheduler_invalid_increment() {
+        use {
+            crate::deterministic::Scheduler,
+            std::sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+        };
+
+        #[derive(Default)]
+        struct CountingScheduler(AtomicUsize);
+
+        impl Scheduler for CountingScheduler {
+            fn before_signal(&self, _: *mut ()) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let scheduler = Arc::new(CountingScheduler::default());
+        let s = Semaphore::new(1, 1, None)
+            .unwrap()
+            .with_scheduler(scheduler.clone());
+
+        // Rejected increments are not signals.
+        s.increment(0).unwrap_err();
+        s.increment_one().unwrap_err();
+        assert_eq!(scheduler.0.load(Ordering::SeqCst), 0);
+
+        s.try_acquire().unwrap();
+        s.increment_one().unwrap();
+        assert_eq!(scheduler.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,9 +1,22 @@
 mod error;
+mod guard;
+mod report;
+
+#[cfg(test)]
+mod tests;
 
 #[cfg(windows)]
 mod win;
 
+#[cfg(target_os = "linux")]
+mod linux;
+
 pub use error::SemaphoreError;
+pub use guard::SemaphoreGuard;
+pub use report::IncrementReport;
 
 #[cfg(windows)]
-pub use win::Semaphore;
+pub use win::{Semaphore, SemaphoreOptions};
+
+#[cfg(target_os = "linux")]
+pub use linux::Semaphore;
@@ -0,0 +1,106 @@
+use crate::{Semaphore, SemaphoreError};
+
+/// Result of incrementing a [`Semaphore`] via [`increment_and_report`].
+///
+/// NOTE - inherently racy: the OS does not report the number of threads waiting on the semaphore,
+/// so whether the increment woke any of them can only be estimated from the previous counter value.
+/// Meant for instrumentation (e.g. producer / consumer throughput metrics), not for synchronization.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+/// [`increment_and_report`]: struct.Semaphore.html#method.increment_and_report
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IncrementReport {
+    previous_count: usize,
+    count: usize,
+}
+
+impl IncrementReport {
+    /// Returns the semaphore's internal counter value before the increment.
+    pub fn previous_count(&self) -> usize {
+        self.previous_count
+    }
+
+    /// Returns the value the semaphore's internal counter was incremented by.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the increment likely woke up at least one waiting thread.
+    ///
+    /// The previous counter value of `0` strongly implies a thread was waiting (or about to wait) on the semaphore,
+    /// while a non-zero value means no thread was blocked on it.
+    pub fn likely_woke_waiter(&self) -> bool {
+        self.previous_count == 0
+    }
+
+    /// Returns the upper bound of the number of waiting threads the increment woke up:
+    /// `count` if the previous counter value was `0`, otherwise `0`.
+    pub fn max_woken(&self) -> usize {
+        if self.likely_woke_waiter() {
+            self.count
+        } else {
+            0
+        }
+    }
+}
+
+impl Semaphore {
+    /// Same as [`increment`], but returns an [`IncrementReport`] which estimates
+    /// whether the increment woke up any waiting threads.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`increment`].
+    ///
+    /// [`increment`]: #method.increment
+    /// [`IncrementReport`]: struct.IncrementReport.html
+    pub fn increment_and_report(&self, count: usize) -> Result<IncrementReport, SemaphoreError> {
+        let previous_count = self.increment(count)?;
+
+        Ok(IncrementReport {
+            previous_count,
+            count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+        std::{sync::Arc, thread, time::Duration},
+    };
+
+    #[test]
+    fn increment_and_report() {
+        let s = Semaphore::new(1, 4, None).unwrap();
+
+        // No thread can be waiting on a signaled semaphore.
+        let report = s.increment_and_report(2).unwrap();
+        assert_eq!(report.previous_count(), 1);
+        assert_eq!(report.count(), 2);
+        assert!(!report.likely_woke_waiter());
+        assert_eq!(report.max_woken(), 0);
+
+        let s = Arc::new(Semaphore::new(0, 4, None).unwrap()); // Not signaled.
+
+        let t = {
+            let s = s.clone();
+
+            thread::spawn(move || {
+                let res = s.wait(Duration::from_secs(1_000_000)).unwrap();
+                assert!(res == WaitableResult::Signaled);
+            })
+        };
+
+        thread::sleep(Duration::from_millis(10));
+
+        let report = s.increment_and_report(1).unwrap();
+        assert_eq!(report.previous_count(), 0);
+        assert!(report.likely_woke_waiter());
+        assert_eq!(report.max_woken(), 1);
+
+        t.join().unwrap();
+    }
+}
@@ -0,0 +1,380 @@
+use {
+    crate::{
+        wait_for_all, wait_for_all_infinite, Semaphore, SemaphoreError, Waitable, WaitableResult,
+    },
+    std::{io, sync::Arc, thread, time::Duration, time::Instant},
+};
+
+#[cfg(windows)]
+use crate::WaitableExt as Element;
+
+#[cfg(not(windows))]
+use crate::Waitable as Element;
+
+#[test]
+fn signaled_method() {
+    let s = Semaphore::new(1, 1, None).unwrap(); // Signaled.
+
+    let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = s.wait(Duration::from_millis(1)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Timeout);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    s.increment_one().err().unwrap(); // Must have failed.
+}
+
+#[test]
+fn signaled_free_function() {
+    let s = Semaphore::new(1, 1, None).unwrap(); // Signaled.
+    let w = [&s as _];
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Timeout);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    s.increment_one().err().unwrap(); // Must have failed.
+}
+
+#[test]
+fn boxed() {
+    use crate::{wait_for_all_boxed, wait_for_one_boxed, Event, WaitablesResult};
+
+    let w: Vec<Box<dyn Element>> = vec![
+        Box::new(Event::new_manual(false, None).unwrap()), // Not signaled.
+        Box::new(Semaphore::new(1, 1, None).unwrap()),     // Signaled.
+    ];
+
+    let res = wait_for_all_boxed(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    let res = wait_for_one_boxed(&w, Duration::from_secs(1_000_000)).unwrap();
+    assert!(res == WaitablesResult::OneSignaled(1));
+
+    // Semaphore counter was decremented.
+    let res = wait_for_one_boxed(&w, Duration::from_millis(1)).unwrap();
+    assert!(res == WaitablesResult::Timeout);
+}
+
+#[test]
+fn increment_saturating() {
+    let s = Semaphore::new(1, 4, None).unwrap();
+
+    assert_eq!(s.increment_saturating(10).unwrap(), 3);
+    assert_eq!(s.count().unwrap(), 4);
+
+    // At the maximum.
+    assert_eq!(s.increment_saturating(1).unwrap(), 0);
+
+    let res = s.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+
+    assert_eq!(s.increment_saturating(2).unwrap(), 1);
+    assert_eq!(s.count().unwrap(), 4);
+}
+
+#[test]
+fn unsignaled_method() {
+    let s = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
+
+    let res = s.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+
+    s.increment_one().unwrap(); // Now signaled.
+
+    let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment(2).err().unwrap(); // Must have failed.
+}
+
+#[test]
+fn unsignaled_free_function() {
+    let s = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
+    let w = [&s as _];
+
+    let res = wait_for_all(&w, Duration::from_millis(1)).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Timeout);
+
+    s.increment_one().unwrap(); // Now signaled.
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment_one().unwrap(); // Signaled again.
+
+    let res = wait_for_all_infinite(&w).unwrap(); // Not signaled.
+    assert!(res == WaitableResult::Signaled);
+
+    s.increment(2).err().unwrap(); // Must have failed.
+}
+
+#[test]
+fn label() {
+    let s = Semaphore::new(0, 1, None).unwrap();
+    assert_eq!(s.label(), None);
+    assert!(!format!("{:?}", s).contains("label"));
+    assert!(format!("{:?}", s).contains("max_count: 1"));
+
+    let s = s.with_label("my_semaphore");
+    assert_eq!(s.label(), Some("my_semaphore"));
+    assert!(format!("{:?}", s).contains("my_semaphore"));
+}
+
+#[test]
+fn thread_signal() {
+    let s = Arc::new(Semaphore::new(0, 2, None).unwrap()); // Not signaled.
+    let s_clone_1 = s.clone();
+    let s_clone_2 = s.clone();
+
+    let t_1 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = s_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    let t_2 = thread::spawn(move || {
+        let now = Instant::now();
+        let res = s_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
+        let elapsed = now.elapsed();
+        (res, elapsed)
+    });
+
+    // Wait for a second.
+    thread::sleep(Duration::from_secs(1));
+
+    s.increment_one().unwrap();
+
+    // One of the threads has exited, the other is still waiting.
+
+    thread::sleep(Duration::from_millis(1_000));
+
+    s.increment_one().unwrap();
+
+    // Now both have exited.
+
+    let res_1 = t_1.join().unwrap();
+
+    assert!(res_1.0 == WaitableResult::Signaled);
+    assert!(res_1.1.as_millis() >= 500);
+
+    let res_2 = t_2.join().unwrap();
+
+    assert!(res_2.0 == WaitableResult::Signaled);
+    assert!(res_2.1.as_millis() >= 500);
+
+    if res_1.1.as_millis() > res_2.1.as_millis() {
+        assert!(res_1.1.as_millis() - res_2.1.as_millis() >= 500);
+    } else {
+        assert!(res_2.1.as_millis() - res_1.1.as_millis() >= 500);
+    }
+
+    // Not signaled.
+
+    let res = s.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Timeout);
+}
+
+#[test]
+fn thread_signal_all() {
+    let s = Arc::new(Semaphore::new(0, 4, None).unwrap()); // Not signaled.
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let s = s.clone();
+            thread::spawn(move || s.wait_infinite().unwrap())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(10));
+    s.increment(4).unwrap();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    assert_eq!(s.count().unwrap(), 0);
+}
+
+#[test]
+fn close() {
+    let mut s = Semaphore::new(1, 1, None).unwrap();
+    assert!(!s.is_closed());
+
+    s.close().unwrap();
+    assert!(s.is_closed());
+
+    s.close().unwrap(); // Already closed - does nothing.
+
+    assert!(s.increment_one().unwrap_err().is_closed()); // Must have failed - closed.
+    s.wait(Duration::from_millis(1)).err().unwrap(); // Must have failed - closed.
+}
+
+#[test]
+fn long_timeout() {
+    // Over `u32::MAX` milliseconds - saturated, must not wrap to a short timeout.
+    let d = Duration::from_secs(60 * 24 * 60 * 60);
+
+    let s = Arc::new(Semaphore::new(0, 1, None).unwrap());
+
+    let t = {
+        let s = s.clone();
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            s.increment_one().unwrap();
+        })
+    };
+
+    assert!(s.wait(d).unwrap() == WaitableResult::Signaled);
+    t.join().unwrap();
+}
+
+#[test]
+fn error_predicates() {
+    let err = Semaphore::new(0, 0, None).unwrap_err();
+    assert!(err.is_invalid_max_count());
+    assert!(err.os_error().is_none());
+
+    let mut s = Semaphore::new(0, 1, None).unwrap();
+    s.close().unwrap();
+
+    let err = s.increment_one().unwrap_err();
+    assert!(err.is_closed());
+    assert!(!err.is_invalid_name());
+}
+
+#[test]
+fn error_source() {
+    use std::error::Error;
+
+    let err = SemaphoreError::FailedToWait(io::Error::from_raw_os_error(6));
+
+    let source = err.source().unwrap();
+    let source = source.downcast_ref::<io::Error>().unwrap();
+    assert_eq!(source.raw_os_error(), Some(6));
+
+    assert!(SemaphoreError::InvalidName { nul_position: 0 }
+        .source()
+        .is_none());
+}
+
+#[test]
+fn count() {
+    let s = Semaphore::new(3, 5, None).unwrap();
+    assert_eq!(s.count().unwrap(), 3);
+
+    s.increment(2).unwrap();
+    assert_eq!(s.count().unwrap(), 5);
+
+    s.wait(Duration::from_millis(1)).unwrap();
+    assert_eq!(s.count().unwrap(), 4);
+}
+
+#[test]
+fn acquire_guard() {
+    let s = Semaphore::new(2, 2, None).unwrap();
+
+    {
+        let _a = s.acquire().unwrap();
+        let _b = s
+            .acquire_timeout(Duration::from_millis(1))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(s.count().unwrap(), 0);
+
+        // Drained.
+        assert!(s
+            .acquire_timeout(Duration::from_millis(1))
+            .unwrap()
+            .is_none());
+    }
+
+    // Released on drop.
+    assert_eq!(s.count().unwrap(), 2);
+
+    // Released on early return.
+    let early_return = |fail: bool| -> Result<(), ()> {
+        let _guard = s.acquire().unwrap();
+
+        if fail {
+            return Err(());
+        }
+
+        Ok(())
+    };
+
+    early_return(true).err().unwrap();
+    early_return(false).unwrap();
+
+    assert_eq!(s.count().unwrap(), 2);
+}
+
+#[test]
+fn try_acquire() {
+    let s = Semaphore::new(3, 3, None).unwrap();
+
+    for _ in 0..3 {
+        assert!(s.try_acquire().unwrap() == WaitableResult::Signaled);
+    }
+
+    // Drained.
+    assert!(s.try_acquire().unwrap() == WaitableResult::Timeout);
+    assert!(s.try_acquire().unwrap() == WaitableResult::Timeout);
+
+    s.increment_one().unwrap();
+
+    assert!(s.try_acquire().unwrap() == WaitableResult::Signaled);
+    assert!(s.try_acquire().unwrap() == WaitableResult::Timeout);
+}
+
+#[test]
+fn increment_reporting() {
+    let s = Semaphore::new(1, 4, None).unwrap();
+    assert_eq!(s.max_count(), 4);
+
+    assert_eq!(s.increment_reporting(2).unwrap(), (1, 3));
+
+    // Up to the maximum.
+    assert_eq!(s.increment_reporting(1).unwrap(), (3, 4));
+
+    // Past the maximum - fails, the counter is unchanged.
+    s.increment_reporting(1).err().unwrap();
+    assert_eq!(s.count().unwrap(), 4);
+
+    let res = s.wait(Duration::from_millis(1)).unwrap();
+    assert!(res == WaitableResult::Signaled);
+
+    assert_eq!(s.increment_reporting(1).unwrap(), (3, 4));
+}
+
+#[test]
+fn invalid_max_count() {
+    match Semaphore::new(0, 0, None) {
+        Err(SemaphoreError::InvalidMaxCount) => {}
+        _ => panic!(),
+    }
+}
@@ -1,17 +1,61 @@
 use {
-    crate::{SemaphoreError, Waitable, WaitableExt, WaitableResult},
-    std::{ffi::CString, io, ptr, time::Duration},
+    super::SemaphoreGuard,
+    crate::{
+        duration_to_timeout_ms,
+        ntdll::{query_object_type, query_semaphore},
+        waitable::{
+            close_handle, create_handle, namespaced_name, security_attributes,
+            wait_for_handle_with_strategy, Namespace, DEFAULT_CREATE_RETRIES,
+        },
+        SemaphoreError, WaitError, WaitStrategy, Waitable, WaitableExt, WaitableResult,
+    },
+    std::os::windows::io::{AsRawHandle, RawHandle},
+    std::{
+        ffi::CString,
+        fmt::{Debug, Formatter},
+        io, ptr, thread,
+        time::{Duration, Instant},
+    },
     winapi::{
-        shared::{minwindef::TRUE, winerror::WAIT_TIMEOUT},
+        shared::{
+            minwindef::{FALSE, MAX_PATH, TRUE},
+            winerror::{ERROR_FILE_NOT_FOUND, ERROR_TOO_MANY_POSTS, WAIT_TIMEOUT},
+        },
         um::{
-            handleapi::CloseHandle,
-            synchapi::{ReleaseSemaphore, WaitForSingleObject},
-            winbase::{CreateSemaphoreA, INFINITE, WAIT_OBJECT_0},
-            winnt::HANDLE,
+            synchapi::ReleaseSemaphore,
+            winbase::{CreateSemaphoreA, OpenSemaphoreA, INFINITE, WAIT_OBJECT_0},
+            winnt::{HANDLE, SEMAPHORE_ALL_ACCESS},
         },
     },
 };
 
+/// Options for creating a [`Semaphore`] via [`new_with_options`].
+///
+/// Construct via `SemaphoreOptions { inheritable: true, ..Default::default() }`,
+/// so that code keeps compiling if more options are added.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+/// [`new_with_options`]: struct.Semaphore.html#method.new_with_options
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SemaphoreOptions {
+    /// If `true`, the semaphore handle is inherited by child processes created by the current process.
+    /// See [`SECURITY_ATTRIBUTES`](https://docs.microsoft.com/en-us/previous-versions/windows/desktop/legacy/aa379560(v=vs.85)) on MSDN.
+    pub inheritable: bool,
+    /// Number of times the semaphore creation is retried after a short sleep if it failed with a transient error -
+    /// i.e. the system was temporarily out of memory / resources. Other errors are never retried.
+    /// `2` by default.
+    pub retries: u32,
+}
+
+impl Default for SemaphoreOptions {
+    fn default() -> Self {
+        Self {
+            inheritable: false,
+            retries: DEFAULT_CREATE_RETRIES,
+        }
+    }
+}
+
 /// Waitable semaphore wrapper.
 /// See [`semaphore`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-createsemaphorea) on MSDN.
 ///
@@ -25,7 +69,17 @@ use {
 /// [`new`]: #method.new
 /// [`increment`]: #method.increment
 pub struct Semaphore {
-    handle: HANDLE,
+    /// `None` if the semaphore was [`close`](#method.close)d.
+    handle: Option<HANDLE>,
+    label: Option<&'static str>,
+    #[cfg(feature = "deterministic")]
+    scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    /// OS id of the thread which last [`increment`](#method.increment)ed the semaphore; `0` if none.
+    #[cfg(feature = "diagnostics")]
+    last_signaler: std::sync::atomic::AtomicU32,
+    /// Maximum value of the internal counter.
+    max_count: usize,
+    wait_strategy: WaitStrategy,
 }
 
 impl Semaphore {
@@ -38,35 +92,264 @@ impl Semaphore {
     ///
     /// # Errors
     ///
-    /// Returns an error if the OS event creation failed, if `name` was invalid - e.g. contained nul bytes.
+    /// Returns an error if the OS event creation failed, if `name` was invalid - e.g. contained nul bytes or exceeded `MAX_PATH`,
+    /// or if `max_count` is `0`.
     ///
     /// [`increment`]: #method.increment
     pub fn new(
-        mut init_count: usize,
+        init_count: usize,
+        max_count: usize,
+        name: Option<&str>,
+    ) -> Result<Semaphore, SemaphoreError> {
+        Semaphore::new_impl(init_count, max_count, name, &SemaphoreOptions::default())
+            .map(|(semaphore, _)| semaphore)
+    }
+
+    /// Same as [`new`], but also returns `true` if an existing named semaphore was reused
+    /// rather than a new one created - e.g. for leader election, where exactly one process must be the creator.
+    ///
+    /// A reused semaphore keeps its original internal counter value and maximum count.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn new_ex(
+        init_count: usize,
+        max_count: usize,
+        name: Option<&str>,
+    ) -> Result<(Semaphore, bool), SemaphoreError> {
+        Semaphore::new_impl(init_count, max_count, name, &SemaphoreOptions::default())
+    }
+
+    /// Same as [`new`], but the semaphore is created with the given `options` -
+    /// e.g. with an inheritable handle for IPC with a child process.
+    ///
+    /// The `options` are ignored if an existing named semaphore is reused.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn new_with_options(
+        init_count: usize,
+        max_count: usize,
+        name: Option<&str>,
+        options: &SemaphoreOptions,
+    ) -> Result<Semaphore, SemaphoreError> {
+        Semaphore::new_impl(init_count, max_count, name, options).map(|(semaphore, _)| semaphore)
+    }
+
+    /// Same as [`new`], but retries the semaphore creation up to `retries` times
+    /// if it failed with a transient error - i.e. the system was temporarily out of memory / resources,
+    /// e.g. while many objects are created in a burst. Other errors are never retried.
+    ///
+    /// [`new`] retries the semaphore creation twice.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn new_with_retries(
+        init_count: usize,
         max_count: usize,
         name: Option<&str>,
+        retries: u32,
+    ) -> Result<Semaphore, SemaphoreError> {
+        let options = SemaphoreOptions {
+            retries,
+            ..Default::default()
+        };
+
+        Semaphore::new_with_options(init_count, max_count, name, &options)
+    }
+
+    /// Same as [`new`], but creates (or reuses) the semaphore named `name` in the global namespace,
+    /// shared by all sessions - e.g. for IPC between a service and user applications.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Global\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Local\`.
+    ///
+    /// [`new`]: #method.new
+    /// [`ConflictingNamespace`]: enum.SemaphoreError.html#variant.ConflictingNamespace
+    pub fn new_global(
+        init_count: usize,
+        max_count: usize,
+        name: &str,
+    ) -> Result<Semaphore, SemaphoreError> {
+        Semaphore::new_namespaced(init_count, max_count, Namespace::Global, name)
+    }
+
+    /// Same as [`new`], but creates (or reuses) the semaphore named `name` in the namespace of the current session.
+    /// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+    ///
+    /// Prepends the `Local\` prefix to the `name`, unless it already has it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`new`].
+    /// Returns [`ConflictingNamespace`] if the `name` already has a different namespace prefix - e.g. `Global\`.
+    ///
+    /// [`new`]: #method.new
+    /// [`ConflictingNamespace`]: enum.SemaphoreError.html#variant.ConflictingNamespace
+    pub fn new_local(
+        init_count: usize,
+        max_count: usize,
+        name: &str,
     ) -> Result<Semaphore, SemaphoreError> {
+        Semaphore::new_namespaced(init_count, max_count, Namespace::Local, name)
+    }
+
+    /// Opens an existing named semaphore.
+    /// See [`OpenSemaphore`](https://docs.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-opensemaphorea) on MSDN.
+    ///
+    /// Unlike [`new`], never creates the semaphore,
+    /// so a worker process may attach to a semaphore published by a coordinator process.
+    ///
+    /// Determines the semaphore's maximum count.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotFound`] if no semaphore named `name` exists.
+    /// Returns an error if the OS function fails, or if `name` was invalid - e.g. empty, contained nul bytes or exceeded `MAX_PATH`.
+    ///
+    /// [`new`]: #method.new
+    /// [`NotFound`]: enum.SemaphoreError.html#variant.NotFound
+    pub fn open(name: &str) -> Result<Semaphore, SemaphoreError> {
         use SemaphoreError::*;
 
-        init_count = init_count.min(max_count);
+        if name.is_empty() {
+            return Err(InvalidName { nul_position: 0 });
+        }
 
-        let name = if let Some(name) = name {
-            if name.len() > 0 {
-                CString::new(name).map_err(|_| InvalidName)?.as_ptr()
+        if name.len() > MAX_PATH {
+            return Err(NameTooLong { len: name.len() });
+        }
+
+        let c_name = CString::new(name).map_err(|err| InvalidName {
+            nul_position: err.nul_position(),
+        })?;
+
+        let handle = unsafe { OpenSemaphoreA(SEMAPHORE_ALL_ACCESS, FALSE, c_name.as_ptr()) };
+
+        if handle.is_null() {
+            let err = io::Error::last_os_error();
+
+            return Err(if err.raw_os_error() == Some(ERROR_FILE_NOT_FOUND as i32) {
+                NotFound
             } else {
-                ptr::null_mut()
+                FailedToOpen(err)
+            });
+        }
+
+        let max_count = match query_semaphore(handle) {
+            Ok((_, max_count)) => max_count,
+            Err(err) => {
+                let _ = close_handle(handle);
+                return Err(FailedToOpen(err));
             }
-        } else {
-            ptr::null_mut()
         };
 
-        let handle =
-            unsafe { CreateSemaphoreA(ptr::null_mut(), init_count as i32, max_count as i32, name) };
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            Some(name),
+            crate::diagnostics::ObjectKind::Semaphore,
+        );
 
-        if handle.is_null() {
-            Err(FailedToCreate(io::Error::last_os_error()))
-        } else {
-            Ok(Semaphore { handle })
+        Ok(Semaphore::from_raw_parts(handle, max_count))
+    }
+
+    /// Takes ownership of the raw semaphore `handle` received from another process
+    /// (e.g. duplicated into the current process via `DuplicateHandle` and sent over a pipe).
+    ///
+    /// Validates that `handle` refers to a semaphore.
+    ///
+    /// # Safety
+    ///
+    /// If `handle` refers to a semaphore, it must be owned by the caller, and must not be used or closed
+    /// by anyone else afterwards - the returned semaphore closes it when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is not a valid handle, or if it does not refer to a semaphore.
+    /// The handle is not closed on error.
+    pub unsafe fn from_ipc_handle(handle: isize) -> Result<Semaphore, SemaphoreError> {
+        use SemaphoreError::*;
+
+        let handle = handle as HANDLE;
+
+        if query_object_type(handle).map_err(InvalidHandle)? != "Semaphore" {
+            return Err(WrongObjectType);
+        }
+
+        let (_, max_count) = query_semaphore(handle).map_err(InvalidHandle)?;
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            None,
+            crate::diagnostics::ObjectKind::Semaphore,
+        );
+
+        Ok(Semaphore::from_raw_parts(handle, max_count))
+    }
+
+    /// Takes ownership of the raw semaphore `handle` created elsewhere in the current process
+    /// (e.g. by foreign code, or returned by [`into_raw_handle`]).
+    ///
+    /// The returned semaphore closes the `handle` when dropped - the caller must not close it afterwards.
+    ///
+    /// Determines the semaphore's maximum count; assumes the largest count supported by the OS
+    /// if the OS query fails.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid semaphore handle owned by the caller.
+    /// Use [`from_ipc_handle`] to validate a handle of unknown provenance.
+    ///
+    /// [`into_raw_handle`]: #method.into_raw_handle
+    /// [`from_ipc_handle`]: #method.from_ipc_handle
+    pub unsafe fn from_raw_handle(handle: *mut ()) -> Semaphore {
+        let handle = handle as HANDLE;
+
+        let max_count = query_semaphore(handle).map_or(i32::MAX as usize, |(_, max)| max);
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            None,
+            crate::diagnostics::ObjectKind::Semaphore,
+        );
+
+        Semaphore::from_raw_parts(handle, max_count)
+    }
+
+    /// Releases ownership of the semaphore's raw OS handle without closing it, and returns it.
+    /// The caller becomes responsible for closing the handle (e.g. by passing it to [`from_raw_handle`]).
+    ///
+    /// Returns a null handle if the semaphore was [`close`]d.
+    ///
+    /// [`from_raw_handle`]: #method.from_raw_handle
+    /// [`close`]: #method.close
+    pub fn into_raw_handle(mut self) -> *mut () {
+        match self.handle.take() {
+            Some(handle) => {
+                #[cfg(feature = "diagnostics")]
+                crate::diagnostics::deregister(handle as *mut ());
+
+                handle as *mut ()
+            }
+            // `Drop` does not close the handle which was taken.
+            None => ptr::null_mut(),
         }
     }
 
@@ -80,18 +363,101 @@ impl Semaphore {
     ///
     /// [`new`]: #method.new
     pub fn increment(&self, count: usize) -> Result<usize, SemaphoreError> {
+        let handle = self.live_handle()?;
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_signal(handle as *mut ());
+
         let mut prev_count: i32 = 0;
 
-        let result =
-            unsafe { ReleaseSemaphore(self.handle, count as i32, &mut prev_count as *mut i32) };
+        let result = unsafe { ReleaseSemaphore(handle, count as i32, &mut prev_count as *mut i32) };
 
         if result == TRUE {
+            #[cfg(feature = "diagnostics")]
+            self.last_signaler.store(
+                unsafe { winapi::um::processthreadsapi::GetCurrentThreadId() },
+                std::sync::atomic::Ordering::Relaxed,
+            );
+
             Ok(prev_count as usize)
         } else {
             Err(SemaphoreError::FailedToIncrement(io::Error::last_os_error()))
         }
     }
 
+    /// Increments the semaphore's internal counter by up to `count`, never exceeding its maximum value
+    /// as determined by `max_count` in [`new`].
+    /// Up to that many waiting threads may be woken up.
+    ///
+    /// Unlike [`increment`], does not fail if `count` would overflow the maximum value -
+    /// instead releases as much as there is headroom for.
+    ///
+    /// On success returns the number actually added to the counter - `0` if it was already at its maximum value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`new`]: #method.new
+    /// [`increment`]: #method.increment
+    pub fn increment_saturating(&self, count: usize) -> Result<usize, SemaphoreError> {
+        loop {
+            let allowed = self.max_count.saturating_sub(self.count()?).min(count);
+
+            if allowed == 0 {
+                return Ok(0);
+            }
+
+            match self.increment(allowed) {
+                Ok(_) => return Ok(allowed),
+                // Raced with another increment - retry with the up-to-date count.
+                Err(SemaphoreError::FailedToIncrement(err))
+                    if err.raw_os_error() == Some(ERROR_TOO_MANY_POSTS as i32) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Same as [`increment`], but on success returns both the previous and the new counter value.
+    ///
+    /// The new counter value is the one right after the increment, before any woken up threads decrement it,
+    /// and never exceeds [`max_count`] - if it equals [`max_count`], no more releases are possible
+    /// until some thread waits on the semaphore.
+    ///
+    /// [`increment`]: #method.increment
+    /// [`max_count`]: #method.max_count
+    pub fn increment_reporting(&self, count: usize) -> Result<(usize, usize), SemaphoreError> {
+        let prev_count = self.increment(count)?;
+
+        Ok((
+            prev_count,
+            prev_count.saturating_add(count).min(self.max_count),
+        ))
+    }
+
+    /// Returns the current value of the semaphore's internal counter.
+    /// See [`NtQuerySemaphore`](http://undocumented.ntinternals.net/index.html?page=UserMode%2FUndocumented%20Functions%2FNT%20Objects%2FSemaphore%2FNtQuerySemaphore.html).
+    ///
+    /// NOTE - the value is a racy snapshot, meant for debugging / diagnostics:
+    /// by the time this method returns, other threads may have already incremented / decremented the counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn count(&self) -> Result<usize, SemaphoreError> {
+        query_semaphore(self.live_handle()?)
+            .map(|(count, _)| count)
+            .map_err(SemaphoreError::FailedToQuery)
+    }
+
+    /// Returns the maximum value of the semaphore's internal counter,
+    /// as determined by `max_count` in [`new`] when the semaphore was created.
+    ///
+    /// [`new`]: #method.new
+    pub fn max_count(&self) -> usize {
+        self.max_count
+    }
+
     /// Increments the semaphore's internal counter by `1`.
     /// At most one waiting thread may be woken up.
     ///
@@ -105,11 +471,333 @@ impl Semaphore {
         self.increment(1)
     }
 
+    /// Decrements the semaphore's internal counter if it is above `0`, without blocking -
+    /// same as waiting on the semaphore with a zero timeout.
+    ///
+    /// Returns `Signaled` if the counter was above `0` and was decremented,
+    /// `Timeout` if it was `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn try_acquire(&self) -> Result<WaitableResult, SemaphoreError> {
+        self.wait_impl(0)
+    }
+
+    /// Blocks the thread until the semaphore is signaled, decrementing its internal counter,
+    /// and returns a guard which [`increment`]s it back by `1` when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`increment`]: #method.increment
+    pub fn acquire(&self) -> Result<SemaphoreGuard<'_>, SemaphoreError> {
+        self.wait_impl(INFINITE)?;
+
+        Ok(SemaphoreGuard::new(self))
+    }
+
+    /// Blocks the thread until the semaphore is signaled or the duration `d` expires.
+    ///
+    /// If the semaphore was signaled, decrements its internal counter and returns a guard
+    /// which [`increment`]s it back by `1` when dropped. Returns `None` on timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`increment`]: #method.increment
+    pub fn acquire_timeout(
+        &self,
+        d: Duration,
+    ) -> Result<Option<SemaphoreGuard<'_>>, SemaphoreError> {
+        let ms = duration_to_timeout_ms(d);
+
+        Ok(match self.wait_impl(ms)? {
+            // Not an alertable wait.
+            WaitableResult::Timeout | WaitableResult::IoCompletion => None,
+            // Semaphores are never abandoned.
+            WaitableResult::Signaled | WaitableResult::Abandoned => Some(SemaphoreGuard::new(self)),
+        })
+    }
+
+    /// Blocks the thread until the semaphore's internal counter is at least `count`
+    /// or the duration `d` expires.
+    ///
+    /// Unlike [`wait`], does not decrement the internal counter.
+    ///
+    /// Polls the internal counter value with an exponential backoff, so may oversleep
+    /// by up to a few milliseconds. The counter is racy by nature - by the time this method
+    /// returns `Signaled`, other threads may have already decremented the counter below `count`.
+    ///
+    /// [`wait`]: #method.wait
+    pub fn wait_for_available(
+        &self,
+        count: usize,
+        d: Duration,
+    ) -> Result<WaitableResult, SemaphoreError> {
+        const MIN_BACKOFF: Duration = Duration::from_micros(100);
+        const MAX_BACKOFF: Duration = Duration::from_millis(10);
+
+        let deadline = Instant::now().checked_add(d);
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            if self.count()? >= count {
+                return Ok(WaitableResult::Signaled);
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => MAX_BACKOFF,
+            };
+
+            if remaining == Duration::from_secs(0) {
+                return Ok(WaitableResult::Timeout);
+            }
+
+            thread::sleep(backoff.min(remaining));
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    /// Closes the owned OS semaphore handle.
+    ///
+    /// All subsequent operations on the semaphore fail with [`SemaphoreError::Closed`];
+    /// the semaphore's [`handle`] becomes null.
+    /// Closing an already closed semaphore does nothing and succeeds.
+    ///
+    /// [`SemaphoreError::Closed`]: enum.SemaphoreError.html#variant.Closed
+    /// [`handle`]: trait.WaitableExt.html#tymethod.handle
+    pub fn close(&mut self) -> Result<(), SemaphoreError> {
+        if let Some(handle) = self.handle.take() {
+            #[cfg(feature = "diagnostics")]
+            crate::diagnostics::deregister(handle as *mut ());
+
+            close_handle(handle).map_err(SemaphoreError::FailedToClose)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if the semaphore was [`close`]d.
+    ///
+    /// [`close`]: #method.close
+    pub fn is_closed(&self) -> bool {
+        self.handle.is_none()
+    }
+
+    /// Returns the semaphore's raw OS handle value as an integer, e.g. for use as a `HashMap` key identifying the semaphore.
+    /// `0` if the semaphore was [`close`]d.
+    ///
+    /// Unique among the open semaphores of the process, as the OS only reuses a handle value after the handle is closed.
+    /// The same named semaphore opened again has a different handle value, hence a different id,
+    /// despite referring to the same OS object.
+    ///
+    /// NOTE - this is why the semaphore does not implement `PartialEq` / `Hash` itself:
+    /// equality of handle values is not equality of the OS objects.
+    ///
+    /// [`close`]: #method.close
+    pub fn id(&self) -> usize {
+        self.handle.unwrap_or(ptr::null_mut()) as usize
+    }
+
+    /// Binds a human-readable `label` to the semaphore, used in its `Debug` output.
+    ///
+    /// Unlike the OS object name, the label is purely for the user's own logging / debugging,
+    /// and does not affect the OS object in any way.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Returns the label bound to the semaphore via [`with_label`], if any.
+    ///
+    /// [`with_label`]: #method.with_label
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// Makes the semaphore's [`wait`] / [`wait_infinite`] methods wait using the `strategy`
+    /// instead of the default [`WaitStrategy::Blocking`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `strategy`'s parameters are invalid - e.g. the spin count is out of range.
+    ///
+    /// [`wait`]: #method.wait
+    /// [`wait_infinite`]: #method.wait_infinite
+    /// [`WaitStrategy::Blocking`]: enum.WaitStrategy.html#variant.Blocking
+    pub fn with_wait_strategy(mut self, strategy: WaitStrategy) -> Result<Self, SemaphoreError> {
+        if !strategy.is_valid() {
+            return Err(SemaphoreError::InvalidWaitStrategy);
+        }
+
+        self.wait_strategy = strategy;
+        Ok(self)
+    }
+
+    /// Returns the strategy the semaphore's [`wait`] / [`wait_infinite`] methods wait with.
+    ///
+    /// [`wait`]: #method.wait
+    /// [`wait_infinite`]: #method.wait_infinite
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait_strategy
+    }
+
+    /// Returns the OS id of the thread which last [`increment`]ed the semaphore, if any.
+    ///
+    /// NOTE - inherently racy if the semaphore is incremented by multiple threads: by the time
+    /// a woken up thread calls this, another thread may have incremented the semaphore again,
+    /// so the returned thread is not necessarily the one which woke up the caller.
+    ///
+    /// [`increment`]: #method.increment
+    #[cfg(feature = "diagnostics")]
+    pub fn last_signaler_thread(&self) -> Option<u32> {
+        match self
+            .last_signaler
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            0 => None,
+            thread_id => Some(thread_id),
+        }
+    }
+
+    /// Routes the semaphore's waits / increments through the `scheduler` instead of the default [`OsScheduler`].
+    ///
+    /// [`OsScheduler`]: deterministic/struct.OsScheduler.html
+    #[cfg(feature = "deterministic")]
+    pub fn with_scheduler(
+        mut self,
+        scheduler: std::sync::Arc<dyn crate::deterministic::Scheduler>,
+    ) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    fn new_namespaced(
+        init_count: usize,
+        max_count: usize,
+        namespace: Namespace,
+        name: &str,
+    ) -> Result<Semaphore, SemaphoreError> {
+        use SemaphoreError::*;
+
+        // An unnamed semaphore has no namespace.
+        if name.is_empty() {
+            return Err(InvalidName { nul_position: 0 });
+        }
+
+        let name = namespaced_name(namespace, name).ok_or(ConflictingNamespace)?;
+
+        Semaphore::new(init_count, max_count, Some(&name))
+    }
+
+    /// Also returns `true` if the named semaphore already existed.
+    fn new_impl(
+        mut init_count: usize,
+        max_count: usize,
+        name: Option<&str>,
+        options: &SemaphoreOptions,
+    ) -> Result<(Semaphore, bool), SemaphoreError> {
+        use SemaphoreError::*;
+
+        if max_count == 0 {
+            return Err(InvalidMaxCount);
+        }
+
+        init_count = init_count.min(max_count);
+
+        let name = name.filter(|name| !name.is_empty());
+
+        #[cfg(feature = "diagnostics")]
+        let diagnostics_name = name;
+
+        let name = if let Some(name) = name {
+            if name.len() > MAX_PATH {
+                return Err(NameTooLong { len: name.len() });
+            }
+
+            Some(CString::new(name).map_err(|err| InvalidName {
+                nul_position: err.nul_position(),
+            })?)
+        } else {
+            None
+        };
+
+        let name = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+
+        let mut security_attributes = security_attributes(options.inheritable);
+
+        let (handle, already_existed) = create_handle(options.retries, || unsafe {
+            CreateSemaphoreA(
+                &mut security_attributes,
+                init_count as i32,
+                max_count as i32,
+                name,
+            )
+        })
+        .map_err(FailedToCreate)?;
+
+        // An existing named semaphore keeps its original maximum count.
+        let max_count = if already_existed {
+            match query_semaphore(handle) {
+                Ok((_, max_count)) => max_count,
+                Err(err) => {
+                    let _ = close_handle(handle);
+                    return Err(FailedToQuery(err));
+                }
+            }
+        } else {
+            max_count
+        };
+
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::register(
+            handle as *mut (),
+            diagnostics_name,
+            crate::diagnostics::ObjectKind::Semaphore,
+        );
+
+        Ok((
+            Semaphore::from_raw_parts(handle, max_count),
+            already_existed,
+        ))
+    }
+
+    fn from_raw_parts(handle: HANDLE, max_count: usize) -> Semaphore {
+        Semaphore {
+            handle: Some(handle),
+            label: None,
+            #[cfg(feature = "deterministic")]
+            scheduler: crate::deterministic::os_scheduler(),
+            #[cfg(feature = "diagnostics")]
+            last_signaler: std::sync::atomic::AtomicU32::new(0),
+            max_count,
+            wait_strategy: WaitStrategy::Blocking,
+        }
+    }
+
+    fn live_handle(&self) -> Result<HANDLE, SemaphoreError> {
+        self.handle.ok_or(SemaphoreError::Closed)
+    }
+
     fn wait_impl(&self, ms: u32) -> Result<WaitableResult, SemaphoreError> {
-        let result = unsafe { WaitForSingleObject(self.handle, ms) };
+        let handle = self.live_handle()?;
+
+        #[cfg(feature = "deterministic")]
+        self.scheduler.before_wait(handle as *mut ());
+
+        let result = wait_for_handle_with_strategy(handle, ms, self.wait_strategy);
 
         match result {
-            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+            WAIT_OBJECT_0 => {
+                #[cfg(feature = "deterministic")]
+                self.scheduler.after_wake(handle as *mut ());
+
+                Ok(WaitableResult::Signaled)
+            }
             WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
             _ => Err(SemaphoreError::FailedToWait(io::Error::last_os_error())),
         }
@@ -118,9 +806,28 @@ impl Semaphore {
 
 impl Drop for Semaphore {
     fn drop(&mut self) {
-        unsafe {
-            CloseHandle(self.handle);
+        // Failing to close the handle means it was invalid or already closed - i.e. a bug elsewhere.
+        let res = self.close();
+        debug_assert!(
+            res.is_ok(),
+            "failed to close the semaphore handle: {:?}",
+            res
+        );
+    }
+}
+
+impl Debug for Semaphore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("Semaphore");
+
+        s.field("handle", &self.handle.unwrap_or(ptr::null_mut()));
+        s.field("max_count", &self.max_count);
+
+        if let Some(label) = self.label {
+            s.field("label", &label);
         }
+
+        s.finish()
     }
 }
 
@@ -128,22 +835,7 @@ unsafe impl Send for Semaphore {}
 unsafe impl Sync for Semaphore {}
 
 impl Waitable for Semaphore {
-    /// Blocks the thread until the semaphore is [`incremented`] or the duration `d` expires.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the OS function fails.
-    ///
-    /// [`incremented`]: struct.Semaphore.html#method.increment
-    fn wait(&self, d: Duration) -> Result<WaitableResult, ()> {
-        let ms = d.as_millis();
-        debug_assert!(ms <= std::u32::MAX as u128);
-        let ms = ms as u32;
-
-        self.wait_impl(ms).map_err(|_| ())
-    }
-
-    /// Blocks the thread until the semaphore is [`incremented`].
+    /// Blocks the thread until the semaphore is [`incremented`] or the timeout of `ms` milliseconds expires.
     ///
     /// Because at most one thread is woken up when the semaphore is [`incremented`],
     /// there's no guarantee any given thread will wake up when there are multiple
@@ -154,15 +846,28 @@ impl Waitable for Semaphore {
     /// Returns an error if the OS function fails.
     ///
     /// [`incremented`]: struct.Semaphore.html#method.increment
-    fn wait_infinite(&self) -> Result<(), ()> {
-        self.wait_impl(INFINITE).map(|_| ()).map_err(|_| ())
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(ms).map_err(WaitError::from)
     }
 }
 
 impl WaitableExt for Semaphore {
     /// Returns the raw handle / pointer to the waitable's OS object.
+    /// Null if the semaphore was [`close`]d.
+    ///
+    /// [`close`]: struct.Semaphore.html#method.close
     fn handle(&self) -> *mut () {
-        self.handle as *mut ()
+        self.handle.unwrap_or(ptr::null_mut()) as *mut ()
+    }
+}
+
+impl AsRawHandle for Semaphore {
+    /// Returns the raw handle to the semaphore's OS object.
+    /// Null if the semaphore was [`close`]d.
+    ///
+    /// [`close`]: struct.Semaphore.html#method.close
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle.unwrap_or(ptr::null_mut()) as RawHandle
     }
 }
 
@@ -170,144 +875,233 @@ impl WaitableExt for Semaphore {
 mod tests {
     use {
         super::*,
-        crate::wait_for_all,
         std::{sync::Arc, thread, time::Instant},
     };
 
     #[test]
-    fn signaled_method() {
-        let s = Semaphore::new(1, 1, None).unwrap(); // Signaled.
+    fn inheritable() {
+        use winapi::um::{handleapi::GetHandleInformation, winbase::HANDLE_FLAG_INHERIT};
 
-        let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        let is_inheritable = |s: &Semaphore| {
+            let mut flags = 0;
+            assert!(unsafe { GetHandleInformation(s.handle() as HANDLE, &mut flags) } != 0);
+            flags & HANDLE_FLAG_INHERIT != 0
+        };
 
-        let res = s.wait(Duration::from_millis(1)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Timeout);
+        let options = SemaphoreOptions {
+            inheritable: true,
+            ..Default::default()
+        };
 
-        s.increment_one().unwrap(); // Signaled again.
+        let s = Semaphore::new_with_options(1, 2, None, &options).unwrap();
+        assert!(is_inheritable(&s));
+        assert_eq!(s.count().unwrap(), 1);
+        assert_eq!(s.max_count(), 2);
 
-        let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        let s = Semaphore::new(1, 2, None).unwrap();
+        assert!(!is_inheritable(&s));
+    }
 
-        s.increment_one().unwrap(); // Signaled again.
+    #[test]
+    fn create_retries() {
+        use {
+            crate::waitable::INJECTED_CREATE_ERRORS,
+            winapi::shared::winerror::{ERROR_INVALID_HANDLE, ERROR_NO_SYSTEM_RESOURCES},
+        };
+
+        let inject = |errs: &[u32]| INJECTED_CREATE_ERRORS.with(|e| e.borrow_mut().extend(errs));
+        let num_injected = || INJECTED_CREATE_ERRORS.with(|e| e.borrow().len());
+
+        inject(&[ERROR_NO_SYSTEM_RESOURCES]);
+        Semaphore::new_with_retries(0, 1, None, 1).unwrap();
+        assert_eq!(num_injected(), 0);
+
+        inject(&[ERROR_NO_SYSTEM_RESOURCES]);
+        assert!(Semaphore::new_with_retries(0, 1, None, 0).is_err());
+
+        // Not retried.
+        inject(&[ERROR_INVALID_HANDLE, ERROR_NO_SYSTEM_RESOURCES]);
+        assert!(Semaphore::new_with_retries(0, 1, None, 1).is_err());
+        assert_eq!(num_injected(), 1);
 
-        s.increment_one().err().unwrap(); // Must have failed.
+        INJECTED_CREATE_ERRORS.with(|e| e.borrow_mut().clear());
     }
 
     #[test]
-    fn signaled_free_function() {
-        let s = Semaphore::new(1, 1, None).unwrap(); // Signaled.
-        let w = [&s as _];
+    fn namespaces() {
+        let s = Semaphore::new_local(0, 2, "minievent_semaphore_namespaces").unwrap();
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        let o = Semaphore::open("Local\\minievent_semaphore_namespaces").unwrap();
+        assert_eq!(o.max_count(), 2);
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Timeout);
+        s.increment(1).unwrap();
+        assert_eq!(o.count().unwrap(), 1);
+
+        match Semaphore::new_local(0, 2, "Global\\minievent_semaphore_namespaces") {
+            Err(SemaphoreError::ConflictingNamespace) => {}
+            _ => panic!(),
+        }
+    }
 
-        s.increment_one().unwrap(); // Signaled again.
+    #[test]
+    fn open() {
+        let s = Semaphore::new(0, 4, Some("minievent_semaphore_open")).unwrap();
+        let o = Semaphore::open("minievent_semaphore_open").unwrap();
+        assert_eq!(o.max_count(), 4);
+
+        // Shared count.
+        s.increment(2).unwrap();
+        assert_eq!(o.count().unwrap(), 2);
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+        let res = o.wait(Duration::from_millis(1)).unwrap();
         assert!(res == WaitableResult::Signaled);
+        assert_eq!(s.count().unwrap(), 1);
+
+        assert!(Semaphore::open("minievent_semaphore_open_not_found")
+            .unwrap_err()
+            .is_not_found());
 
-        s.increment_one().unwrap(); // Signaled again.
+        match Semaphore::open("") {
+            Err(SemaphoreError::InvalidName { nul_position: 0 }) => {}
+            _ => panic!(),
+        }
+
+        match Semaphore::new(0, 1, Some("foo\0bar")) {
+            Err(SemaphoreError::InvalidName { nul_position }) => assert_eq!(nul_position, 3),
+            _ => panic!(),
+        }
 
-        s.increment_one().err().unwrap(); // Must have failed.
+        match Semaphore::new(0, 1, Some(&"a".repeat(MAX_PATH + 1))) {
+            Err(SemaphoreError::NameTooLong { len }) => assert_eq!(len, MAX_PATH + 1),
+            _ => panic!(),
+        }
     }
 
     #[test]
-    fn unsignaled_method() {
-        let s = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
+    fn wait_for_available() {
+        let s = Arc::new(Semaphore::new(0, 4, None).unwrap()); // Not signaled.
+        let s_clone = s.clone();
 
-        let res = s.wait(Duration::from_millis(1)).unwrap();
+        let res = s.wait_for_available(1, Duration::from_millis(100)).unwrap();
         assert!(res == WaitableResult::Timeout);
 
-        s.increment_one().unwrap(); // Now signaled.
+        let t = thread::spawn(move || {
+            for _ in 0..3 {
+                thread::sleep(Duration::from_millis(100));
+                s_clone.increment_one().unwrap();
+            }
+        });
 
-        let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
+        let now = Instant::now();
+        let res = s
+            .wait_for_available(3, Duration::from_secs(1_000_000))
+            .unwrap();
         assert!(res == WaitableResult::Signaled);
+        assert!(now.elapsed().as_millis() >= 250);
 
-        s.increment_one().unwrap(); // Signaled again.
+        t.join().unwrap();
 
-        let res = s.wait(Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        // Counter was not decremented.
 
-        s.increment(2).err().unwrap(); // Must have failed.
+        for _ in 0..3 {
+            let res = s.wait(Duration::from_millis(1)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        let res = s.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
     }
 
     #[test]
-    fn unsignaled_free_function() {
-        let s = Semaphore::new(0, 1, None).unwrap(); // Not signaled.
-        let w = [&s as _];
+    fn close_handle_once() {
+        let num_closed = || crate::waitable::NUM_CLOSED_HANDLES.with(|num| num.get());
+        let before = num_closed();
 
-        let res = wait_for_all(&w, Duration::from_millis(1)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Timeout);
+        let mut s = Semaphore::new(1, 1, None).unwrap();
+        assert!(!s.is_closed());
 
-        s.increment_one().unwrap(); // Now signaled.
+        s.close().unwrap();
+        assert!(s.is_closed());
+        assert_eq!(num_closed(), before + 1);
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
+        s.close().unwrap(); // Already closed - does nothing.
+        assert_eq!(num_closed(), before + 1);
 
-        s.increment_one().unwrap(); // Signaled again.
+        s.increment_one().err().unwrap(); // Must have failed - closed.
+        s.wait(Duration::from_millis(1)).err().unwrap(); // Must have failed - closed.
+        assert!(s.handle().is_null());
 
-        let res = wait_for_all(&w, Duration::from_secs(1_000_000)).unwrap(); // Not signaled.
-        assert!(res == WaitableResult::Signaled);
-
-        s.increment(2).err().unwrap(); // Must have failed.
+        drop(s); // Must not close the handle again.
+        assert_eq!(num_closed(), before + 1);
     }
 
+    #[cfg(feature = "diagnostics")]
     #[test]
-    fn thread_signal() {
-        let s = Arc::new(Semaphore::new(0, 2, None).unwrap()); // Not signaled.
-        let s_clone_1 = s.clone();
-        let s_clone_2 = s.clone();
-
-        let t_1 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = s_clone_1.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
+    fn last_signaler_thread() {
+        use winapi::um::processthreadsapi::GetCurrentThreadId;
 
-        let t_2 = thread::spawn(move || {
-            let now = Instant::now();
-            let res = s_clone_2.wait(Duration::from_secs(1_000_000)).unwrap();
-            let elapsed = now.elapsed();
-            (res, elapsed)
-        });
+        let s = Arc::new(Semaphore::new(0, 1, None).unwrap());
+        assert_eq!(s.last_signaler_thread(), None);
+
+        let signaler = {
+            let s = s.clone();
 
-        // Wait for a second.
-        thread::sleep(Duration::from_secs(1));
+            thread::spawn(move || {
+                s.increment_one().unwrap();
+                unsafe { GetCurrentThreadId() }
+            })
+        };
 
-        s.increment_one().unwrap();
+        s.wait_infinite().unwrap();
 
-        // One of the threads has exited, the other is still waiting.
+        let signaler = signaler.join().unwrap();
+        assert_eq!(s.last_signaler_thread(), Some(signaler));
+        assert_ne!(signaler, unsafe { GetCurrentThreadId() });
+    }
 
-        thread::sleep(Duration::from_millis(1_000));
+    #[test]
+    fn new_ex() {
+        let (s0, already_existed) =
+            Semaphore::new_ex(1, 2, Some("minievent_semaphore_new_ex")).unwrap();
+        assert!(!already_existed);
 
-        s.increment_one().unwrap();
+        let (s1, already_existed) =
+            Semaphore::new_ex(0, 5, Some("minievent_semaphore_new_ex")).unwrap();
+        assert!(already_existed);
 
-        // Now both have exited.
+        // Original counter and maximum count.
+        assert_eq!(s1.count().unwrap(), 1);
+        assert_eq!(s1.max_count(), 2);
 
-        let res_1 = t_1.join().unwrap();
+        drop(s0);
+        drop(s1);
 
-        assert!(res_1.0 == WaitableResult::Signaled);
-        assert!(res_1.1.as_millis() >= 500);
+        let (_, already_existed) = Semaphore::new_ex(0, 1, None).unwrap();
+        assert!(!already_existed);
+    }
 
-        let res_2 = t_2.join().unwrap();
+    #[test]
+    fn error_predicates() {
+        let err = Semaphore::new(0, 1, Some("minievent\0error_predicates")).unwrap_err();
+        assert!(err.is_invalid_name());
 
-        assert!(res_2.0 == WaitableResult::Signaled);
-        assert!(res_2.1.as_millis() >= 500);
+        let err = unsafe { Semaphore::from_ipc_handle(0) }.unwrap_err();
+        assert!(err.is_invalid_handle());
+        assert!(err.os_error().is_some());
+    }
 
-        if res_1.1.as_millis() > res_2.1.as_millis() {
-            assert!(res_1.1.as_millis() - res_2.1.as_millis() >= 500);
-        } else {
-            assert!(res_2.1.as_millis() - res_1.1.as_millis() >= 500);
-        }
+    #[test]
+    fn raw_handle_round_trip() {
+        let s = Semaphore::new(2, 3, None).unwrap();
 
-        // Not signaled.
+        let handle = s.into_raw_handle();
+        assert!(!handle.is_null());
 
-        let res = s.wait(Duration::from_millis(1)).unwrap();
-        assert!(res == WaitableResult::Timeout);
+        // Not closed by `into_raw_handle`.
+        let s = unsafe { Semaphore::from_raw_handle(handle) };
+        assert_eq!(s.handle(), handle);
+        assert_eq!(s.as_raw_handle(), handle as RawHandle);
+        assert_eq!(s.max_count(), 3);
+        assert_eq!(s.count().unwrap(), 2);
     }
 }
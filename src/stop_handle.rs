@@ -0,0 +1,142 @@
+use {
+    crate::{Event, EventError},
+    std::{
+        sync::Arc,
+        thread::{self, JoinHandle},
+    },
+};
+
+/// Spawns a worker thread running the closure `f`, which is passed a manual reset stop event.
+///
+/// Packages the common "background loop with a shutdown event" pattern:
+///
+/// ```ignore
+/// let worker = spawn_with_stop(|stop| {
+///     while stop.wait(Duration::from_millis(10)).unwrap() != WaitableResult::Signaled {
+///         // ...
+///     }
+/// })?;
+///
+/// worker.stop()?;
+/// worker.join().unwrap();
+/// ```
+///
+/// The returned [`StopHandle`] sets the stop event and joins the thread when dropped,
+/// so the worker can't be forgotten running.
+///
+/// # Errors
+///
+/// Returns an error if the OS event creation failed.
+///
+/// # Panics
+///
+/// Panics if the OS fails to create the thread.
+///
+/// [`StopHandle`]: struct.StopHandle.html
+pub fn spawn_with_stop<F, T>(f: F) -> Result<StopHandle<T>, EventError>
+where
+    F: FnOnce(&Event) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let stop = Arc::new(Event::new_manual(false, None)?);
+
+    let thread = {
+        let stop = stop.clone();
+        thread::spawn(move || f(&stop))
+    };
+
+    Ok(StopHandle {
+        stop,
+        thread: Some(thread),
+    })
+}
+
+/// Stop handle of a worker thread spawned by [`spawn_with_stop`].
+///
+/// [`stop`]s the worker and waits for it to finish when dropped, unless it was already [`join`]ed.
+///
+/// [`spawn_with_stop`]: fn.spawn_with_stop.html
+/// [`stop`]: #method.stop
+/// [`join`]: #method.join
+pub struct StopHandle<T> {
+    stop: Arc<Event>,
+    /// `None` once joined.
+    thread: Option<JoinHandle<T>>,
+}
+
+impl<T> StopHandle<T> {
+    /// Sets the worker's stop event. Does not wait for the worker to finish - see [`join`].
+    ///
+    /// [`join`]: #method.join
+    pub fn stop(&self) -> Result<(), EventError> {
+        self.stop.set()
+    }
+
+    /// Returns `true` if the worker thread has finished running.
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// Blocks the thread until the worker thread finishes, returning the result of its closure.
+    ///
+    /// NOTE - does not [`stop`] the worker.
+    ///
+    /// # Errors
+    ///
+    /// Returns the panic payload if the worker's closure panicked.
+    ///
+    /// [`stop`]: #method.stop
+    pub fn join(mut self) -> thread::Result<T> {
+        self.thread.take().unwrap().join()
+    }
+}
+
+impl<T> Drop for StopHandle<T> {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = self.stop();
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+        std::time::{Duration, Instant},
+    };
+
+    #[test]
+    fn stop_and_join() {
+        let worker = spawn_with_stop(|stop| {
+            let mut num_iterations = 0;
+
+            while stop.wait(Duration::from_millis(1)).unwrap() != WaitableResult::Signaled {
+                num_iterations += 1;
+            }
+
+            num_iterations
+        })
+        .unwrap();
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(!worker.is_finished());
+
+        let now = Instant::now();
+
+        worker.stop().unwrap();
+        let num_iterations = worker.join().unwrap();
+
+        assert!(now.elapsed() < Duration::from_millis(500));
+        assert!(num_iterations > 0);
+    }
+
+    #[test]
+    fn stop_on_drop() {
+        let worker = spawn_with_stop(|stop| stop.wait_infinite().unwrap()).unwrap();
+
+        drop(worker); // Would block forever if not stopped.
+    }
+}
@@ -0,0 +1,57 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum TimerError {
+    FailedToCreate(io::Error),
+    FailedToSet(io::Error),
+    FailedToCancel(io::Error),
+    FailedToWait(io::Error),
+    InvalidDueTime,
+    InvalidPeriod,
+}
+
+impl Error for TimerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use TimerError::*;
+
+        match self {
+            FailedToCreate(err) | FailedToSet(err) | FailedToCancel(err) | FailedToWait(err) => {
+                Some(err)
+            }
+            InvalidDueTime | InvalidPeriod => None,
+        }
+    }
+}
+
+impl Display for TimerError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use TimerError::*;
+
+        match self {
+            FailedToCreate(err) => write!(f, "failed to create the timer: {}", err),
+            FailedToSet(err) => write!(f, "failed to set the timer: {}", err),
+            FailedToCancel(err) => write!(f, "failed to cancel the timer: {}", err),
+            FailedToWait(err) => write!(f, "failed to wait on the timer: {}", err),
+            InvalidDueTime => "the timer due time is out of range".fmt(f),
+            InvalidPeriod => "the timer period must be between 1 millisecond and ~24.8 days".fmt(f),
+        }
+    }
+}
+
+impl From<TimerError> for io::Error {
+    /// Unwraps the underlying OS error, if any.
+    fn from(err: TimerError) -> Self {
+        use TimerError::*;
+
+        match err {
+            FailedToCreate(err) | FailedToSet(err) | FailedToCancel(err) | FailedToWait(err) => err,
+            err @ (InvalidDueTime | InvalidPeriod) => {
+                io::Error::new(io::ErrorKind::InvalidInput, err)
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod error;
+
+#[cfg(windows)]
+mod win;
+
+pub use error::TimerError;
+
+#[cfg(windows)]
+pub use win::WaitableTimer;
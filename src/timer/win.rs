@@ -0,0 +1,317 @@
+use {
+    crate::{
+        duration_to_timeout_ms, waitable::close_handle, TimerError, WaitError, Waitable,
+        WaitableExt, WaitableResult,
+    },
+    std::os::windows::io::{AsRawHandle, RawHandle},
+    std::{
+        fmt::{Debug, Formatter},
+        io, mem, ptr,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    winapi::{
+        shared::{minwindef::FALSE, ntdef::LARGE_INTEGER, winerror::WAIT_TIMEOUT},
+        um::{
+            synchapi::{CancelWaitableTimer, SetWaitableTimer, WaitForSingleObject},
+            winbase::{CreateWaitableTimerA, WAIT_OBJECT_0},
+            winnt::HANDLE,
+        },
+    },
+};
+
+/// `FILETIME` of the Unix epoch - number of 100 nanosecond intervals since January 1, 1601 (UTC).
+const UNIX_EPOCH_FILETIME: u64 = 116_444_736_000_000_000;
+
+/// Waitable timer wrapper.
+/// See [`waitable timer`](https://docs.microsoft.com/en-us/windows/win32/sync/waitable-timer-objects) on MSDN.
+///
+/// Becomes signaled when its due time arrives, so a timeout may be waited on alongside other waitables
+/// in a single [`wait_for_one`] call.
+///
+/// Auto timer (synchronization timer): gets reset when one awaiting thread is woken up.
+/// Manual timer: stays signaled until it is set again.
+///
+/// Closes the owned OS timer handle when dropped.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub struct WaitableTimer {
+    handle: HANDLE,
+}
+
+impl WaitableTimer {
+    /// Creates a new disarmed auto reset (synchronization) timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS timer creation failed.
+    pub fn new_auto() -> Result<WaitableTimer, TimerError> {
+        WaitableTimer::new(false)
+    }
+
+    /// Creates a new disarmed manual reset (notification) timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS timer creation failed.
+    pub fn new_manual() -> Result<WaitableTimer, TimerError> {
+        WaitableTimer::new(true)
+    }
+
+    /// Arms the timer to become signaled once, after the duration `d` elapses.
+    /// Re-arms the timer if it was already armed, and resets it if it was signaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn set_relative(&self, d: Duration) -> Result<(), TimerError> {
+        // Negative values are relative to the current time.
+        self.set_impl(-duration_to_ticks(d), 0)
+    }
+
+    /// Arms the timer to become signaled once, when the system (wall clock) time reaches `time`.
+    /// Re-arms the timer if it was already armed, and resets it if it was signaled.
+    ///
+    /// Follows the changes to the system time - e.g. the timer fires early if the clock is moved forward.
+    /// If `time` has already passed, the timer becomes signaled immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `time` is not representable as a `FILETIME` (i.e. before the year 1601),
+    /// or if the OS function fails.
+    pub fn set_absolute(&self, time: SystemTime) -> Result<(), TimerError> {
+        let filetime = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => {
+                (UNIX_EPOCH_FILETIME as i64).checked_add(duration_to_ticks(since_epoch))
+            }
+            Err(err) => (UNIX_EPOCH_FILETIME as i64).checked_sub(duration_to_ticks(err.duration())),
+        };
+
+        match filetime {
+            // Positive values are absolute `FILETIME`s.
+            Some(filetime) if filetime > 0 => self.set_impl(filetime, 0),
+            _ => Err(TimerError::InvalidDueTime),
+        }
+    }
+
+    /// Arms the timer to become signaled after the duration `d` elapses, and then every `period`,
+    /// until it is [`cancel`]ed or set again.
+    /// Re-arms the timer if it was already armed, and resets it if it was signaled.
+    ///
+    /// The `period` is rounded up to whole milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `period` is zero or does not fit in an `i32` number of milliseconds,
+    /// or if the OS function fails.
+    ///
+    /// [`cancel`]: #method.cancel
+    pub fn set_periodic(&self, d: Duration, period: Duration) -> Result<(), TimerError> {
+        let period = duration_to_timeout_ms(period);
+
+        if period == 0 || period > i32::MAX as u32 {
+            return Err(TimerError::InvalidPeriod);
+        }
+
+        self.set_impl(-duration_to_ticks(d), period as i32)
+    }
+
+    /// Disarms the timer. Does not change its signaled state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn cancel(&self) -> Result<(), TimerError> {
+        if unsafe { CancelWaitableTimer(self.handle) } == FALSE {
+            Err(TimerError::FailedToCancel(io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn new(manual: bool) -> Result<WaitableTimer, TimerError> {
+        let manual = if manual { 1 } else { 0 };
+
+        let handle = unsafe { CreateWaitableTimerA(ptr::null_mut(), manual, ptr::null()) };
+
+        if handle.is_null() {
+            Err(TimerError::FailedToCreate(io::Error::last_os_error()))
+        } else {
+            Ok(WaitableTimer { handle })
+        }
+    }
+
+    /// `due_time` - see the [`docs`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-setwaitabletimer).
+    fn set_impl(&self, due_time: i64, period: i32) -> Result<(), TimerError> {
+        let mut due: LARGE_INTEGER = unsafe { mem::zeroed() };
+
+        unsafe {
+            *due.QuadPart_mut() = due_time;
+        }
+
+        let result =
+            unsafe { SetWaitableTimer(self.handle, &due, period, None, ptr::null_mut(), FALSE) };
+
+        if result == FALSE {
+            Err(TimerError::FailedToSet(io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn wait_impl(&self, ms: u32) -> Result<WaitableResult, TimerError> {
+        let result = unsafe { WaitForSingleObject(self.handle, ms) };
+
+        match result {
+            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+            _ => Err(TimerError::FailedToWait(io::Error::last_os_error())),
+        }
+    }
+}
+
+/// Converts the duration `d` to the number of 100 nanosecond intervals, rounded up.
+/// Saturates to `i64::MAX`.
+fn duration_to_ticks(d: Duration) -> i64 {
+    d.as_nanos().div_ceil(100).min(i64::MAX as u128) as i64
+}
+
+impl Drop for WaitableTimer {
+    fn drop(&mut self) {
+        let _ = close_handle(self.handle);
+    }
+}
+
+impl Debug for WaitableTimer {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("WaitableTimer")
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+unsafe impl Send for WaitableTimer {}
+unsafe impl Sync for WaitableTimer {}
+
+impl Waitable for WaitableTimer {
+    /// Blocks the thread until the timer is signaled or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(ms).map_err(WaitError::from)
+    }
+}
+
+impl WaitableExt for WaitableTimer {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.handle as *mut ()
+    }
+}
+
+impl AsRawHandle for WaitableTimer {
+    /// Returns the raw handle to the timer's OS object.
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle as RawHandle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{wait_for_one_infinite, Event, WaitablesResult},
+        std::time::Instant,
+    };
+
+    #[test]
+    fn relative() {
+        let t = WaitableTimer::new_auto().unwrap();
+
+        // Not armed.
+        let res = t.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let now = Instant::now();
+        t.set_relative(Duration::from_millis(50)).unwrap();
+
+        let res = t.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+        assert!(now.elapsed() >= Duration::from_millis(40));
+
+        // Auto timer was reset by the wait.
+        let res = t.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Cancelled.
+
+        t.set_relative(Duration::from_millis(10)).unwrap();
+        t.cancel().unwrap();
+
+        let res = t.wait(Duration::from_millis(50)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+    }
+
+    #[test]
+    fn absolute() {
+        let t = WaitableTimer::new_manual().unwrap();
+
+        t.set_absolute(SystemTime::now() + Duration::from_millis(20))
+            .unwrap();
+
+        let res = t.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Manual timer stays signaled.
+        let res = t.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Already passed.
+
+        t.set_absolute(SystemTime::now() - Duration::from_secs(1))
+            .unwrap();
+
+        let res = t.wait(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Before 1601, if representable at all.
+        if let Some(time) = UNIX_EPOCH.checked_sub(Duration::from_secs(400 * 365 * 24 * 60 * 60)) {
+            match t.set_absolute(time) {
+                Err(TimerError::InvalidDueTime) => {}
+                _ => panic!("expected an error"),
+            }
+        }
+    }
+
+    #[test]
+    fn periodic() {
+        let t = WaitableTimer::new_auto().unwrap();
+
+        t.set_periodic(Duration::from_millis(1), Duration::from_millis(1))
+            .unwrap();
+
+        for _ in 0..3 {
+            let res = t.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        t.cancel().unwrap();
+
+        match t.set_periodic(Duration::from_millis(1), Duration::from_secs(0)) {
+            Err(TimerError::InvalidPeriod) => {}
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn timeout_alongside_event() {
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let t = WaitableTimer::new_auto().unwrap();
+
+        t.set_relative(Duration::from_millis(10)).unwrap();
+
+        let res = wait_for_one_infinite(&[&e, &t]).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+    }
+}
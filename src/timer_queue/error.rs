@@ -0,0 +1,49 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum TimerQueueError {
+    FailedToCreate(io::Error),
+    FailedToSchedule(io::Error),
+    FailedToCancel(io::Error),
+    UnknownTimer,
+}
+
+impl Error for TimerQueueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use TimerQueueError::*;
+
+        match self {
+            FailedToCreate(err) | FailedToSchedule(err) | FailedToCancel(err) => Some(err),
+            UnknownTimer => None,
+        }
+    }
+}
+
+impl Display for TimerQueueError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use TimerQueueError::*;
+
+        match self {
+            FailedToCreate(err) => write!(f, "failed to create the timer queue: {}", err),
+            FailedToSchedule(err) => write!(f, "failed to schedule the timer: {}", err),
+            FailedToCancel(err) => write!(f, "failed to cancel the timer: {}", err),
+            UnknownTimer => "the timer does not belong to the timer queue".fmt(f),
+        }
+    }
+}
+
+impl From<TimerQueueError> for io::Error {
+    /// Unwraps the underlying OS error, if any.
+    fn from(err: TimerQueueError) -> Self {
+        use TimerQueueError::*;
+
+        match err {
+            FailedToCreate(err) | FailedToSchedule(err) | FailedToCancel(err) => err,
+            UnknownTimer => io::Error::new(io::ErrorKind::InvalidInput, UnknownTimer),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod error;
+
+#[cfg(windows)]
+mod win;
+
+pub use error::TimerQueueError;
+
+#[cfg(windows)]
+pub use win::{TimerHandle, TimerQueue};
@@ -0,0 +1,313 @@
+use {
+    crate::{duration_to_timeout_ms, TimerQueueError},
+    std::{
+        fmt::{Debug, Formatter},
+        io,
+        panic::{self, AssertUnwindSafe},
+        ptr,
+        sync::Mutex,
+        time::Duration,
+    },
+    winapi::{
+        shared::{
+            minwindef::FALSE,
+            ntdef::{BOOLEAN, PVOID},
+        },
+        um::{
+            handleapi::INVALID_HANDLE_VALUE,
+            threadpoollegacyapiset::{
+                CreateTimerQueue, CreateTimerQueueTimer, DeleteTimerQueueEx, DeleteTimerQueueTimer,
+            },
+            winnt::{HANDLE, WT_EXECUTEDEFAULT},
+        },
+    },
+};
+
+type Callback = Box<dyn Fn() + Send + Sync>;
+
+/// A timer scheduled on a [`TimerQueue`]. Pass to [`cancel`] to stop the timer.
+///
+/// Kept by the caller if the cancellation fails, so it may be retried.
+///
+/// [`TimerQueue`]: struct.TimerQueue.html
+/// [`cancel`]: struct.TimerQueue.html#method.cancel
+#[derive(Debug)]
+pub struct TimerHandle(HANDLE);
+
+unsafe impl Send for TimerHandle {}
+unsafe impl Sync for TimerHandle {}
+
+/// Timer queue wrapper.
+/// See [`timer queues`](https://docs.microsoft.com/en-us/windows/win32/sync/timer-queues) on MSDN.
+///
+/// Runs the callbacks of any number of one-shot / periodic timers on the OS thread pool.
+/// Scales better than one waitable timer per schedule for applications with thousands of timers.
+///
+/// Owns the callbacks of all scheduled timers until they are [`cancel`]ed or the queue is dropped.
+///
+/// Cancels all timers, waiting for all running callbacks to complete,
+/// and closes the owned OS timer queue handle when dropped.
+///
+/// [`cancel`]: #method.cancel
+pub struct TimerQueue {
+    handle: HANDLE,
+    timers: Mutex<Vec<Timer>>,
+}
+
+struct Timer {
+    handle: HANDLE,
+    callback: *mut Callback,
+}
+
+unsafe impl Send for Timer {}
+
+impl TimerQueue {
+    /// Creates a new empty timer queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS timer queue creation failed.
+    pub fn new() -> Result<TimerQueue, TimerQueueError> {
+        let handle = unsafe { CreateTimerQueue() };
+
+        if handle.is_null() {
+            Err(TimerQueueError::FailedToCreate(io::Error::last_os_error()))
+        } else {
+            Ok(TimerQueue {
+                handle,
+                timers: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    /// Schedules the `callback` to be called on the OS thread pool after the `delay` expires,
+    /// and then every `period`, if any, until the timer is [`cancel`]ed.
+    ///
+    /// The `callback` of a periodic timer may be called concurrently on multiple threads
+    /// if it takes longer than the `period` to complete.
+    /// Panics in the `callback` are caught and ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`cancel`]: #method.cancel
+    pub fn schedule<F: Fn() + Send + Sync + 'static>(
+        &self,
+        delay: Duration,
+        period: Option<Duration>,
+        callback: F,
+    ) -> Result<TimerHandle, TimerQueueError> {
+        let delay = duration_to_timeout_ms(delay);
+        let period = period.map_or(0, duration_to_timeout_ms);
+
+        let callback: *mut Callback = Box::into_raw(Box::new(Box::new(callback)));
+
+        let mut handle = ptr::null_mut();
+
+        let result = unsafe {
+            CreateTimerQueueTimer(
+                &mut handle,
+                self.handle,
+                Some(timer_callback),
+                callback as PVOID,
+                delay,
+                period,
+                WT_EXECUTEDEFAULT,
+            )
+        };
+
+        if result == FALSE {
+            let err = io::Error::last_os_error();
+
+            unsafe {
+                drop(Box::from_raw(callback));
+            }
+
+            Err(TimerQueueError::FailedToSchedule(err))
+        } else {
+            self.timers().push(Timer { handle, callback });
+
+            Ok(TimerHandle(handle))
+        }
+    }
+
+    /// Cancels the `timer`, blocking until its callback completes if it is currently running,
+    /// and frees the callback.
+    ///
+    /// On success, the `timer` is invalidated - cancelling it again returns [`UnknownTimer`].
+    ///
+    /// NOTE - must not be called from the timer's own callback, as that would never complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `timer` was not scheduled on this queue, or if the OS function fails.
+    /// In the latter case the timer may still be running - it stays scheduled on the queue, which keeps owning its callback,
+    /// and the `timer` remains valid, so the cancellation may be retried.
+    ///
+    /// [`UnknownTimer`]: enum.TimerQueueError.html#variant.UnknownTimer
+    pub fn cancel(&self, timer: &mut TimerHandle) -> Result<(), TimerQueueError> {
+        // Don't hold the lock while blocking below - the callback may schedule / cancel other timers.
+        let removed = {
+            let mut timers = self.timers();
+
+            let idx = timers
+                .iter()
+                .position(|t| t.handle == timer.0)
+                .ok_or(TimerQueueError::UnknownTimer)?;
+
+            timers.swap_remove(idx)
+        };
+
+        // Blocks until the callback, if running, completes.
+        let result =
+            unsafe { DeleteTimerQueueTimer(self.handle, removed.handle, INVALID_HANDLE_VALUE) };
+
+        // Never free the callback if the timer might still be running.
+        if result == FALSE {
+            let err = io::Error::last_os_error();
+            self.timers().push(removed);
+            return Err(TimerQueueError::FailedToCancel(err));
+        }
+
+        unsafe {
+            drop(Box::from_raw(removed.callback));
+        }
+
+        // Null never matches a scheduled timer.
+        timer.0 = ptr::null_mut();
+
+        Ok(())
+    }
+
+    fn timers(&self) -> std::sync::MutexGuard<'_, Vec<Timer>> {
+        // Callbacks are not run under the lock, so never propagate a poisoned lock.
+        self.timers.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+unsafe extern "system" fn timer_callback(callback: PVOID, _: BOOLEAN) {
+    let callback = &*(callback as *const Callback);
+
+    let _ = panic::catch_unwind(AssertUnwindSafe(callback));
+}
+
+impl Drop for TimerQueue {
+    fn drop(&mut self) {
+        // Blocks until all running callbacks complete.
+        let result = unsafe { DeleteTimerQueueEx(self.handle, INVALID_HANDLE_VALUE) };
+
+        // Never free the callbacks if the timers might still be running.
+        if result == FALSE {
+            return;
+        }
+
+        for timer in self.timers().drain(..) {
+            unsafe {
+                drop(Box::from_raw(timer.callback));
+            }
+        }
+    }
+}
+
+impl Debug for TimerQueue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("TimerQueue")
+            .field("handle", &self.handle)
+            .field("num_timers", &self.timers().len())
+            .finish()
+    }
+}
+
+unsafe impl Send for TimerQueue {}
+unsafe impl Sync for TimerQueue {}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Semaphore, Waitable, WaitableResult},
+        std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            thread,
+        },
+    };
+
+    #[test]
+    fn callbacks_fire_in_order() {
+        let q = TimerQueue::new().unwrap();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let done = Arc::new(Semaphore::new(0, 3, None).unwrap());
+
+        for (idx, delay) in [300, 100, 200].iter().enumerate() {
+            let order = order.clone();
+            let done = done.clone();
+
+            q.schedule(Duration::from_millis(*delay), None, move || {
+                order.lock().unwrap().push(idx);
+                done.increment_one().unwrap();
+            })
+            .unwrap();
+        }
+
+        for _ in 0..3 {
+            let res = done.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        assert_eq!(*order.lock().unwrap(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn cancel_periodic() {
+        let q = TimerQueue::new().unwrap();
+        let other = TimerQueue::new().unwrap();
+
+        let num_calls = Arc::new(AtomicUsize::new(0));
+        let done = Arc::new(Semaphore::new(0, i32::MAX as usize, None).unwrap());
+
+        let mut timer = {
+            let num_calls = num_calls.clone();
+            let done = done.clone();
+
+            q.schedule(
+                Duration::from_millis(1),
+                Some(Duration::from_millis(1)),
+                move || {
+                    num_calls.fetch_add(1, Ordering::SeqCst);
+                    done.increment_one().unwrap();
+                },
+            )
+            .unwrap()
+        };
+
+        // Fires repeatedly.
+        for _ in 0..3 {
+            let res = done.wait(Duration::from_secs(1_000_000)).unwrap();
+            assert!(res == WaitableResult::Signaled);
+        }
+
+        // Not scheduled on `other` - the handle is kept.
+        match other.cancel(&mut timer) {
+            Err(TimerQueueError::UnknownTimer) => {}
+            _ => panic!(),
+        }
+
+        q.cancel(&mut timer).unwrap();
+
+        // Already cancelled.
+        match q.cancel(&mut timer) {
+            Err(TimerQueueError::UnknownTimer) => {}
+            _ => panic!(),
+        }
+
+        // No more calls after cancellation.
+        let num_calls_after_cancel = num_calls.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(num_calls.load(Ordering::SeqCst), num_calls_after_cancel);
+    }
+}
@@ -0,0 +1,128 @@
+use {
+    super::win::{max_num_waitables, wait_for_handles_unchecked, wait_for_one},
+    crate::{Event, WaitError, WaitableExt, WaitablesResult},
+    std::{ptr, sync::Mutex, thread, time::Duration},
+    winapi::um::winnt::{HANDLE, MAXIMUM_WAIT_OBJECTS},
+};
+
+/// Blocks the thread until at least one of the waitables is signaled or the duration `d` expires.
+///
+/// Same as [`wait_for_one`], but accepts any number of waitables.
+/// If the len of `waitables` does not exceed the value returned by [`max_num_waitables`], simply calls [`wait_for_one`].
+/// Otherwise splits the `waitables` into chunks of `max_num_waitables() - 1`, and spawns one helper thread per chunk,
+/// which waits on the chunk and a cancellation event, set by the first helper thread whose wait returns.
+/// The index in the returned result is the index in `waitables`.
+///
+/// NOTE - spawns (and joins) the helper threads on every call, which costs far more than the wait itself
+/// for waitables which are often signaled - prefer fewer waitables, or a [`WaitSet`], where possible.
+///
+/// NOTE - the timeout is approximate: each helper thread waits for the duration `d` from the moment it starts,
+/// so the call may return somewhat later than `d` (plus the thread spawn latency).
+///
+/// NOTE - unlike [`wait_for_one`], the wait is not atomic: waitables in different chunks may be signaled
+/// at the same time, before the other helper threads are cancelled, and all of their waits are satisfied -
+/// but only one index is returned, so the signals of the other auto events / semaphores are lost.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails, or if the cancellation event creation failed.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitSet`]: struct.WaitSet.html
+pub fn wait_for_one_chunked(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    let max = max_num_waitables();
+
+    if waitables.len() <= max {
+        return wait_for_one(waitables, d);
+    }
+
+    let cancel =
+        Event::new_manual(false, None).map_err(|err| WaitError::FailedToWait(err.into()))?;
+
+    // Raw handles are not `Send` - pass them to the helper threads as integers.
+    let handles: Vec<usize> = waitables.iter().map(|w| w.handle() as usize).collect();
+
+    // The first result reported by a helper thread wins.
+    let first = Mutex::new(None);
+
+    thread::scope(|scope| {
+        // Leaves room for the cancellation event.
+        for (chunk_idx, chunk) in handles.chunks(max - 1).enumerate() {
+            let offset = chunk_idx * (max - 1);
+            let cancel = &cancel;
+            let first = &first;
+
+            scope.spawn(move || {
+                let mut chunk_handles = [ptr::null_mut() as HANDLE; MAXIMUM_WAIT_OBJECTS as usize];
+                chunk_handles[0] = cancel.handle() as HANDLE;
+
+                for (dst, &src) in chunk_handles[1..].iter_mut().zip(chunk) {
+                    *dst = src as HANDLE;
+                }
+
+                let res =
+                    match wait_for_handles_unchecked(&chunk_handles[..chunk.len() + 1], d, false) {
+                        Ok(WaitablesResult::OneSignaled(idx)) if idx > 0 => {
+                            Ok(WaitablesResult::OneSignaled(offset + idx - 1))
+                        }
+                        Ok(WaitablesResult::Abandoned(idx)) if idx > 0 => {
+                            Ok(WaitablesResult::Abandoned(offset + idx - 1))
+                        }
+                        // Cancelled, or timed out. Not an alertable wait.
+                        Ok(_) => return,
+                        Err(err) => Err(err),
+                    };
+
+                let _ = cancel.set();
+
+                first
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .get_or_insert(res);
+            });
+        }
+    });
+
+    first
+        .into_inner()
+        .unwrap_or_else(|err| err.into_inner())
+        .unwrap_or(Ok(WaitablesResult::Timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Waitable, WaitableResult},
+    };
+
+    #[test]
+    fn chunked() {
+        let events: Vec<_> = (0..200)
+            .map(|_| Event::new_auto(false, None).unwrap())
+            .collect();
+        let waitables: Vec<_> = events.iter().map(|e| e as &dyn WaitableExt).collect();
+
+        let res = wait_for_one_chunked(&waitables, Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        events[150].set().unwrap();
+
+        let res = wait_for_one_chunked(&waitables, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(150));
+
+        // The signal was consumed.
+        let res = events[150].wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Fits in a single wait.
+        events[3].set().unwrap();
+
+        let res = wait_for_one_chunked(&waitables[..8], Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(3));
+    }
+}
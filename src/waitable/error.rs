@@ -0,0 +1,95 @@
+use {
+    crate::{EventError, SemaphoreError},
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+        io,
+    },
+};
+
+#[derive(Debug)]
+pub enum WaitError {
+    /// More waitables were passed than the platform supports.
+    TooManyWaitables {
+        /// Number of waitables passed.
+        given: usize,
+        /// Value returned by [`max_num_waitables`](fn.max_num_waitables.html).
+        max: usize,
+    },
+    /// The caller-provided handle buffer is shorter than the number of waitables
+    /// (see [`wait_for_one_with_buf`](fn.wait_for_one_with_buf.html)).
+    BufferTooSmall {
+        /// Length of the buffer passed.
+        len: usize,
+        /// Number of waitables passed - the minimum buffer length.
+        required: usize,
+    },
+    FailedToWait(io::Error),
+}
+
+impl Error for WaitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use WaitError::*;
+
+        match self {
+            FailedToWait(err) => Some(err),
+            TooManyWaitables { .. } | BufferTooSmall { .. } => None,
+        }
+    }
+}
+
+impl Display for WaitError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use WaitError::*;
+
+        match self {
+            TooManyWaitables { given, max } => write!(
+                f,
+                "too many waitables: {} given, at most {} supported",
+                given, max
+            ),
+            BufferTooSmall { len, required } => write!(
+                f,
+                "handle buffer too small: {} long, at least {} required",
+                len, required
+            ),
+            FailedToWait(err) => write!(f, "failed to wait on the waitables: {}", err),
+        }
+    }
+}
+
+impl From<WaitError> for io::Error {
+    /// Unwraps the underlying OS error, if any.
+    fn from(err: WaitError) -> Self {
+        use WaitError::*;
+
+        match err {
+            FailedToWait(err) => err,
+            err @ (TooManyWaitables { .. } | BufferTooSmall { .. }) => {
+                io::Error::new(io::ErrorKind::InvalidInput, err)
+            }
+        }
+    }
+}
+
+impl From<EventError> for WaitError {
+    /// Wraps the underlying OS error, if any.
+    fn from(err: EventError) -> Self {
+        WaitError::FailedToWait(err.into())
+    }
+}
+
+impl From<SemaphoreError> for WaitError {
+    /// Wraps the underlying OS error, if any.
+    fn from(err: SemaphoreError) -> Self {
+        WaitError::FailedToWait(err.into())
+    }
+}
+
+#[cfg(windows)]
+impl From<crate::TimerError> for WaitError {
+    /// Wraps the underlying OS error, if any.
+    fn from(err: crate::TimerError) -> Self {
+        WaitError::FailedToWait(err.into())
+    }
+}
@@ -0,0 +1,124 @@
+use {
+    super::win::{check_num_waitables, wait_for_handles_unchecked},
+    crate::{WaitError, WaitableExt, WaitablesResult},
+    std::time::Duration,
+    winapi::um::winnt::{HANDLE, MAXIMUM_WAIT_OBJECTS},
+};
+
+/// A set of waitables which are serviced fairly over repeated waits.
+///
+/// When multiple waitables are signaled, [`wait_for_one`] always reports the one with the lowest index,
+/// so a waitable which is signaled often (e.g. an auto event set in a loop) may starve all the waitables after it.
+///
+/// [`wait_one`] instead rotates the order the waitables are passed to the OS in,
+/// starting after the waitable which was serviced last, so a waitable which stays signaled
+/// is guaranteed to be serviced at least once every `len` calls to [`wait_one`].
+///
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_one`]: #method.wait_one
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+pub struct FairWaitSet<'a> {
+    waitables: Vec<&'a dyn WaitableExt>,
+    service_counts: Vec<usize>,
+    first: usize,
+}
+
+impl<'a> FairWaitSet<'a> {
+    /// Creates a new fair wait set over the `waitables`.
+    pub fn new(waitables: &[&'a dyn WaitableExt]) -> Self {
+        Self {
+            waitables: waitables.to_vec(),
+            service_counts: vec![0; waitables.len()],
+            first: 0,
+        }
+    }
+
+    /// Blocks the thread until at least one of the waitables in the set is signaled or the duration `d` expires.
+    /// Returned indices correspond to the order of waitables passed to [`new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    /// Returns [`WaitError::TooManyWaitables`] if the number of waitables in the set exceeds the value returned by [`max_num_waitables`].
+    ///
+    /// [`new`]: #method.new
+    /// [`max_num_waitables`]: fn.max_num_waitables.html
+    /// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+    pub fn wait_one(&mut self, d: Duration) -> Result<WaitablesResult, WaitError> {
+        let num_waitables = self.waitables.len();
+
+        check_num_waitables(num_waitables)?;
+
+        let mut handles = [0 as HANDLE; MAXIMUM_WAIT_OBJECTS as usize];
+
+        for (idx, handle) in handles.iter_mut().take(num_waitables).enumerate() {
+            *handle = self.waitables[(self.first + idx) % num_waitables].handle() as HANDLE;
+        }
+
+        let result = wait_for_handles_unchecked(&handles[..num_waitables], d, false)?;
+
+        match result {
+            WaitablesResult::OneSignaled(idx) => {
+                Ok(WaitablesResult::OneSignaled(self.serviced(idx)))
+            }
+            WaitablesResult::Abandoned(idx) => Ok(WaitablesResult::Abandoned(self.serviced(idx))),
+            _ => Ok(result),
+        }
+    }
+
+    /// Returns the number of times each waitable in the set was reported signaled by [`wait_one`],
+    /// in order of waitables passed to [`new`].
+    ///
+    /// [`wait_one`]: #method.wait_one
+    /// [`new`]: #method.new
+    pub fn service_counts(&self) -> &[usize] {
+        &self.service_counts
+    }
+
+    /// Maps the index `idx` of the waitable reported by the OS to its index in the set, and rotates the set past it.
+    fn serviced(&mut self, idx: usize) -> usize {
+        let num_waitables = self.waitables.len();
+        let idx = (self.first + idx) % num_waitables;
+
+        self.service_counts[idx] += 1;
+        self.first = (idx + 1) % num_waitables;
+
+        idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Event};
+
+    #[test]
+    fn always_signaled() {
+        let e0 = Event::new_auto(false, None).unwrap();
+        let e1 = Event::new_auto(false, None).unwrap();
+
+        let mut s = FairWaitSet::new(&[&e0, &e1]);
+
+        let mut prev = None;
+
+        for _ in 0..100 {
+            // Both are always signaled.
+            e0.set().unwrap();
+            e1.set().unwrap();
+
+            let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+
+            let idx = match res {
+                WaitablesResult::OneSignaled(idx) => idx,
+                _ => panic!("expected a signaled event"),
+            };
+
+            // Never serviced twice in a row.
+            assert!(prev != Some(idx));
+            prev = Some(idx);
+        }
+
+        assert_eq!(s.service_counts(), &[50, 50]);
+    }
+}
@@ -0,0 +1,504 @@
+use {
+    super::timeout::{Deadline, RealTimeSource},
+    crate::{wait_for_all, wait_for_one, WaitError, WaitableResult, WaitablesResult},
+    std::{io, thread, time::Duration},
+};
+
+#[cfg(windows)]
+use crate::WaitableExt as Element;
+
+#[cfg(not(windows))]
+use crate::Waitable as Element;
+
+/// Result of waiting on a [`WaitGroup`].
+///
+/// [`WaitGroup`]: struct.WaitGroup.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum WaitGroupResult {
+    /// The group's condition was satisfied.
+    /// Contains the indices of the waitables which satisfied it, in ascending order -
+    /// all waitables of the satisfied "all of" groups and the satisfying member of each satisfied "one of" group.
+    /// Abandoned mutexes count as signaled.
+    Signaled(Vec<usize>),
+    /// The timeout duration elapsed before the group's condition was satisfied.
+    Timeout,
+}
+
+/// Composite wait condition over waitables and nested groups, each either "all of" or "one of" its members -
+/// e.g. "`a` is signaled and (`b` or `c`) is signaled", which a single [`wait_for_all`] / [`wait_for_one`] can't express.
+///
+/// ```no_run
+/// use {minievent::{Event, WaitGroup, WaitGroupResult}, std::time::Duration};
+///
+/// let a = Event::new_auto(false, None).unwrap();
+/// let b = Event::new_auto(false, None).unwrap();
+/// let c = Event::new_auto(false, None).unwrap();
+///
+/// // `a` and (`b` or `c`).
+/// let group = WaitGroup::all_of()
+///     .with(&a)
+///     .with_group(WaitGroup::one_of().with(&b).with(&c));
+///
+/// if let WaitGroupResult::Signaled(signaled) = group.wait(Duration::from_secs(1)).unwrap() {
+///     assert!(signaled == [0, 1] || signaled == [0, 2]);
+/// }
+/// ```
+///
+/// Waitables are indexed in the order they appear in the (fully built) group, depth first -
+/// i.e. in the builder expression, left to right.
+///
+/// Implemented by nesting [`wait_for_all`] / [`wait_for_one`] calls against a common deadline:
+/// an "all of" group waits on its own waitables, then on each of its sub-groups in turn.
+/// A "one of" group with sub-groups expands into the alternative sets of its waitables which satisfy it
+/// (e.g. "`a` and `b`" or "`a` and `c`" for "`a` and (`b` or `c`)"), and polls each set via [`wait_for_all`]
+/// with a short backoff, so may oversleep by up to a few milliseconds.
+/// The number of sets grows with the product of the sizes of the "one of" groups nested in an "all of" group.
+/// Such a group is thus not a single blocking OS wait, but a busy-wait: the thread wakes up repeatedly (every
+/// 10 milliseconds at most once the backoff saturates) for as long as the group is unsatisfied, costing CPU time,
+/// and a signal is only noticed on the next poll. Where latency or CPU time matter,
+/// prefer conditions whose "one of" groups only contain waitables.
+/// On Windows, each set must also not exceed [`max_num_waitables`] waitables, even if no group directly does (see [`wait`]).
+///
+/// NOTE - only the waitables directly in the same "all of" group (or in the same polled set) are waited on at once.
+/// The signals consumed by the satisfied parts of the condition (e.g. of auto reset events or semaphores)
+/// are not restored if the condition as a whole times out.
+/// On Windows, a polled set consumes no signals unless all of its waitables are signaled.
+/// On other platforms, [`wait_for_all`] waits on the waitables in turn,
+/// so polling a partially signaled set may consume the signals of the waitables before the first unsignaled one.
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`wait`]: #method.wait
+pub struct WaitGroup<'a> {
+    all: bool,
+    members: Vec<Member<'a>>,
+}
+
+enum Member<'a> {
+    Waitable(&'a dyn Element),
+    Group(WaitGroup<'a>),
+}
+
+impl<'a> WaitGroup<'a> {
+    /// Creates a new empty group which is satisfied when all of its members are.
+    pub fn all_of() -> Self {
+        Self::new(true)
+    }
+
+    /// Creates a new empty group which is satisfied when at least one of its members is.
+    pub fn one_of() -> Self {
+        Self::new(false)
+    }
+
+    /// Adds the `waitable` to the group.
+    pub fn with<W: Element>(mut self, waitable: &'a W) -> Self {
+        self.members.push(Member::Waitable(waitable));
+        self
+    }
+
+    /// Adds the sub-group `group` to the group.
+    pub fn with_group(mut self, group: WaitGroup<'a>) -> Self {
+        self.members.push(Member::Group(group));
+        self
+    }
+
+    /// Returns the number of waitables in the group, including the ones in its sub-groups.
+    pub fn num_waitables(&self) -> usize {
+        self.members
+            .iter()
+            .map(|member| match member {
+                Member::Waitable(_) => 1,
+                Member::Group(group) => group.num_waitables(),
+            })
+            .sum()
+    }
+
+    /// Blocks the thread until the group's condition is satisfied or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails, or if the group or any of its sub-groups is empty.
+    ///
+    /// On Windows, returns [`WaitError::TooManyWaitables`] before waiting if any single wait would exceed
+    /// [`max_num_waitables`] waitables - i.e. if any group directly contains more waitables,
+    /// or if any alternative set polled for a "one of" group with sub-groups does
+    /// (e.g. "(`a` and `b`) or `c`" polls a set of two).
+    ///
+    /// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+    /// [`max_num_waitables`]: fn.max_num_waitables.html
+    pub fn wait(&self, d: Duration) -> Result<WaitGroupResult, WaitError> {
+        if self.has_empty_group() {
+            return Err(WaitError::FailedToWait(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty wait group",
+            )));
+        }
+
+        #[cfg(windows)]
+        {
+            let given = self.max_wait_size();
+            let max = crate::max_num_waitables();
+
+            if given > max {
+                return Err(WaitError::TooManyWaitables { given, max });
+            }
+        }
+
+        let mut signaled = Vec::new();
+
+        if self.wait_impl(0, &Deadline::new(d, &RealTimeSource), &mut signaled)? {
+            signaled.sort_unstable();
+
+            Ok(WaitGroupResult::Signaled(signaled))
+        } else {
+            Ok(WaitGroupResult::Timeout)
+        }
+    }
+
+    fn new(all: bool) -> Self {
+        Self {
+            all,
+            members: Vec::new(),
+        }
+    }
+
+    fn has_empty_group(&self) -> bool {
+        self.members.is_empty()
+            || self.members.iter().any(|member| match member {
+                Member::Waitable(_) => false,
+                Member::Group(group) => group.has_empty_group(),
+            })
+    }
+
+    /// Returns the number of waitables in the largest single wait on the group -
+    /// its own waitables, the largest alternative set if it's polled, or the largest wait on its sub-groups.
+    #[cfg(windows)]
+    fn max_wait_size(&self) -> usize {
+        let num_own = self
+            .members
+            .iter()
+            .filter(|member| matches!(member, Member::Waitable(_)))
+            .count();
+        let has_groups = num_own < self.members.len();
+
+        let own = if !self.all && has_groups {
+            self.max_alternative_size()
+        } else {
+            num_own
+        };
+
+        self.members
+            .iter()
+            .filter_map(|member| match member {
+                Member::Waitable(_) => None,
+                Member::Group(group) => Some(group.max_wait_size()),
+            })
+            .fold(own, usize::max)
+    }
+
+    /// Returns the size of the group's largest alternative set (see `alternatives`), without building them.
+    #[cfg(windows)]
+    fn max_alternative_size(&self) -> usize {
+        let sizes = self.members.iter().map(|member| match member {
+            Member::Waitable(_) => 1,
+            Member::Group(group) => group.max_alternative_size(),
+        });
+
+        if self.all {
+            sizes.sum()
+        } else {
+            sizes.max().unwrap_or(0)
+        }
+    }
+
+    /// `offset` is the index of the group's first waitable.
+    /// Returns `true` and appends the indices of the waitables which satisfied the condition to `signaled`
+    /// if the condition was satisfied; `signaled` contents are unspecified otherwise.
+    fn wait_impl(
+        &self,
+        offset: usize,
+        deadline: &Deadline,
+        signaled: &mut Vec<usize>,
+    ) -> Result<bool, WaitError> {
+        let mut waitables = Vec::new();
+        let mut indices = Vec::new();
+        let mut groups = Vec::new();
+
+        let mut index = offset;
+
+        for member in self.members.iter() {
+            match member {
+                Member::Waitable(waitable) => {
+                    waitables.push(*waitable);
+                    indices.push(index);
+                    index += 1;
+                }
+                Member::Group(group) => {
+                    groups.push((group, index));
+                    index += group.num_waitables();
+                }
+            }
+        }
+
+        let remaining = || deadline.remaining(&RealTimeSource);
+
+        if self.all {
+            if !waitables.is_empty() {
+                if wait_for_all(&waitables, remaining())? == WaitableResult::Timeout {
+                    return Ok(false);
+                }
+
+                signaled.extend_from_slice(&indices);
+            }
+
+            for (group, offset) in groups {
+                if !group.wait_impl(offset, deadline, signaled)? {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        } else if groups.is_empty() {
+            match wait_for_one(&waitables, remaining())? {
+                WaitablesResult::OneSignaled(idx) | WaitablesResult::Abandoned(idx) => {
+                    signaled.push(indices[idx]);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        } else {
+            const MIN_BACKOFF: Duration = Duration::from_micros(100);
+            const MAX_BACKOFF: Duration = Duration::from_millis(10);
+
+            let mut leaves = Vec::with_capacity(index - offset);
+            self.leaves(&mut leaves);
+
+            let alternatives: Vec<(Vec<usize>, Vec<_>)> = self
+                .alternatives(offset)
+                .into_iter()
+                .map(|indices| {
+                    let waitables = indices.iter().map(|&idx| leaves[idx - offset]).collect();
+                    (indices, waitables)
+                })
+                .collect();
+
+            let mut backoff = MIN_BACKOFF;
+
+            loop {
+                for (indices, waitables) in alternatives.iter() {
+                    if wait_for_all(waitables, Duration::from_secs(0))? != WaitableResult::Timeout {
+                        signaled.extend_from_slice(indices);
+                        return Ok(true);
+                    }
+                }
+
+                let remaining = remaining();
+
+                if remaining == Duration::from_secs(0) {
+                    return Ok(false);
+                }
+
+                thread::sleep(backoff.min(remaining));
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    /// Appends the group's waitables to `leaves`, in index order.
+    fn leaves(&self, leaves: &mut Vec<&'a dyn Element>) {
+        for member in self.members.iter() {
+            match member {
+                Member::Waitable(waitable) => leaves.push(*waitable),
+                Member::Group(group) => group.leaves(leaves),
+            }
+        }
+    }
+
+    /// Returns the alternative sets of the group's waitables (as ascending indices, starting at `offset`)
+    /// any of which satisfies the group's condition when all of its waitables are signaled -
+    /// e.g. `[[0, 1], [0, 2]]` for "`a` and (`b` or `c`)".
+    fn alternatives(&self, offset: usize) -> Vec<Vec<usize>> {
+        let mut alternatives = if self.all {
+            vec![Vec::new()]
+        } else {
+            Vec::new()
+        };
+        let mut index = offset;
+
+        for member in self.members.iter() {
+            let (member_alternatives, num_waitables) = match member {
+                Member::Waitable(_) => (vec![vec![index]], 1),
+                Member::Group(group) => (group.alternatives(index), group.num_waitables()),
+            };
+
+            index += num_waitables;
+
+            if self.all {
+                alternatives = alternatives
+                    .iter()
+                    .flat_map(|alternative| {
+                        member_alternatives.iter().map(move |member_alternative| {
+                            alternative
+                                .iter()
+                                .chain(member_alternative.iter())
+                                .copied()
+                                .collect()
+                        })
+                    })
+                    .collect();
+            } else {
+                alternatives.extend(member_alternatives);
+            }
+        }
+
+        alternatives
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Event};
+
+    #[test]
+    fn wait_group() {
+        let a = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let b = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let c = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+        // `a` and (`b` or `c`).
+        let group = WaitGroup::all_of()
+            .with(&a)
+            .with_group(WaitGroup::one_of().with(&b).with(&c));
+        assert_eq!(group.num_waitables(), 3);
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Timeout);
+
+        a.set().unwrap();
+        c.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![0, 2]));
+
+        b.set().unwrap();
+
+        // `a` is not signaled - `b` is left as is.
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Timeout);
+
+        a.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![0, 1]));
+
+        // (`a` and `b`) or `c`.
+        let group = WaitGroup::one_of()
+            .with_group(WaitGroup::all_of().with(&a).with(&b))
+            .with(&c);
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Timeout);
+
+        c.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![2]));
+
+        a.set().unwrap();
+        b.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![0, 1]));
+
+        let group = WaitGroup::all_of().with(&a).with_group(WaitGroup::one_of());
+
+        match group.wait(Duration::from_millis(1)) {
+            Err(WaitError::FailedToWait(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::InvalidInput)
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+
+    /// Only atomic on Windows - see the `WaitGroup` docs.
+    #[cfg(windows)]
+    #[test]
+    fn wait_group_nested_one_of() {
+        let a = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let b = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let c = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let d = Event::new_auto(false, None).unwrap(); // Not signaled.
+
+        // (`a` and (`b` or `c`)) or `d`.
+        let group = WaitGroup::one_of()
+            .with_group(
+                WaitGroup::all_of()
+                    .with(&a)
+                    .with_group(WaitGroup::one_of().with(&b).with(&c)),
+            )
+            .with(&d);
+
+        // `a` is set before `b` - polling does not consume its signal.
+        a.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(20)).unwrap();
+        assert!(res == WaitGroupResult::Timeout);
+
+        b.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(20)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![0, 1]));
+
+        a.set().unwrap();
+        c.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(20)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![0, 2]));
+
+        d.set().unwrap();
+
+        let res = group.wait(Duration::from_millis(20)).unwrap();
+        assert!(res == WaitGroupResult::Signaled(vec![3]));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn wait_group_too_many_polled() {
+        fn all_of(events: &[Event]) -> WaitGroup<'_> {
+            events
+                .iter()
+                .fold(WaitGroup::all_of(), |group, event| group.with(event))
+        }
+
+        let half = crate::max_num_waitables() / 2 + 1;
+
+        let events: Vec<_> = (0..half * 2)
+            .map(|_| Event::new_manual(true, None).unwrap()) // Signaled.
+            .collect();
+
+        // No group directly contains more than `max_num_waitables` waitables,
+        // but the single polled set does.
+        let group = WaitGroup::one_of().with_group(
+            WaitGroup::all_of()
+                .with_group(all_of(&events[..half]))
+                .with_group(all_of(&events[half..])),
+        );
+
+        match group.wait(Duration::from_millis(1)) {
+            Err(WaitError::TooManyWaitables { given, max }) => {
+                assert_eq!(given, half * 2);
+                assert_eq!(max, crate::max_num_waitables());
+            }
+            _ => panic!(),
+        }
+
+        // An "all of" group waits on its sub-groups in turn.
+        let group = WaitGroup::all_of()
+            .with_group(all_of(&events[..half]))
+            .with_group(all_of(&events[half..]));
+
+        let res = group.wait(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitGroupResult::Signaled((0..half * 2).collect()));
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    super::win::wait_for_handles_unchecked,
+    crate::{Event, WaitError, WaitableExt, WaitablesResult},
+    std::{io, sync::Mutex, time::Duration},
+    winapi::um::winnt::HANDLE,
+};
+
+/// The process-wide interrupt event, created on first use and never destroyed.
+static INTERRUPT_EVENT: Mutex<Option<&'static Event>> = Mutex::new(None);
+
+/// Result of an interruptible wait on a waitable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InterruptibleResult {
+    /// The waitable was signaled.
+    Signaled,
+    /// The timeout duration elapsed before the waitable was signaled.
+    Timeout,
+    /// [`interrupt_all`](fn.interrupt_all.html) was called before the waitable was signaled.
+    Interrupted,
+}
+
+/// Interrupts all current and future [`wait_interruptible`] calls in the process,
+/// until [`clear_interrupt`] is called.
+///
+/// NOTE - the interrupt is global state, shared by all users of the crate in the process
+/// (e.g. all libraries using it), and is meant as the last-resort lever, e.g. for emergency shutdown.
+///
+/// # Errors
+///
+/// Returns an error if the OS event creation failed, or if the OS function fails.
+///
+/// [`wait_interruptible`]: trait.WaitableExt.html#method.wait_interruptible
+/// [`clear_interrupt`]: fn.clear_interrupt.html
+pub fn interrupt_all() -> io::Result<()> {
+    interrupt_event()?.set().map_err(io::Error::from)
+}
+
+/// Clears the interrupt set by [`interrupt_all`], so that [`wait_interruptible`] calls block again.
+///
+/// # Errors
+///
+/// Returns an error if the OS event creation failed, or if the OS function fails.
+///
+/// [`interrupt_all`]: fn.interrupt_all.html
+/// [`wait_interruptible`]: trait.WaitableExt.html#method.wait_interruptible
+pub fn clear_interrupt() -> io::Result<()> {
+    interrupt_event()?.reset().map_err(io::Error::from)
+}
+
+/// Same as `wait_for_one(&[interrupt_event, waitable], d)`.
+pub(crate) fn wait_interruptible(
+    handle: HANDLE,
+    d: Duration,
+) -> Result<InterruptibleResult, WaitError> {
+    let interrupt = interrupt_event().map_err(WaitError::FailedToWait)?;
+
+    // The interrupt takes priority, and the waitable's signal is not consumed if both are signaled.
+    let handles = [interrupt.handle() as HANDLE, handle];
+
+    match wait_for_handles_unchecked(&handles, d, false)? {
+        WaitablesResult::OneSignaled(0) => Ok(InterruptibleResult::Interrupted),
+        // The abandoned mutex is still acquired.
+        WaitablesResult::OneSignaled(_) | WaitablesResult::Abandoned(_) => {
+            Ok(InterruptibleResult::Signaled)
+        }
+        _ => Ok(InterruptibleResult::Timeout),
+    }
+}
+
+fn interrupt_event() -> io::Result<&'static Event> {
+    // Never propagate a poisoned lock - the event is created at most once.
+    let mut interrupt_event = INTERRUPT_EVENT
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+
+    if let Some(event) = *interrupt_event {
+        Ok(event)
+    } else {
+        let event: &'static Event = Box::leak(Box::new(Event::new_manual(false, None)?));
+        *interrupt_event = Some(event);
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{sync::Arc, thread},
+    };
+
+    #[test]
+    fn interrupt_all_waits() {
+        let events: Vec<_> = (0..4)
+            .map(|_| Arc::new(Event::new_auto(false, None).unwrap())) // Not signaled.
+            .collect();
+
+        let threads: Vec<_> = events
+            .iter()
+            .map(|e| {
+                let e = e.clone();
+                thread::spawn(move || {
+                    e.wait_interruptible(Duration::from_secs(1_000_000))
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        thread::sleep(Duration::from_millis(10));
+        interrupt_all().unwrap();
+
+        for t in threads {
+            assert!(t.join().unwrap() == InterruptibleResult::Interrupted);
+        }
+
+        // Stays interrupted, and takes priority over the waitable's signal, which is not consumed.
+
+        events[0].set().unwrap();
+
+        let res = events[0]
+            .wait_interruptible(Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == InterruptibleResult::Interrupted);
+
+        clear_interrupt().unwrap();
+
+        let res = events[0]
+            .wait_interruptible(Duration::from_secs(1_000_000))
+            .unwrap();
+        assert!(res == InterruptibleResult::Signaled);
+
+        let res = events[0]
+            .wait_interruptible(Duration::from_millis(1))
+            .unwrap();
+        assert!(res == InterruptibleResult::Timeout);
+    }
+}
@@ -1,4 +1,10 @@
-use std::time::Duration;
+use {
+    crate::{EventError, SemaphoreError},
+    std::{
+        io,
+        time::{Duration, Instant},
+    },
+};
 
 /// Result of waiting on a single waitable, or multiple waitables if all must be siganled.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -7,6 +13,15 @@ pub enum WaitableResult {
     Signaled,
     /// The timeout duration elapsed before the waitable was signaled / all waitables were signaled.
     Timeout,
+    /// All waitables were signaled, but (at least) one of them was an abandoned mutex -
+    /// i.e. its owning thread exited without releasing it.
+    /// The ownership of the mutex is granted to the calling thread, but the state it protects may be inconsistent.
+    Abandoned,
+    /// The alertable wait was ended early because the OS queued an APC or I/O completion routine to the thread,
+    /// which was run before the wait returned.
+    ///
+    /// Only returned by alertable waits (e.g. [`wait_alertable`](trait.WaitableExt.html#method.wait_alertable)), on Windows.
+    IoCompletion,
 }
 
 /// Result of waiting on multiple waitables.
@@ -19,19 +34,521 @@ pub enum WaitablesResult {
     AllSignaled,
     /// The timeout duration elapsed before any waitable was signaled.
     Timeout,
+    /// One of the waitables was an abandoned mutex - i.e. its owning thread exited without releasing it.
+    /// Contains the index of the abandoned waitable.
+    /// The ownership of the mutex is granted to the calling thread, but the state it protects may be inconsistent.
+    ///
+    /// When waiting for all waitables, all of them were signaled.
+    Abandoned(usize),
+    /// The alertable wait was ended early because the OS queued an APC or I/O completion routine to the thread,
+    /// which was run before the wait returned.
+    ///
+    /// Only returned by alertable waits (e.g. [`wait_for_one_alertable`](fn.wait_for_one_alertable.html)), on Windows.
+    IoCompletion,
 }
 
+/// Result of waiting on multiple waitables via [`wait_for_all_or_partial`](fn.wait_for_all_or_partial.html).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PartialResult {
+    /// All of the waitables were signaled.
+    AllSignaled,
+    /// The timeout duration elapsed before all waitables were signaled.
+    /// Contains the indices of the waitables which were signaled at that point, in ascending order;
+    /// empty if none were.
+    Partial(Vec<usize>),
+}
+
+impl PartialResult {
+    /// Returns, for each of the `num_waitables` waitables passed to the wait, whether it was signaled.
+    ///
+    /// See [`wait_for_all_detailed`](fn.wait_for_all_detailed.html), which returns the mask directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the signaled indices is out of `num_waitables` range.
+    pub fn signaled_mask(&self, num_waitables: usize) -> Vec<bool> {
+        match self {
+            PartialResult::AllSignaled => vec![true; num_waitables],
+            PartialResult::Partial(signaled) => {
+                let mut mask = vec![false; num_waitables];
+
+                for &idx in signaled {
+                    mask[idx] = true;
+                }
+
+                mask
+            }
+        }
+    }
+}
+
+/// Result of waiting on a waitable while watching an atomic value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WatchedResult {
+    /// The waitable was signaled.
+    Signaled,
+    /// The timeout duration elapsed before the waitable was signaled.
+    Timeout,
+    /// The watched value changed before the waitable was signaled.
+    Changed,
+}
+
+/// How a waitable's [`wait`] / [`wait_infinite`] methods wait for the waitable to be signaled.
+///
+/// Only affects the waitable's own wait methods -
+/// waits on multiple waitables (e.g. [`wait_for_one`]) always block.
+///
+/// [`wait`]: trait.Waitable.html#method.wait
+/// [`wait_infinite`]: trait.Waitable.html#method.wait_infinite
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum WaitStrategy {
+    /// Blocks the thread in the OS until the waitable is signaled. The default.
+    #[default]
+    Blocking,
+    /// Checks whether the waitable is signaled up to `spin` times, busy-waiting in between,
+    /// before blocking the thread in the OS.
+    /// Reduces the latency for waitables which are usually signaled shortly after the wait begins,
+    /// at the cost of burning CPU time.
+    ///
+    /// `spin` must be in `1..=MAX_SPIN`.
+    SpinThenBlock { spin: usize },
+    /// Repeatedly checks whether the waitable is signaled, yielding the rest of the thread's
+    /// time slice to other threads in between, and never blocks the thread in the OS.
+    Yielding,
+}
+
+impl WaitStrategy {
+    /// Maximum spin count of `SpinThenBlock`.
+    pub const MAX_SPIN: usize = 1 << 20;
+
+    /// Returns `true` if the strategy's parameters are valid.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            WaitStrategy::SpinThenBlock { spin } => (1..=Self::MAX_SPIN).contains(spin),
+            _ => true,
+        }
+    }
+}
+
+/// Timeout in milliseconds passed to [`Waitable::wait_raw_ms`] to wait until the waitable is signaled,
+/// without a timeout. Same as the Windows `INFINITE`.
+///
+/// [`Waitable::wait_raw_ms`]: trait.Waitable.html#tymethod.wait_raw_ms
+pub const INFINITE_MS: u32 = u32::MAX;
+
 /// Waitable object trait.
+///
+/// Implementors only need to provide [`wait_raw_ms`]; the other wait methods are implemented in terms of it.
+///
+/// [`wait_raw_ms`]: #tymethod.wait_raw_ms
 pub trait Waitable {
+    /// Blocks the thread until the waitable is signaled or the timeout of `ms` milliseconds expires.
+    ///
+    /// [`INFINITE_MS`] means no timeout - the wait returns only once the waitable is signaled.
+    ///
+    /// [`INFINITE_MS`]: constant.INFINITE_MS.html
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError>;
+
     /// Blocks the thread until the waitable is signaled or the duration `d` expires.
-    fn wait(&self, d: Duration) -> Result<WaitableResult, ()>;
+    ///
+    /// The duration `d` is converted to milliseconds via [`duration_to_timeout_ms`].
+    ///
+    /// [`duration_to_timeout_ms`]: fn.duration_to_timeout_ms.html
+    fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        self.wait_raw_ms(duration_to_timeout_ms(d))
+    }
 
     /// Blocks the thread until the waitable is signaled.
-    fn wait_infinite(&self) -> Result<(), ()>;
+    fn wait_infinite(&self) -> Result<(), WaitError> {
+        self.wait_raw_ms(INFINITE_MS).map(|_| ())
+    }
+
+    /// Blocks the thread until the waitable is signaled or the `deadline` passes.
+    ///
+    /// The `deadline` is an `Instant` on the monotonic clock, so the wait is immune to
+    /// changes of the system (wall-clock) time, e.g. NTP adjustments or the user changing the clock.
+    /// Waits for a calendar time (i.e. a `SystemTime`) should use a waitable timer instead,
+    /// keeping in mind that those may fire early or late if the wall clock jumps.
+    ///
+    /// If the `deadline` has already passed, checks whether the waitable is signaled without blocking.
+    fn wait_until(&self, deadline: Instant) -> Result<WaitableResult, WaitError> {
+        self.wait(deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Same as [`wait`], but also returns the wall time the thread actually spent blocked,
+    /// which may differ from the duration `d` due to the timer resolution and scheduling.
+    ///
+    /// Useful for adaptive backoff, which tunes the next timeout based on the observed wait latency.
+    ///
+    /// [`wait`]: #method.wait
+    fn wait_timed(&self, d: Duration) -> Result<(WaitableResult, Duration), WaitError> {
+        let start = Instant::now();
+        let res = self.wait(d)?;
+
+        Ok((res, start.elapsed()))
+    }
+
+    /// Same as [`wait`], but also logs how long the wait blocked, tagged with the `label`.
+    ///
+    /// Emits a `debug` level [`log`](https://docs.rs/log/*/log/) record with the `minievent` target
+    /// and the `label`, `result` (e.g. `Signaled`, `Timeout`) and `elapsed_us` key-values when the wait returns,
+    /// or a `warn` level record if it fails.
+    ///
+    /// Only available with the `metrics` feature.
+    ///
+    /// [`wait`]: #method.wait
+    #[cfg(feature = "metrics")]
+    fn wait_timed_logged(&self, d: Duration, label: &str) -> Result<WaitableResult, WaitError> {
+        let start = Instant::now();
+        let res = self.wait(d);
+        let elapsed_us = start.elapsed().as_micros() as u64;
+
+        match &res {
+            Ok(res) => log::debug!(
+                target: "minievent",
+                label,
+                result:? = res,
+                elapsed_us;
+                "wait on `{}` returned `{:?}` after {} us",
+                label,
+                res,
+                elapsed_us
+            ),
+            Err(err) => log::warn!(
+                target: "minievent",
+                label,
+                elapsed_us;
+                "wait on `{}` failed after {} us: {}",
+                label,
+                elapsed_us,
+                err
+            ),
+        }
+
+        res
+    }
+}
+
+/// Converts the duration `d` to the timeout in milliseconds passed to the OS wait functions,
+/// as done by all waits in the crate.
+///
+/// - zero duration stays zero - i.e. the wait only checks whether the waitable is signaled without blocking;
+/// - partial milliseconds are rounded up (e.g. `1ns` becomes `1ms`), so a wait never times out
+///   before the duration `d` expires;
+/// - durations too long to be represented saturate to `u32::MAX - 1` milliseconds (~49.7 days),
+///   one less than the special [`INFINITE_MS`] value, so a finite duration never results in an infinite wait.
+///
+/// [`INFINITE_MS`]: constant.INFINITE_MS.html
+pub fn duration_to_timeout_ms(d: Duration) -> u32 {
+    const MAX_TIMEOUT_MS: u128 = (u32::MAX - 1) as u128;
+
+    d.as_nanos().div_ceil(1_000_000).min(MAX_TIMEOUT_MS) as u32
+}
+
+/// Extension trait converting the results of waiting on a waitable into `std::io::Result`,
+/// so that wait failures may flow through `io::Result`-returning functions.
+///
+/// ```ignore
+/// let signaled = event.wait(d).into_io()?;
+/// ```
+pub trait IntoIoResult {
+    /// `Signaled` maps to `Ok(true)`, `Timeout` (and `IoCompletion`) maps to `Ok(false)`, failures map to `Err`.
+    ///
+    /// `Abandoned` maps to an `Err` of kind `Other` - the state protected by the abandoned mutex may be inconsistent,
+    /// so it must not be mistaken for a timeout.
+    fn into_io(self) -> io::Result<bool>;
+}
+
+impl IntoIoResult for WaitableResult {
+    fn into_io(self) -> io::Result<bool> {
+        match self {
+            WaitableResult::Signaled => Ok(true),
+            WaitableResult::Timeout | WaitableResult::IoCompletion => Ok(false),
+            WaitableResult::Abandoned => Err(io::Error::other("the waited on mutex was abandoned")),
+        }
+    }
+}
+
+impl IntoIoResult for Result<WaitableResult, WaitError> {
+    fn into_io(self) -> io::Result<bool> {
+        self?.into_io()
+    }
+}
+
+impl IntoIoResult for Result<WaitableResult, EventError> {
+    fn into_io(self) -> io::Result<bool> {
+        self?.into_io()
+    }
 }
 
+impl IntoIoResult for Result<WaitableResult, SemaphoreError> {
+    fn into_io(self) -> io::Result<bool> {
+        self?.into_io()
+    }
+}
+
+mod error;
+mod group;
+mod sequential;
+mod timeout;
+mod tuple;
+
+pub use {
+    error::WaitError,
+    group::{WaitGroup, WaitGroupResult},
+    sequential::{wait_for_all_sequential, wait_for_one_sequential},
+    tuple::{wait_for_all_of, wait_for_one_of, WaitableTuple},
+};
+
+#[cfg(not(windows))]
+pub use sequential::{
+    wait_for_all, wait_for_all_boxed, wait_for_all_infinite, wait_for_all_until, wait_for_one,
+    wait_for_one_boxed, wait_for_one_infinite, wait_for_one_iter, wait_for_one_ref,
+    wait_for_one_until,
+};
+
+#[cfg(windows)]
+mod chunked;
+#[cfg(windows)]
+mod fair_wait_set;
+#[cfg(windows)]
+mod interrupt;
+#[cfg(windows)]
+mod msg;
+#[cfg(windows)]
+mod process;
+#[cfg(windows)]
+mod reusable_wait_set;
+#[cfg(windows)]
+mod wait_set;
 #[cfg(windows)]
 mod win;
 
 #[cfg(windows)]
-pub use win::{max_num_waitables, wait_for_all, wait_for_one, WaitableExt};
+pub use {
+    chunked::wait_for_one_chunked,
+    fair_wait_set::FairWaitSet,
+    interrupt::{clear_interrupt, interrupt_all, InterruptibleResult},
+    msg::{wait_for_one_msg, MsgWaitResult},
+    process::ProcessWaitable,
+    reusable_wait_set::ReusableWaitSet,
+    wait_set::WaitSet,
+    win::{
+        all_signaled, max_num_waitables, signal_and_wait, wait_for_all, wait_for_all_alertable,
+        wait_for_all_boxed, wait_for_all_detailed, wait_for_all_infinite, wait_for_all_or_partial,
+        wait_for_all_typed, wait_for_all_until, wait_for_one, wait_for_one_alertable,
+        wait_for_one_boxed, wait_for_one_infinite, wait_for_one_iter, wait_for_one_profiled,
+        wait_for_one_ref, wait_for_one_typed, wait_for_one_until, wait_for_one_with_buf,
+        ThreadPriority, WaitableExt,
+    },
+};
+
+#[cfg(windows)]
+pub(crate) use win::{
+    close_handle, create_handle, duplicate_handle, namespaced_name, security_attributes,
+    wait_for_handle_with_strategy, Namespace, DEFAULT_CREATE_RETRIES,
+};
+
+#[cfg(all(windows, test))]
+pub(crate) use win::{INJECTED_CREATE_ERRORS, NUM_CLOSED_HANDLES};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_to_timeout_ms() {
+        let max = u32::MAX - 1;
+
+        for (d, ms) in [
+            (Duration::from_secs(0), 0),
+            (Duration::from_nanos(1), 1),
+            (Duration::from_micros(999), 1),
+            (Duration::from_millis(1), 1),
+            (Duration::from_nanos(1_000_001), 2),
+            (Duration::from_millis(1_000_000), 1_000_000),
+            (Duration::from_millis(max as u64), max),
+            (Duration::from_millis(u32::MAX as u64), max),
+            (Duration::from_millis(u32::MAX as u64 + 1), max),
+            (Duration::from_secs(u64::MAX), max),
+            (Duration::MAX, max),
+        ]
+        .iter()
+        {
+            assert_eq!(super::duration_to_timeout_ms(*d), *ms, "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn wait_raw_ms() {
+        use std::cell::Cell;
+
+        /// Records the timeout of the last wait.
+        struct RawWaitable(Cell<u32>);
+
+        impl Waitable for RawWaitable {
+            fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+                self.0.set(ms);
+                Ok(WaitableResult::Timeout)
+            }
+        }
+
+        let w = RawWaitable(Cell::new(0));
+
+        w.wait(Duration::from_nanos(1)).unwrap();
+        assert_eq!(w.0.get(), 1);
+
+        w.wait(Duration::MAX).unwrap();
+        assert_eq!(w.0.get(), INFINITE_MS - 1);
+
+        w.wait_infinite().unwrap();
+        assert_eq!(w.0.get(), INFINITE_MS);
+    }
+
+    #[test]
+    fn into_io() {
+        assert!(WaitableResult::Signaled.into_io().unwrap());
+        assert!(!WaitableResult::Timeout.into_io().unwrap());
+
+        let err = WaitableResult::Abandoned.into_io().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert!(err.to_string().contains("abandoned"));
+
+        let res: Result<WaitableResult, WaitError> = Ok(WaitableResult::Abandoned);
+        assert_eq!(res.into_io().unwrap_err().kind(), io::ErrorKind::Other);
+
+        let res: Result<WaitableResult, WaitError> = Ok(WaitableResult::Signaled);
+        assert!(res.into_io().unwrap());
+
+        let res: Result<WaitableResult, WaitError> = Ok(WaitableResult::Timeout);
+        assert!(!res.into_io().unwrap());
+
+        let res: Result<WaitableResult, WaitError> =
+            Err(WaitError::FailedToWait(io::Error::from_raw_os_error(6)));
+        assert_eq!(res.into_io().err().unwrap().raw_os_error(), Some(6));
+
+        let res: Result<WaitableResult, WaitError> =
+            Err(EventError::FailedToWait(io::Error::from_raw_os_error(6)).into());
+        assert_eq!(res.into_io().err().unwrap().raw_os_error(), Some(6));
+
+        let res: Result<WaitableResult, EventError> = Ok(WaitableResult::Signaled);
+        assert!(res.into_io().unwrap());
+
+        let res: Result<WaitableResult, EventError> = Ok(WaitableResult::Timeout);
+        assert!(!res.into_io().unwrap());
+
+        let res: Result<WaitableResult, EventError> =
+            Err(EventError::FailedToWait(io::Error::from_raw_os_error(6)));
+        assert_eq!(res.into_io().err().unwrap().raw_os_error(), Some(6));
+
+        let res: Result<WaitableResult, EventError> =
+            Err(EventError::InvalidName { nul_position: 0 });
+        assert_eq!(
+            res.into_io().err().unwrap().kind(),
+            io::ErrorKind::InvalidInput
+        );
+
+        let res: Result<WaitableResult, SemaphoreError> = Ok(WaitableResult::Signaled);
+        assert!(res.into_io().unwrap());
+
+        let res: Result<WaitableResult, SemaphoreError> = Ok(WaitableResult::Timeout);
+        assert!(!res.into_io().unwrap());
+
+        let res: Result<WaitableResult, SemaphoreError> = Err(SemaphoreError::FailedToWait(
+            io::Error::from_raw_os_error(6),
+        ));
+        assert_eq!(res.into_io().err().unwrap().raw_os_error(), Some(6));
+
+        let res: Result<WaitableResult, SemaphoreError> =
+            Err(SemaphoreError::InvalidName { nul_position: 0 });
+        assert_eq!(
+            res.into_io().err().unwrap().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn wait_timed() {
+        let e = crate::Event::new_auto(false, None).unwrap();
+
+        let (res, elapsed) = e.wait_timed(Duration::from_millis(10)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+        // OS waits may time out slightly early due to the timer resolution.
+        assert!(elapsed > Duration::from_millis(0));
+
+        e.set().unwrap();
+
+        let (res, elapsed) = e.wait_timed(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+        assert!(elapsed < Duration::from_secs(1_000_000));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn wait_timed_logged() {
+        use {
+            crate::Event,
+            log::{kv::Key, Level, Log, Metadata, Record},
+            std::sync::Mutex,
+        };
+
+        /// (label, result, elapsed_us)
+        static RECORDS: Mutex<Vec<(String, String, u64)>> = Mutex::new(Vec::new());
+
+        struct TestLogger;
+
+        impl Log for TestLogger {
+            fn enabled(&self, metadata: &Metadata) -> bool {
+                metadata.target() == "minievent"
+            }
+
+            fn log(&self, record: &Record) {
+                if !self.enabled(record.metadata()) || record.level() != Level::Debug {
+                    return;
+                }
+
+                let kv = record.key_values();
+                let get = |key| kv.get(Key::from_str(key)).unwrap();
+
+                RECORDS.lock().unwrap().push((
+                    get("label").to_string(),
+                    get("result").to_string(),
+                    get("elapsed_us").to_u64().unwrap(),
+                ));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: TestLogger = TestLogger;
+
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let e = Event::new_auto(true, None).unwrap(); // Signaled.
+
+        let res = e
+            .wait_timed_logged(Duration::from_secs(1_000_000), "signaled_wait")
+            .unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        let res = e
+            .wait_timed_logged(Duration::from_millis(20), "timed_out_wait")
+            .unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let records = RECORDS.lock().unwrap();
+        assert_eq!(records.len(), 2);
+
+        let (label, result, elapsed_us) = &records[0];
+        assert_eq!(label, "signaled_wait");
+        assert_eq!(result, "Signaled");
+        assert!(*elapsed_us < 1_000_000);
+
+        let (label, result, elapsed_us) = &records[1];
+        assert_eq!(label, "timed_out_wait");
+        assert_eq!(result, "Timeout");
+        assert!(*elapsed_us >= 10_000 && *elapsed_us < 1_000_000);
+    }
+}
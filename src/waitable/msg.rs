@@ -0,0 +1,156 @@
+use {
+    super::win::max_num_waitables,
+    crate::{duration_to_timeout_ms, WaitError, WaitableExt},
+    std::{io, time::Duration},
+    winapi::{
+        shared::winerror::WAIT_TIMEOUT,
+        um::{
+            winbase::{WAIT_ABANDONED_0, WAIT_OBJECT_0},
+            winnt::{HANDLE, MAXIMUM_WAIT_OBJECTS},
+            winuser::{MsgWaitForMultipleObjectsEx, MWMO_INPUTAVAILABLE, QS_ALLINPUT},
+        },
+    },
+};
+
+/// Result of waiting on multiple waitables and the thread's message queue via [`wait_for_one_msg`].
+///
+/// [`wait_for_one_msg`]: fn.wait_for_one_msg.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MsgWaitResult {
+    /// One of the waitables was signaled.
+    /// Contains the index of the signaled waitable.
+    OneSignaled(usize),
+    /// One of the waitables was an abandoned mutex - i.e. its owning thread exited without releasing it.
+    /// Contains the index of the abandoned waitable.
+    /// The ownership of the mutex is granted to the calling thread, but the state it protects may be inconsistent.
+    Abandoned(usize),
+    /// Input (e.g. a window message) is available in the thread's message queue.
+    /// The caller should pump the messages and wait again.
+    MessageAvailable,
+    /// The timeout duration elapsed before any waitable was signaled or any input was available.
+    Timeout,
+}
+
+/// Blocks the thread until at least one of the waitables is signaled, the duration `d` expires,
+/// or input (e.g. a window message) is available in the thread's message queue.
+/// See [`MsgWaitForMultipleObjectsEx`](https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-msgwaitformultipleobjectsex) on MSDN.
+///
+/// Meant for GUI threads, which must keep pumping their messages to stay responsive:
+/// on `MessageAvailable` the caller pumps the messages (e.g. via `PeekMessage` / `DispatchMessage`) and waits again.
+/// Waits for any input (`QS_ALLINPUT`), including input which was already in the queue
+/// but not yet removed when the wait began (`MWMO_INPUTAVAILABLE`).
+///
+/// Maximum number of waitables is one less than the value returned by [`max_num_waitables`],
+/// as the message queue takes up one slot.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds `max_num_waitables() - 1`.
+///
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_one_msg(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<MsgWaitResult, WaitError> {
+    let num_waitables = waitables.len();
+    let max = max_num_waitables() - 1;
+
+    if num_waitables > max {
+        return Err(WaitError::TooManyWaitables {
+            given: num_waitables,
+            max,
+        });
+    }
+
+    let mut handles = [0 as HANDLE; MAXIMUM_WAIT_OBJECTS as usize];
+
+    for (idx, waitable) in waitables.iter().enumerate() {
+        handles[idx] = waitable.handle() as HANDLE;
+    }
+
+    let result = unsafe {
+        MsgWaitForMultipleObjectsEx(
+            num_waitables as u32,
+            handles.as_ptr(),
+            duration_to_timeout_ms(d),
+            QS_ALLINPUT,
+            MWMO_INPUTAVAILABLE,
+        )
+    };
+
+    if result < WAIT_OBJECT_0 + num_waitables as u32 {
+        Ok(MsgWaitResult::OneSignaled(result as usize))
+    } else if result == WAIT_OBJECT_0 + num_waitables as u32 {
+        Ok(MsgWaitResult::MessageAvailable)
+    } else if (WAIT_ABANDONED_0..WAIT_ABANDONED_0 + num_waitables as u32).contains(&result) {
+        Ok(MsgWaitResult::Abandoned(
+            (result - WAIT_ABANDONED_0) as usize,
+        ))
+    } else if result == WAIT_TIMEOUT {
+        Ok(MsgWaitResult::Timeout)
+    } else {
+        Err(WaitError::FailedToWait(io::Error::last_os_error()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::Event,
+        std::{mem, ptr},
+        winapi::um::{
+            processthreadsapi::GetCurrentThreadId,
+            winuser::{PeekMessageA, PostThreadMessageA, MSG, PM_NOREMOVE, PM_REMOVE, WM_USER},
+        },
+    };
+
+    #[test]
+    fn message_available() {
+        let e = Event::new_auto(false, None).unwrap(); // Not signaled.
+        let w = [&e as &dyn WaitableExt];
+
+        let mut msg: MSG = unsafe { mem::zeroed() };
+
+        // Creates the thread's message queue.
+        unsafe { PeekMessageA(&mut msg, ptr::null_mut(), 0, 0, PM_NOREMOVE) };
+
+        let res = wait_for_one_msg(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == MsgWaitResult::Timeout);
+
+        e.set().unwrap();
+
+        let res = wait_for_one_msg(&w, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == MsgWaitResult::OneSignaled(0));
+
+        assert_ne!(
+            unsafe { PostThreadMessageA(GetCurrentThreadId(), WM_USER, 0, 0) },
+            0
+        );
+
+        let res = wait_for_one_msg(&w, Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == MsgWaitResult::MessageAvailable);
+
+        // Pumps the message.
+        assert_ne!(
+            unsafe { PeekMessageA(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) },
+            0
+        );
+        assert_eq!(msg.message, WM_USER);
+
+        let res = wait_for_one_msg(&w, Duration::from_millis(1)).unwrap();
+        assert!(res == MsgWaitResult::Timeout);
+
+        let w = vec![&e as &dyn WaitableExt; max_num_waitables()];
+
+        match wait_for_one_msg(&w, Duration::from_millis(1)) {
+            Err(WaitError::TooManyWaitables { given, max }) => {
+                assert_eq!(given, max_num_waitables());
+                assert_eq!(max, max_num_waitables() - 1);
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use {
+    crate::{WaitError, Waitable, WaitableExt, WaitableResult},
+    std::io,
+    std::os::windows::io::{AsRawHandle, RawHandle},
+    winapi::{
+        shared::winerror::WAIT_TIMEOUT,
+        um::{synchapi::WaitForSingleObject, winbase::WAIT_OBJECT_0, winnt::HANDLE},
+    },
+};
+
+/// Waitable process handle wrapper.
+/// See [`Processes and Threads`](https://docs.microsoft.com/en-us/windows/win32/procthread/processes-and-threads) on MSDN.
+///
+/// A process handle becomes signaled when the process exits, so a child process's exit may be waited on
+/// alongside other waitables in a single [`wait_for_one`] call:
+///
+/// ```ignore
+/// let child = Command::new("child.exe").spawn()?;
+/// let process = ProcessWaitable::new(child.as_raw_handle());
+///
+/// match wait_for_one(&[&stop, &process], d)? {
+///     WaitablesResult::OneSignaled(1) => { /* The child exited. */ }
+///     // ...
+/// }
+/// ```
+///
+/// NOTE - does not own the process handle and never closes it - e.g. the handle is owned by the `std::process::Child`
+/// it was obtained from. The caller must keep the handle open for as long as the wrapper is used.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[derive(Clone, Copy, Debug)]
+pub struct ProcessWaitable {
+    handle: HANDLE,
+}
+
+impl ProcessWaitable {
+    /// Wraps the raw process `handle`, which must have `SYNCHRONIZE` access.
+    /// Does not take ownership of the `handle`.
+    pub fn new(handle: RawHandle) -> ProcessWaitable {
+        ProcessWaitable {
+            handle: handle as HANDLE,
+        }
+    }
+
+    fn wait_impl(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        match unsafe { WaitForSingleObject(self.handle, ms) } {
+            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+            _ => Err(WaitError::FailedToWait(io::Error::last_os_error())),
+        }
+    }
+}
+
+unsafe impl Send for ProcessWaitable {}
+unsafe impl Sync for ProcessWaitable {}
+
+impl Waitable for ProcessWaitable {
+    /// Blocks the thread until the process exits or the timeout of `ms` milliseconds expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails - e.g. if the handle was closed.
+    fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+        self.wait_impl(ms)
+    }
+}
+
+impl WaitableExt for ProcessWaitable {
+    /// Returns the raw handle / pointer to the waitable's OS object.
+    fn handle(&self) -> *mut () {
+        self.handle as *mut ()
+    }
+}
+
+impl AsRawHandle for ProcessWaitable {
+    /// Returns the wrapped raw process handle.
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle as RawHandle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{wait_for_one_infinite, Event, WaitablesResult},
+        std::{env, process::Command, thread, time::Duration},
+    };
+
+    /// Run in a child process by `child_exit`.
+    #[test]
+    #[ignore]
+    fn child_exit_child() {
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    #[test]
+    fn child_exit() {
+        let mut child = Command::new(env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "waitable::process::tests::child_exit_child",
+                "--ignored",
+                "--test-threads=1",
+            ])
+            .spawn()
+            .unwrap();
+
+        let process = ProcessWaitable::new(child.as_raw_handle());
+        let e = Event::new_manual(false, None).unwrap(); // Not signaled.
+
+        // Still running.
+        let res = process.wait(Duration::from_secs(0)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        let res = wait_for_one_infinite(&[&e, &process]).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        // Stays signaled.
+        let res = process.wait(Duration::from_secs(0)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        assert!(child.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,157 @@
+use {
+    super::win::{all_result, wait_for_handles_impl},
+    crate::{WaitError, WaitableExt, WaitableResult, WaitablesResult},
+    std::time::Duration,
+    winapi::um::winnt::HANDLE,
+};
+
+/// A set of waitables which may be repeatedly waited on.
+///
+/// Unlike [`wait_for_one`] / [`wait_for_all`], which collect the waitables' OS handles on every call,
+/// the handles are collected once when the waitables are added to the set, and reused by every
+/// [`wait_one`] / [`wait_all`] call until the set's membership changes.
+///
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`wait_one`]: #method.wait_one
+/// [`wait_all`]: #method.wait_all
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+#[derive(Default)]
+pub struct ReusableWaitSet<'a> {
+    waitables: Vec<&'a dyn WaitableExt>,
+    handles: Vec<HANDLE>,
+}
+
+impl<'a> ReusableWaitSet<'a> {
+    /// Creates a new empty wait set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty wait set with the internal handle buffer pre-sized
+    /// to hold `capacity` waitables without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            waitables: Vec::with_capacity(capacity),
+            handles: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Adds the `waitable` to the end of the set.
+    pub fn push(&mut self, waitable: &'a dyn WaitableExt) {
+        self.waitables.push(waitable);
+        self.handles.push(waitable.handle() as HANDLE);
+    }
+
+    /// Removes and returns the waitable at `index`, shifting all waitables after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> &'a dyn WaitableExt {
+        self.handles.remove(index);
+        self.waitables.remove(index)
+    }
+
+    /// Removes all waitables from the set.
+    pub fn clear(&mut self) {
+        self.waitables.clear();
+        self.handles.clear();
+    }
+
+    /// Returns the waitable at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&'a dyn WaitableExt> {
+        self.waitables.get(index).copied()
+    }
+
+    /// Returns the number of waitables in the set.
+    pub fn len(&self) -> usize {
+        self.waitables.len()
+    }
+
+    /// Returns `true` if the set contains no waitables.
+    pub fn is_empty(&self) -> bool {
+        self.waitables.is_empty()
+    }
+
+    /// Blocks the thread until at least one of the waitables in the set is signaled or the duration `d` expires.
+    /// Returned indices correspond to the order the waitables were added to the set in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    /// Returns [`WaitError::TooManyWaitables`] if the number of waitables in the set exceeds the value returned by [`max_num_waitables`].
+    ///
+    /// [`max_num_waitables`]: fn.max_num_waitables.html
+    /// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+    pub fn wait_one(&self, d: Duration) -> Result<WaitablesResult, WaitError> {
+        wait_for_handles_impl(&self.handles, d, false)
+    }
+
+    /// Blocks the thread until all waitables in the set are signaled or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    /// Returns [`WaitError::TooManyWaitables`] if the number of waitables in the set exceeds the value returned by [`max_num_waitables`].
+    ///
+    /// [`max_num_waitables`]: fn.max_num_waitables.html
+    /// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+    pub fn wait_all(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        wait_for_handles_impl(&self.handles, d, true).map(all_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Event};
+
+    #[test]
+    fn membership_changes() {
+        let e0 = Event::new_manual(false, None).unwrap(); // Not signaled.
+        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+        let e2 = Event::new_manual(true, None).unwrap(); // Signaled.
+
+        let mut s = ReusableWaitSet::with_capacity(3);
+        assert!(s.is_empty());
+
+        s.push(&e0);
+        s.push(&e1);
+        assert_eq!(s.len(), 2);
+
+        let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        let res = s.wait_all(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        // Only signaled events remain.
+
+        s.remove(0);
+        s.push(&e2);
+        assert_eq!(s.len(), 2);
+
+        let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(0));
+
+        let res = s.wait_all(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        e1.reset().unwrap(); // Only `e2` is signaled.
+
+        let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        // Empty set.
+
+        s.clear();
+        assert!(s.is_empty());
+
+        s.push(&e0);
+
+        let res = s.wait_one(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+    }
+}
@@ -0,0 +1,454 @@
+use {
+    super::timeout::{Deadline, RealTimeSource, TimeoutSource},
+    crate::{WaitError, Waitable, WaitableResult, WaitablesResult},
+    std::{io, thread, time::Duration},
+};
+
+/// Blocks the thread until all waitables are signaled or the duration `d` expires.
+///
+/// Portable fallback for [`wait_for_all`], which waits on each waitable in turn
+/// with the remaining part of the duration `d`, and does not limit the number of waitables.
+///
+/// NOTE - unlike [`wait_for_all`], the wait is not atomic: if a later waitable times out,
+/// auto events / semaphores earlier in `waitables` have already been consumed / decremented.
+///
+/// # Errors
+///
+/// Returns an error if waiting on any of the waitables fails, or if `waitables` is empty.
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub fn wait_for_all_sequential(
+    waitables: &[&dyn Waitable],
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    match wait_for_waitables_sequential(waitables, d, true)? {
+        WaitablesResult::AllSignaled => Ok(WaitableResult::Signaled),
+        WaitablesResult::Abandoned(_) => Ok(WaitableResult::Abandoned),
+        _ => Ok(WaitableResult::Timeout),
+    }
+}
+
+/// Blocks the thread until at least one of the waitables are signaled or the duration `d` expires.
+///
+/// Portable fallback for [`wait_for_one`], which repeatedly polls each waitable in turn
+/// with an exponential backoff until the duration `d` expires,
+/// and does not limit the number of waitables.
+/// Like [`wait_for_one`], reports the signaled waitable with the lowest index.
+///
+/// # Errors
+///
+/// Returns an error if waiting on any of the waitables fails, or if `waitables` is empty.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_sequential(
+    waitables: &[&dyn Waitable],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_sequential(waitables, d, false)
+}
+
+pub(crate) fn wait_for_waitables_sequential<W: Waitable + ?Sized>(
+    waitables: &[&W],
+    d: Duration,
+    wait_for_all: bool,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_sequential_impl(waitables, d, wait_for_all, &RealTimeSource)
+}
+
+fn wait_for_waitables_sequential_impl<W: Waitable + ?Sized>(
+    waitables: &[&W],
+    d: Duration,
+    wait_for_all: bool,
+    source: &dyn TimeoutSource,
+) -> Result<WaitablesResult, WaitError> {
+    if waitables.is_empty() {
+        return Err(WaitError::FailedToWait(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no waitables given",
+        )));
+    }
+
+    let deadline = Deadline::new(d, source);
+    let remaining = || deadline.remaining(source);
+
+    if wait_for_all {
+        // Like the native wait, reports the (first) abandoned mutex only once all waitables are signaled.
+        let mut abandoned = None;
+
+        for (idx, waitable) in waitables.iter().enumerate() {
+            match waitable.wait(remaining())? {
+                WaitableResult::Timeout => return Ok(WaitablesResult::Timeout),
+                WaitableResult::Abandoned => {
+                    abandoned.get_or_insert(idx);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(abandoned.map_or(WaitablesResult::AllSignaled, WaitablesResult::Abandoned))
+    } else {
+        const MIN_BACKOFF: Duration = Duration::from_micros(100);
+        const MAX_BACKOFF: Duration = Duration::from_millis(10);
+
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            for (idx, waitable) in waitables.iter().enumerate() {
+                match waitable.wait(Duration::from_secs(0))? {
+                    WaitableResult::Signaled => return Ok(WaitablesResult::OneSignaled(idx)),
+                    WaitableResult::Abandoned => return Ok(WaitablesResult::Abandoned(idx)),
+                    _ => {}
+                }
+            }
+
+            let remaining = remaining();
+
+            if remaining == Duration::from_secs(0) {
+                return Ok(WaitablesResult::Timeout);
+            }
+
+            thread::sleep(backoff.min(remaining));
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+/// Blocks the thread until all waitables are signaled or the duration `d` expires.
+///
+/// Implemented by [`wait_for_all_sequential`] on this platform.
+///
+/// # Errors
+///
+/// Returns an error if waiting on any of the waitables fails, or if `waitables` is empty.
+///
+/// [`wait_for_all_sequential`]: fn.wait_for_all_sequential.html
+#[cfg(not(windows))]
+pub fn wait_for_all(waitables: &[&dyn Waitable], d: Duration) -> Result<WaitableResult, WaitError> {
+    wait_for_all_sequential(waitables, d)
+}
+
+/// Same as [`wait_for_all`], but blocks until the `deadline` passes rather than for a duration.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+#[cfg(not(windows))]
+pub fn wait_for_all_until(
+    waitables: &[&dyn Waitable],
+    deadline: std::time::Instant,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_all(
+        waitables,
+        deadline.saturating_duration_since(std::time::Instant::now()),
+    )
+}
+
+/// Blocks the thread until at least one of the waitables are signaled or the duration `d` expires.
+///
+/// Implemented by [`wait_for_one_sequential`] on this platform.
+///
+/// # Errors
+///
+/// Returns an error if waiting on any of the waitables fails, or if `waitables` is empty.
+///
+/// [`wait_for_one_sequential`]: fn.wait_for_one_sequential.html
+#[cfg(not(windows))]
+pub fn wait_for_one(
+    waitables: &[&dyn Waitable],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_one_sequential(waitables, d)
+}
+
+/// Same as [`wait_for_one`], but blocks until the `deadline` passes rather than for a duration.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[cfg(not(windows))]
+pub fn wait_for_one_until(
+    waitables: &[&dyn Waitable],
+    deadline: std::time::Instant,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_one(
+        waitables,
+        deadline.saturating_duration_since(std::time::Instant::now()),
+    )
+}
+
+/// Blocks the thread until all waitables are signaled, without a timeout.
+///
+/// Same as [`wait_for_all`], but waits forever - never returns `Timeout`.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+#[cfg(not(windows))]
+pub fn wait_for_all_infinite(waitables: &[&dyn Waitable]) -> Result<WaitableResult, WaitError> {
+    // The deadline overflows - each waitable is waited on forever.
+    wait_for_all(waitables, Duration::MAX)
+}
+
+/// Blocks the thread until at least one of the waitables is signaled, without a timeout.
+///
+/// Same as [`wait_for_one`], but waits forever - never returns `Timeout`.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[cfg(not(windows))]
+pub fn wait_for_one_infinite(waitables: &[&dyn Waitable]) -> Result<WaitablesResult, WaitError> {
+    // The deadline overflows - the waitables are polled forever.
+    wait_for_one(waitables, Duration::MAX)
+}
+
+/// Same as [`wait_for_all`], but takes a slice of owned, boxed waitables,
+/// e.g. a dynamic collection of waitables of different types.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+#[cfg(not(windows))]
+pub fn wait_for_all_boxed(
+    waitables: &[Box<dyn Waitable>],
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_all(&unbox(waitables), d)
+}
+
+/// Same as [`wait_for_one`], but takes a slice of owned, boxed waitables,
+/// e.g. a dynamic collection of waitables of different types.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[cfg(not(windows))]
+pub fn wait_for_one_boxed(
+    waitables: &[Box<dyn Waitable>],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_one(&unbox(waitables), d)
+}
+
+/// Same as [`wait_for_one`], but takes any iterable of references to waitables of the same type,
+/// e.g. `&Vec<Event>`, without building an intermediate slice of trait objects.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[cfg(not(windows))]
+pub fn wait_for_one_iter<'w, I, W>(waitables: I, d: Duration) -> Result<WaitablesResult, WaitError>
+where
+    I: IntoIterator<Item = &'w W>,
+    W: Waitable + ?Sized + 'w,
+{
+    let waitables: Vec<_> = waitables.into_iter().collect();
+
+    wait_for_waitables_sequential(&waitables, d, false)
+}
+
+/// Same as [`wait_for_one`], but returns both the index and the reference to the signaled waitable,
+/// or `None` if the duration `d` expired before any waitable was signaled.
+///
+/// An abandoned mutex is returned the same as a signaled waitable.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+#[cfg(not(windows))]
+pub fn wait_for_one_ref<'a>(
+    waitables: &'a [&'a dyn Waitable],
+    d: Duration,
+) -> Result<Option<(usize, &'a dyn Waitable)>, WaitError> {
+    match wait_for_one(waitables, d)? {
+        WaitablesResult::OneSignaled(idx) | WaitablesResult::Abandoned(idx) => {
+            Ok(Some((idx, waitables[idx])))
+        }
+        // Not an alertable wait.
+        _ => Ok(None),
+    }
+}
+
+#[cfg(not(windows))]
+fn unbox(waitables: &[Box<dyn Waitable>]) -> Vec<&dyn Waitable> {
+    waitables.iter().map(|w| &**w).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::waitable::timeout::ManualTimeSource,
+        std::cell::{Cell, RefCell},
+    };
+
+    /// Signaled after `wait` was called for the total of `signaled_after`;
+    /// advances the time source by the duration of each wait instead of sleeping.
+    struct MockWaitable<'s> {
+        source: &'s ManualTimeSource,
+        signaled_after: Duration,
+        waited: Cell<Duration>,
+        /// Durations of all `wait` calls.
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    impl<'s> MockWaitable<'s> {
+        fn new(source: &'s ManualTimeSource, signaled_after: Duration) -> Self {
+            Self {
+                source,
+                signaled_after,
+                waited: Cell::new(Duration::from_secs(0)),
+                waits: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl<'s> Waitable for MockWaitable<'s> {
+        fn wait_raw_ms(&self, ms: u32) -> Result<WaitableResult, WaitError> {
+            self.wait(Duration::from_millis(ms.into()))
+        }
+
+        fn wait(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+            self.waits.borrow_mut().push(d);
+
+            let left = self.signaled_after.saturating_sub(self.waited.get());
+            let waited = left.min(d);
+
+            self.waited.set(self.waited.get() + waited);
+            self.source.advance(waited);
+
+            if waited == left {
+                Ok(WaitableResult::Signaled)
+            } else {
+                Ok(WaitableResult::Timeout)
+            }
+        }
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn all_wait_budget() {
+        let source = ManualTimeSource::new();
+
+        let w0 = MockWaitable::new(&source, ms(30));
+        let w1 = MockWaitable::new(&source, ms(50));
+        let w2 = MockWaitable::new(&source, ms(10));
+        let w: [&dyn Waitable; 3] = [&w0, &w1, &w2];
+
+        // Each waitable gets the remaining part of the budget.
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(100), true, &source).unwrap();
+        assert!(res == WaitablesResult::AllSignaled);
+
+        assert_eq!(*w0.waits.borrow(), vec![ms(100)]);
+        assert_eq!(*w1.waits.borrow(), vec![ms(70)]);
+        assert_eq!(*w2.waits.borrow(), vec![ms(20)]);
+
+        // The budget runs out on the second waitable.
+
+        let w0 = MockWaitable::new(&source, ms(30));
+        let w1 = MockWaitable::new(&source, ms(100));
+        let w2 = MockWaitable::new(&source, ms(10));
+        let w: [&dyn Waitable; 3] = [&w0, &w1, &w2];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(100), true, &source).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        assert_eq!(*w0.waits.borrow(), vec![ms(100)]);
+        assert_eq!(*w1.waits.borrow(), vec![ms(70)]);
+        assert!(w2.waits.borrow().is_empty());
+
+        // The deadline overflows - every waitable gets the full duration.
+
+        let w0 = MockWaitable::new(&source, ms(30));
+        let w1 = MockWaitable::new(&source, ms(50));
+        let w: [&dyn Waitable; 2] = [&w0, &w1];
+
+        let res = wait_for_waitables_sequential_impl(&w, Duration::MAX, true, &source).unwrap();
+        assert!(res == WaitablesResult::AllSignaled);
+
+        assert_eq!(*w0.waits.borrow(), vec![Duration::MAX]);
+        assert_eq!(*w1.waits.borrow(), vec![Duration::MAX]);
+    }
+
+    #[test]
+    fn one_wait_budget() {
+        let source = ManualTimeSource::new();
+
+        // Zero duration - polls each waitable once.
+
+        let w0 = MockWaitable::new(&source, ms(10));
+        let w1 = MockWaitable::new(&source, ms(0));
+        let w: [&dyn Waitable; 2] = [&w0, &w1];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(0), false, &source).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        assert_eq!(*w0.waits.borrow(), vec![ms(0)]);
+        assert_eq!(*w1.waits.borrow(), vec![ms(0)]);
+
+        // None signaled - times out after a single poll, without sleeping.
+
+        let w0 = MockWaitable::new(&source, ms(10));
+        let w: [&dyn Waitable; 1] = [&w0];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(0), false, &source).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        assert_eq!(*w0.waits.borrow(), vec![ms(0)]);
+    }
+
+    /// Abandoned mutex - every wait reports it as abandoned.
+    struct AbandonedWaitable;
+
+    impl Waitable for AbandonedWaitable {
+        fn wait_raw_ms(&self, _: u32) -> Result<WaitableResult, WaitError> {
+            Ok(WaitableResult::Abandoned)
+        }
+    }
+
+    #[test]
+    fn abandoned() {
+        let source = ManualTimeSource::new();
+
+        let signaled = MockWaitable::new(&source, ms(0));
+        let never = MockWaitable::new(&source, ms(1_000_000));
+        let w: [&dyn Waitable; 3] = [&never, &AbandonedWaitable, &signaled];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(10), false, &source).unwrap();
+        assert!(res == WaitablesResult::Abandoned(1));
+
+        // Reported once all waitables are signaled - the first abandoned one.
+        let w: [&dyn Waitable; 3] = [&signaled, &AbandonedWaitable, &AbandonedWaitable];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(10), true, &source).unwrap();
+        assert!(res == WaitablesResult::Abandoned(1));
+
+        let res = wait_for_all_sequential(&w, ms(10)).unwrap();
+        assert!(res == WaitableResult::Abandoned);
+
+        let w: [&dyn Waitable; 2] = [&AbandonedWaitable, &never];
+
+        let res = wait_for_waitables_sequential_impl(&w, ms(10), true, &source).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+/// Source of the current time consulted by the waits which split their timeout duration
+/// over multiple OS waits, so that their deadline arithmetic may be tested without sleeping.
+pub(crate) trait TimeoutSource {
+    fn now(&self) -> Instant;
+}
+
+/// The monotonic system clock.
+pub(crate) struct RealTimeSource;
+
+impl TimeoutSource for RealTimeSource {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Deadline of a wait for the duration `d`, started at the time of creation.
+pub(crate) struct Deadline {
+    /// `None` if the deadline overflows - i.e. the wait never times out.
+    deadline: Option<Instant>,
+    d: Duration,
+}
+
+impl Deadline {
+    pub(crate) fn new(d: Duration, source: &dyn TimeoutSource) -> Self {
+        Self {
+            deadline: source.now().checked_add(d),
+            d,
+        }
+    }
+
+    /// Returns the remaining part of the duration; zero once the deadline has passed.
+    /// Always returns the full duration if the deadline overflows.
+    pub(crate) fn remaining(&self, source: &dyn TimeoutSource) -> Duration {
+        match self.deadline {
+            Some(deadline) => deadline.saturating_duration_since(source.now()),
+            None => self.d,
+        }
+    }
+}
+
+/// Time source which only advances when told to.
+#[cfg(test)]
+pub(crate) struct ManualTimeSource {
+    now: std::cell::Cell<Instant>,
+}
+
+#[cfg(test)]
+impl ManualTimeSource {
+    pub(crate) fn new() -> Self {
+        Self {
+            now: std::cell::Cell::new(Instant::now()),
+        }
+    }
+
+    pub(crate) fn advance(&self, d: Duration) {
+        self.now.set(self.now.get() + d);
+    }
+}
+
+#[cfg(test)]
+impl TimeoutSource for ManualTimeSource {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline() {
+        let source = ManualTimeSource::new();
+
+        let deadline = Deadline::new(Duration::from_millis(100), &source);
+        assert_eq!(deadline.remaining(&source), Duration::from_millis(100));
+
+        source.advance(Duration::from_millis(30));
+        assert_eq!(deadline.remaining(&source), Duration::from_millis(70));
+
+        // Saturates.
+        source.advance(Duration::from_millis(100));
+        assert_eq!(deadline.remaining(&source), Duration::from_secs(0));
+
+        // Overflows - never expires.
+        let deadline = Deadline::new(Duration::MAX, &source);
+        source.advance(Duration::from_secs(1_000_000));
+        assert_eq!(deadline.remaining(&source), Duration::MAX);
+    }
+}
@@ -0,0 +1,112 @@
+use {
+    crate::{wait_for_all, wait_for_one, WaitError, WaitableResult, WaitablesResult},
+    std::time::Duration,
+};
+
+#[cfg(windows)]
+use crate::WaitableExt as Element;
+
+#[cfg(not(windows))]
+use crate::Waitable as Element;
+
+/// A tuple of references to (possibly different types of) waitables,
+/// e.g. `(&Event, &Semaphore)`, which may be passed to [`wait_for_one_of`] / [`wait_for_all_of`].
+///
+/// Implemented for tuples of 2 to 8 elements. Sealed - cannot be implemented outside of the crate.
+///
+/// [`wait_for_one_of`]: fn.wait_for_one_of.html
+/// [`wait_for_all_of`]: fn.wait_for_all_of.html
+pub trait WaitableTuple: private::Sealed {}
+
+mod private {
+    use super::Element;
+
+    pub trait Sealed {
+        /// Calls `f` with the slice of the tuple's waitables, in order.
+        fn with_waitables<R>(&self, f: impl FnOnce(&[&dyn Element]) -> R) -> R;
+    }
+}
+
+macro_rules! impl_waitable_tuple {
+    ($($ty:ident . $idx:tt),+) => {
+        impl<'a, $($ty: Element),+> private::Sealed for ($(&'a $ty,)+) {
+            fn with_waitables<R>(&self, f: impl FnOnce(&[&dyn Element]) -> R) -> R {
+                f(&[$(self.$idx as &dyn Element),+])
+            }
+        }
+
+        impl<'a, $($ty: Element),+> WaitableTuple for ($(&'a $ty,)+) {}
+    };
+}
+
+impl_waitable_tuple!(A.0, B.1);
+impl_waitable_tuple!(A.0, B.1, C.2);
+impl_waitable_tuple!(A.0, B.1, C.2, D.3);
+impl_waitable_tuple!(A.0, B.1, C.2, D.3, E.4);
+impl_waitable_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+impl_waitable_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+impl_waitable_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+
+/// Blocks the thread until at least one of the waitables in the tuple is signaled or the duration `d` expires.
+///
+/// Same as [`wait_for_one`], but takes a tuple of references to waitables,
+/// e.g. `wait_for_one_of((&event, &semaphore), d)`, instead of a slice of trait objects.
+/// The index in the returned result is the index of the waitable in the tuple.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_of<T: WaitableTuple>(
+    waitables: T,
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    waitables.with_waitables(|waitables| wait_for_one(waitables, d))
+}
+
+/// Blocks the thread until all waitables in the tuple are signaled or the duration `d` expires.
+///
+/// Same as [`wait_for_all`], but takes a tuple of references to waitables,
+/// e.g. `wait_for_all_of((&event, &semaphore), d)`, instead of a slice of trait objects.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub fn wait_for_all_of<T: WaitableTuple>(
+    waitables: T,
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    waitables.with_waitables(|waitables| wait_for_all(waitables, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Event, Semaphore},
+    };
+
+    #[test]
+    fn tuples() {
+        let e = Event::new_manual(false, None).unwrap();
+        let e2 = Event::new_manual(true, None).unwrap();
+        let s = Semaphore::new(0, 1, None).unwrap();
+
+        let res = wait_for_one_of((&e, &s), Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::Timeout);
+
+        s.increment(1).unwrap();
+
+        let res = wait_for_one_of((&e, &s), Duration::from_millis(1)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        e.set().unwrap();
+        s.increment(1).unwrap();
+
+        let res = wait_for_all_of((&e, &s, &e2), Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+    }
+}
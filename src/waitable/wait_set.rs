@@ -0,0 +1,118 @@
+use {
+    super::win::{all_result, wait_for_handles_unchecked},
+    crate::{WaitError, WaitableExt, WaitableResult, WaitablesResult},
+    std::time::Duration,
+    winapi::um::winnt::{HANDLE, MAXIMUM_WAIT_OBJECTS},
+};
+
+/// A fixed set of `N` waitables, checked at compile time to be a valid number of waitables to wait on.
+///
+/// Unlike [`wait_for_one`] / [`wait_for_all`] and [`ReusableWaitSet`], the number of waitables
+/// is known to be valid at compile time, so waiting on the set does not allocate
+/// and skips the runtime check against [`max_num_waitables`].
+///
+/// `N` must be in `1..=max_num_waitables()` - using a `WaitSet` with any other `N` fails to compile:
+///
+/// ```compile_fail
+/// use minievent::{Event, WaitSet, WaitableExt};
+///
+/// let e = Event::new_auto(false, None).unwrap();
+/// let _ = WaitSet::new([&e as &dyn WaitableExt; 65]);
+/// ```
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`ReusableWaitSet`]: struct.ReusableWaitSet.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+pub struct WaitSet<'a, const N: usize> {
+    waitables: [&'a dyn WaitableExt; N],
+    handles: [HANDLE; N],
+}
+
+impl<'a, const N: usize> WaitSet<'a, N> {
+    /// Evaluated (and fails to compile if `N` is invalid) when the set is created.
+    const VALID_NUM_WAITABLES: () = assert!(
+        N > 0 && N <= MAXIMUM_WAIT_OBJECTS as usize,
+        "invalid number of waitables in a `WaitSet`"
+    );
+
+    /// Creates a new wait set over the `waitables`.
+    pub fn new(waitables: [&'a dyn WaitableExt; N]) -> Self {
+        let () = Self::VALID_NUM_WAITABLES;
+
+        let mut handles = [0 as HANDLE; N];
+
+        for (handle, waitable) in handles.iter_mut().zip(waitables.iter()) {
+            *handle = waitable.handle() as HANDLE;
+        }
+
+        Self { waitables, handles }
+    }
+
+    /// Returns the waitable at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&'a dyn WaitableExt> {
+        self.waitables.get(index).copied()
+    }
+
+    /// Blocks the thread until at least one of the waitables in the set is signaled or the duration `d` expires.
+    /// Returned indices correspond to the order of the waitables passed to [`new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`new`]: #method.new
+    pub fn wait_one(&self, d: Duration) -> Result<WaitablesResult, WaitError> {
+        wait_for_handles_unchecked(&self.handles, d, false)
+    }
+
+    /// Blocks the thread until all waitables in the set are signaled or the duration `d` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    pub fn wait_all(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        wait_for_handles_unchecked(&self.handles, d, true).map(all_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Event};
+
+    #[test]
+    fn fixed_set() {
+        let e0 = Event::new_manual(false, None).unwrap(); // Not signaled.
+        let e1 = Event::new_manual(true, None).unwrap(); // Signaled.
+
+        let s = WaitSet::new([&e0 as &dyn WaitableExt, &e1]);
+
+        let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(1));
+
+        let res = s.wait_all(Duration::from_millis(1)).unwrap();
+        assert!(res == WaitableResult::Timeout);
+
+        e0.set().unwrap();
+
+        let res = s.wait_all(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitableResult::Signaled);
+
+        // Maximum size (the OS does not allow duplicate handles).
+
+        let events: Vec<_> = (0..MAXIMUM_WAIT_OBJECTS)
+            .map(|idx| Event::new_manual(idx == MAXIMUM_WAIT_OBJECTS - 1, None).unwrap())
+            .collect();
+
+        let mut w = [&e0 as &dyn WaitableExt; MAXIMUM_WAIT_OBJECTS as usize];
+
+        for (w, e) in w.iter_mut().zip(events.iter()) {
+            *w = e;
+        }
+
+        let s = WaitSet::new(w);
+
+        let res = s.wait_one(Duration::from_secs(1_000_000)).unwrap();
+        assert!(res == WaitablesResult::OneSignaled(MAXIMUM_WAIT_OBJECTS as usize - 1));
+    }
+}
@@ -1,20 +1,226 @@
 use {
-    crate::{WaitableResult, WaitablesResult},
-    std::time::Duration,
+    super::{
+        interrupt::{wait_interruptible, InterruptibleResult},
+        timeout::{Deadline, RealTimeSource},
+    },
+    crate::{
+        duration_to_timeout_ms, PartialResult, WaitError, WaitStrategy, Waitable, WaitableResult,
+        WaitablesResult,
+    },
+    std::{
+        io,
+        mem::MaybeUninit,
+        slice, thread,
+        time::{Duration, Instant},
+    },
     winapi::{
-        shared::winerror::WAIT_TIMEOUT,
+        shared::{
+            minwindef::{FALSE, TRUE},
+            winerror::{
+                ERROR_ALREADY_EXISTS, ERROR_COMMITMENT_LIMIT, ERROR_INVALID_HANDLE,
+                ERROR_NOT_ENOUGH_MEMORY, ERROR_NO_SYSTEM_RESOURCES, ERROR_OUTOFMEMORY,
+                WAIT_TIMEOUT,
+            },
+        },
         um::{
-            synchapi::WaitForMultipleObjectsEx,
-            winbase::WAIT_OBJECT_0,
-            winnt::{HANDLE, MAXIMUM_WAIT_OBJECTS},
+            handleapi::{CloseHandle, DuplicateHandle, INVALID_HANDLE_VALUE},
+            minwinbase::SECURITY_ATTRIBUTES,
+            processthreadsapi::{
+                GetCurrentProcess, GetCurrentThread, GetThreadPriority, SetThreadPriority,
+            },
+            synchapi::{
+                SignalObjectAndWait, WaitForMultipleObjectsEx, WaitForSingleObject,
+                WaitForSingleObjectEx,
+            },
+            winbase::{
+                INFINITE, THREAD_PRIORITY_ABOVE_NORMAL, THREAD_PRIORITY_BELOW_NORMAL,
+                THREAD_PRIORITY_ERROR_RETURN, THREAD_PRIORITY_HIGHEST, THREAD_PRIORITY_LOWEST,
+                THREAD_PRIORITY_NORMAL, THREAD_PRIORITY_TIME_CRITICAL, WAIT_ABANDONED_0,
+                WAIT_IO_COMPLETION, WAIT_OBJECT_0,
+            },
+            winnt::{DUPLICATE_SAME_ACCESS, HANDLE, MAXIMUM_WAIT_OBJECTS},
         },
     },
 };
 
 /// Platform-specific waitable object extension trait.
-pub trait WaitableExt {
+pub trait WaitableExt: Waitable {
     /// Returns the raw handle / pointer to the waitable's OS object.
+    ///
+    /// NOTE - the crate's waitables also implement the standard `std::os::windows::io::AsRawHandle`,
+    /// which is preferred when passing the handle to other crates.
     fn handle(&self) -> *mut ();
+
+    /// Blocks the thread until the waitable is signaled or the duration `d` expires,
+    /// with the calling thread's priority raised to `boost` for the duration of the wait.
+    /// See [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) on MSDN.
+    ///
+    /// The thread's previous priority is restored when the wait returns, even if it panics.
+    /// Does not lower the priority of a thread which already runs at `boost` priority or above.
+    ///
+    /// The boost makes the OS scheduler prefer the thread over lower priority threads
+    /// once it is woken up, reducing the wakeup latency, but may starve lower priority threads
+    /// if the thread does a lot of work before it returns to the wait.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails, or if the thread's priority could not be changed.
+    fn wait_priority_boost(
+        &self,
+        d: Duration,
+        boost: ThreadPriority,
+    ) -> Result<WaitableResult, WaitError> {
+        let _guard = PriorityGuard::new(boost).map_err(WaitError::FailedToWait)?;
+        self.wait(d)
+    }
+
+    /// Blocks the thread until the waitable is signaled or the duration `d` expires,
+    /// running any APCs queued to the thread in the meantime.
+    /// See [`WaitForSingleObjectEx`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitforsingleobjectex) on MSDN.
+    ///
+    /// Unlike [`wait`], the wait is alertable, but re-enters the wait with the remaining part
+    /// of the duration `d` after the APCs were run, so never returns early because of them.
+    ///
+    /// NOTE - waits on the waitable's OS object directly, like [`wait_for_one`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`wait`]: trait.Waitable.html#method.wait
+    /// [`wait_for_one`]: fn.wait_for_one.html
+    fn wait_servicing_apcs(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        let handle = self.handle() as HANDLE;
+        let deadline = Deadline::new(d, &RealTimeSource);
+
+        loop {
+            let ms = duration_to_timeout_ms(deadline.remaining(&RealTimeSource));
+
+            match unsafe { WaitForSingleObjectEx(handle, ms, TRUE) } {
+                WAIT_OBJECT_0 => return Ok(WaitableResult::Signaled),
+                WAIT_ABANDONED_0 => return Ok(WaitableResult::Abandoned),
+                WAIT_TIMEOUT => return Ok(WaitableResult::Timeout),
+                WAIT_IO_COMPLETION => continue,
+                _ => return Err(WaitError::FailedToWait(io::Error::last_os_error())),
+            }
+        }
+    }
+
+    /// Blocks the thread until the waitable is signaled or the duration `d` expires,
+    /// or an APC or I/O completion routine is queued to the thread and run.
+    /// See [`WaitForSingleObjectEx`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitforsingleobjectex) on MSDN.
+    ///
+    /// Unlike [`wait_servicing_apcs`], returns `IoCompletion` as soon as the APCs were run,
+    /// so another thread may cancel the wait via [`QueueUserAPC`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-queueuserapc).
+    ///
+    /// NOTE - waits on the waitable's OS object directly, like [`wait_for_one`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`wait_servicing_apcs`]: #method.wait_servicing_apcs
+    /// [`wait_for_one`]: fn.wait_for_one.html
+    fn wait_alertable(&self, d: Duration) -> Result<WaitableResult, WaitError> {
+        let ms = duration_to_timeout_ms(d);
+
+        match unsafe { WaitForSingleObjectEx(self.handle() as HANDLE, ms, TRUE) } {
+            WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+            WAIT_ABANDONED_0 => Ok(WaitableResult::Abandoned),
+            WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+            WAIT_IO_COMPLETION => Ok(WaitableResult::IoCompletion),
+            _ => Err(WaitError::FailedToWait(io::Error::last_os_error())),
+        }
+    }
+
+    /// Blocks the thread until the waitable is signaled, the duration `d` expires,
+    /// or [`interrupt_all`] is called.
+    ///
+    /// Waits on the waitable together with the process-wide interrupt event, like [`wait_for_one`] -
+    /// if the interrupt is set, returns `Interrupted` without consuming the waitable's signal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS function fails.
+    ///
+    /// [`interrupt_all`]: fn.interrupt_all.html
+    /// [`wait_for_one`]: fn.wait_for_one.html
+    fn wait_interruptible(&self, d: Duration) -> Result<InterruptibleResult, WaitError> {
+        wait_interruptible(self.handle() as HANDLE, d)
+    }
+}
+
+/// Thread priority levels.
+/// See [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) on MSDN.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ThreadPriority {
+    /// `THREAD_PRIORITY_LOWEST`.
+    Lowest,
+    /// `THREAD_PRIORITY_BELOW_NORMAL`.
+    BelowNormal,
+    /// `THREAD_PRIORITY_NORMAL`.
+    Normal,
+    /// `THREAD_PRIORITY_ABOVE_NORMAL`.
+    AboveNormal,
+    /// `THREAD_PRIORITY_HIGHEST`.
+    Highest,
+    /// `THREAD_PRIORITY_TIME_CRITICAL`.
+    TimeCritical,
+}
+
+impl ThreadPriority {
+    fn to_raw(self) -> i32 {
+        use ThreadPriority::*;
+
+        (match self {
+            Lowest => THREAD_PRIORITY_LOWEST,
+            BelowNormal => THREAD_PRIORITY_BELOW_NORMAL,
+            Normal => THREAD_PRIORITY_NORMAL,
+            AboveNormal => THREAD_PRIORITY_ABOVE_NORMAL,
+            Highest => THREAD_PRIORITY_HIGHEST,
+            TimeCritical => THREAD_PRIORITY_TIME_CRITICAL,
+        }) as i32
+    }
+}
+
+/// Raises the current thread's priority, restoring the previous priority when dropped.
+struct PriorityGuard {
+    /// `None` if the priority was not changed.
+    prev: Option<i32>,
+}
+
+impl PriorityGuard {
+    fn new(boost: ThreadPriority) -> io::Result<Self> {
+        let thread = unsafe { GetCurrentThread() };
+
+        let prev = unsafe { GetThreadPriority(thread) };
+
+        if prev == THREAD_PRIORITY_ERROR_RETURN as i32 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let boost = boost.to_raw();
+
+        if prev >= boost {
+            return Ok(Self { prev: None });
+        }
+
+        if unsafe { SetThreadPriority(thread, boost) } == FALSE {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self { prev: Some(prev) })
+        }
+    }
+}
+
+impl Drop for PriorityGuard {
+    fn drop(&mut self) {
+        if let Some(prev) = self.prev {
+            unsafe {
+                SetThreadPriority(GetCurrentThread(), prev);
+            }
+        }
+    }
 }
 
 /// Returns the platfrom-specific maximum number of waitables
@@ -31,18 +237,130 @@ pub fn max_num_waitables() -> usize {
 /// Blocks the thread until all waitables are signaled or the duration `d` expires.
 /// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
 ///
+/// See [`wait_for_all_or_partial`] to find out which waitables were signaled on timeout.
+///
 /// # Errors
 ///
 /// Returns an error if the OS function fails.
-/// Returns an error if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
 ///
 /// [`max_num_waitables`]: fn.max_num_waitables.html
-pub fn wait_for_all(waitables: &[&dyn WaitableExt], d: Duration) -> Result<WaitableResult, ()> {
-    match wait_for_waitables_impl(waitables, d, true) {
-        Ok(WaitablesResult::AllSignaled) => Ok(WaitableResult::Signaled),
-        Ok(WaitablesResult::Timeout) => Ok(WaitableResult::Timeout),
-        _ => Err(()),
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+/// [`wait_for_all_or_partial`]: fn.wait_for_all_or_partial.html
+pub fn wait_for_all(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter().copied(), duration_to_timeout_ms(d), true)
+        .map(all_result)
+}
+
+/// Same as [`wait_for_all`], but blocks until the `deadline` passes rather than for a duration.
+///
+/// If the `deadline` has already passed, checks whether all waitables are signaled without blocking.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub fn wait_for_all_until(
+    waitables: &[&dyn WaitableExt],
+    deadline: Instant,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_all(
+        waitables,
+        deadline.saturating_duration_since(Instant::now()),
+    )
+}
+
+/// Blocks the thread until all waitables are signaled or the duration `d` expires.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// Same as [`wait_for_all`], but on timeout checks each waitable without blocking
+/// and returns the subset of them which is signaled, so the caller may make progress on whatever is ready.
+///
+/// NOTE - unlike the all-wait itself, the check on timeout is not atomic, and consumes the signal of
+/// every signaled auto event and decrements the counter of every signaled semaphore in `waitables`,
+/// just like waiting on each of them individually would.
+/// The caller is then responsible for handling the returned waitables.
+/// Manual events are unaffected.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_all_or_partial(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<PartialResult, WaitError> {
+    if wait_for_all(waitables, d)? != WaitableResult::Timeout {
+        return Ok(PartialResult::AllSignaled);
     }
+
+    let mut signaled = Vec::new();
+
+    for (idx, waitable) in waitables.iter().enumerate() {
+        let res = waitable.wait(Duration::from_secs(0))?;
+
+        if res == WaitableResult::Signaled {
+            signaled.push(idx);
+        }
+    }
+
+    Ok(PartialResult::Partial(signaled))
+}
+
+/// Same as [`wait_for_all`], but also returns, for each of the `waitables`, whether it was signaled -
+/// e.g. to log which waitables a thread stuck waiting for all of them is still waiting on.
+///
+/// The waitables are only checked individually on timeout, via [`wait_for_all_or_partial`] -
+/// see it for the caveats of the check. If all waitables were signaled, returns `Signaled` and all `true`.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`wait_for_all_or_partial`]: fn.wait_for_all_or_partial.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_all_detailed(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<(WaitableResult, Vec<bool>), WaitError> {
+    let res = wait_for_all_or_partial(waitables, d)?;
+    let mask = res.signaled_mask(waitables.len());
+
+    match res {
+        PartialResult::AllSignaled => Ok((WaitableResult::Signaled, mask)),
+        PartialResult::Partial(_) => Ok((WaitableResult::Timeout, mask)),
+    }
+}
+
+/// Returns `true` if all waitables are signaled, without blocking.
+/// Equivalent to calling [`wait_for_all`] with a zero duration.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// NOTE - like [`wait_for_all`], if all waitables are signaled, this consumes the signal of
+/// every auto event and decrements the counter of every semaphore in `waitables`.
+/// Meant to be used with manual events, which are unaffected.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn all_signaled(waitables: &[&dyn WaitableExt]) -> Result<bool, WaitError> {
+    wait_for_all(waitables, Duration::from_secs(0)).map(|res| res != WaitableResult::Timeout)
 }
 
 /// Blocks the thread until at least one of the waitables are signaled or the duration `d` expires.
@@ -51,39 +369,470 @@ pub fn wait_for_all(waitables: &[&dyn WaitableExt], d: Duration) -> Result<Waita
 /// # Errors
 ///
 /// Returns an error if the OS function fails.
-/// Returns an error if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
 ///
 /// [`max_num_waitables`]: fn.max_num_waitables.html
-pub fn wait_for_one(waitables: &[&dyn WaitableExt], d: Duration) -> Result<WaitablesResult, ()> {
-    wait_for_waitables_impl(waitables, d, false)
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_one(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter().copied(), duration_to_timeout_ms(d), false)
 }
 
-fn wait_for_waitables_impl(
+/// Same as [`wait_for_one`], but blocks until the `deadline` passes rather than for a duration.
+///
+/// If the `deadline` has already passed, checks whether any waitable is signaled without blocking.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_until(
     waitables: &[&dyn WaitableExt],
+    deadline: Instant,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_one(
+        waitables,
+        deadline.saturating_duration_since(Instant::now()),
+    )
+}
+
+/// Blocks the thread until all waitables are signaled, without a timeout.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// Same as [`wait_for_all`], but waits forever (i.e. passes `INFINITE` to the OS function) -
+/// never returns `Timeout`.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+pub fn wait_for_all_infinite(waitables: &[&dyn WaitableExt]) -> Result<WaitableResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter().copied(), INFINITE, true).map(all_result)
+}
+
+/// Blocks the thread until at least one of the waitables is signaled, without a timeout.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// Same as [`wait_for_one`], but waits forever (i.e. passes `INFINITE` to the OS function) -
+/// never returns `Timeout`.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+pub fn wait_for_one_infinite(waitables: &[&dyn WaitableExt]) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter().copied(), INFINITE, false)
+}
+
+/// Same as [`wait_for_all`], but takes a slice of owned, boxed waitables,
+/// e.g. a dynamic collection of waitables of different types.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub fn wait_for_all_boxed(
+    waitables: &[Box<dyn WaitableExt>],
     d: Duration,
-    wait_for_all: bool,
-) -> Result<WaitablesResult, ()> {
+) -> Result<WaitableResult, WaitError> {
+    wait_for_waitables_impl(
+        waitables.iter().map(|w| &**w),
+        duration_to_timeout_ms(d),
+        true,
+    )
+    .map(all_result)
+}
+
+/// Same as [`wait_for_one`], but takes a slice of owned, boxed waitables,
+/// e.g. a dynamic collection of waitables of different types.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_boxed(
+    waitables: &[Box<dyn WaitableExt>],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_impl(
+        waitables.iter().map(|w| &**w),
+        duration_to_timeout_ms(d),
+        false,
+    )
+}
+
+/// Same as [`wait_for_one`], but takes any iterable of references to waitables of the same type,
+/// e.g. `&Vec<Event>`, without building an intermediate slice of trait objects.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the number of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_one_iter<'w, I, W>(waitables: I, d: Duration) -> Result<WaitablesResult, WaitError>
+where
+    I: IntoIterator<Item = &'w W>,
+    W: WaitableExt + ?Sized + 'w,
+{
+    let mut waitables = waitables.into_iter();
+
+    let mut handles = [MaybeUninit::uninit(); MAXIMUM_WAIT_OBJECTS as usize];
+    let handles = fill_handles(waitables.by_ref(), &mut handles);
+
+    // Validate before doing any work - counts the waitables which did not fit, if any.
+    check_num_waitables(handles.len() + waitables.count())?;
+
+    wait_for_handles_unchecked(handles, d, false)
+}
+
+/// Same as [`wait_for_one`], but copies the waitables' OS handles to the caller-provided buffer `buf`
+/// instead of a temporary one on the stack - e.g. reused across the iterations of a hot wait loop.
+///
+/// `buf` - must be at least as long as `waitables`; its contents on return are unspecified.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+/// Returns [`WaitError::BufferTooSmall`] if `buf` is shorter than `waitables`, without waiting.
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`WaitError::BufferTooSmall`]: enum.WaitError.html#variant.BufferTooSmall
+pub fn wait_for_one_with_buf(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+    buf: &mut [*mut ()],
+) -> Result<WaitablesResult, WaitError> {
     let num_waitables = waitables.len();
 
-    if num_waitables > max_num_waitables() {
-        return Err(());
+    check_num_waitables(num_waitables)?;
+
+    if buf.len() < num_waitables {
+        return Err(WaitError::BufferTooSmall {
+            len: buf.len(),
+            required: num_waitables,
+        });
     }
 
-    let mut handles = [0 as HANDLE; MAXIMUM_WAIT_OBJECTS as usize];
+    let buf = &mut buf[..num_waitables];
 
-    for (idx, waitable) in waitables.iter().enumerate() {
-        handles[idx] = waitable.handle() as HANDLE;
+    for (handle, waitable) in buf.iter_mut().zip(waitables) {
+        *handle = waitable.handle();
+    }
+
+    // Raw pointers of the same size - `HANDLE` is `*mut c_void`.
+    let handles = unsafe { slice::from_raw_parts(buf.as_ptr() as *const HANDLE, num_waitables) };
+
+    wait_for_handles_unchecked(handles, d, false)
+}
+
+/// Same as [`wait_for_one`], but returns both the index and the reference to the signaled waitable,
+/// or `None` if the duration `d` expired before any waitable was signaled.
+///
+/// An abandoned mutex is returned the same as a signaled waitable.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_ref<'a>(
+    waitables: &'a [&'a dyn WaitableExt],
+    d: Duration,
+) -> Result<Option<(usize, &'a dyn WaitableExt)>, WaitError> {
+    match wait_for_one(waitables, d)? {
+        WaitablesResult::OneSignaled(idx) | WaitablesResult::Abandoned(idx) => {
+            Ok(Some((idx, waitables[idx])))
+        }
+        // Not an alertable wait.
+        _ => Ok(None),
     }
+}
+
+/// Same as [`wait_for_all`], but the wait is alertable - returns `IoCompletion` if an APC or I/O completion routine
+/// was queued to the calling thread (e.g. via [`QueueUserAPC`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-queueuserapc))
+/// and run before all waitables were signaled or the duration `d` expired.
+/// See [`WaitForMultipleObjectsEx`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitformultipleobjectsex) on MSDN.
+///
+/// Queueing an APC to a thread blocked in an alertable wait is the idiomatic way to cancel the wait.
+///
+/// # Errors
+///
+/// Same as [`wait_for_all`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+pub fn wait_for_all_alertable(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_waitables_alertable(waitables, d, true).map(all_result)
+}
+
+/// Same as [`wait_for_one`], but the wait is alertable - returns `IoCompletion` if an APC or I/O completion routine
+/// was queued to the calling thread (e.g. via [`QueueUserAPC`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-queueuserapc))
+/// and run before any waitable was signaled or the duration `d` expired.
+/// See [`WaitForMultipleObjectsEx`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-waitformultipleobjectsex) on MSDN.
+///
+/// # Errors
+///
+/// Same as [`wait_for_one`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+pub fn wait_for_one_alertable(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_alertable(waitables, d, false)
+}
+
+/// Atomically signals the `to_signal` waitable and blocks the thread until the `to_wait` waitable is signaled
+/// or the duration `d` expires.
+/// See [`SignalObjectAndWait`](https://docs.microsoft.com/en-us/windows/win32/api/synchapi/nf-synchapi-signalobjectandwait) on MSDN.
+///
+/// `to_signal` - must be an event (which is set) or a semaphore (whose counter is incremented by `1`).
+/// `alertable` - if `true`, returns `IoCompletion` if an APC or I/O completion routine was queued to the thread
+/// and run before `to_wait` was signaled or the duration `d` expired, like [`wait_for_one_alertable`].
+///
+/// Unlike signaling and then waiting in two separate calls, no other thread may observe the signal
+/// before the calling thread begins waiting - the correct primitive for thread hand-off / ping-pong patterns.
+///
+/// NOTE - signals the OS object directly, bypassing the signaler's own bookkeeping
+/// (e.g. [`Event::set_n`] releases or the `diagnostics` feature's last signaler).
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails - e.g. if `to_signal` is not an event or a semaphore,
+/// or if signaling it would exceed the semaphore's maximum count.
+///
+/// [`wait_for_one_alertable`]: fn.wait_for_one_alertable.html
+/// [`Event::set_n`]: struct.Event.html#method.set_n
+pub fn signal_and_wait(
+    to_signal: &dyn WaitableExt,
+    to_wait: &dyn WaitableExt,
+    d: Duration,
+    alertable: bool,
+) -> Result<WaitableResult, WaitError> {
+    let ms = duration_to_timeout_ms(d);
+    let alertable = if alertable { TRUE } else { FALSE };
+
+    let result = unsafe {
+        SignalObjectAndWait(
+            to_signal.handle() as HANDLE,
+            to_wait.handle() as HANDLE,
+            ms,
+            alertable,
+        )
+    };
+
+    match result {
+        WAIT_OBJECT_0 => Ok(WaitableResult::Signaled),
+        WAIT_ABANDONED_0 => Ok(WaitableResult::Abandoned),
+        WAIT_TIMEOUT => Ok(WaitableResult::Timeout),
+        WAIT_IO_COMPLETION => Ok(WaitableResult::IoCompletion),
+        _ => Err(WaitError::FailedToWait(io::Error::last_os_error())),
+    }
+}
+
+fn wait_for_waitables_alertable(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+    wait_for_all: bool,
+) -> Result<WaitablesResult, WaitError> {
+    check_num_waitables(waitables.len())?;
+
+    let mut handles = [MaybeUninit::uninit(); MAXIMUM_WAIT_OBJECTS as usize];
+    let handles = fill_handles(waitables.iter().copied(), &mut handles);
+
+    wait_for_handles_ex(handles, duration_to_timeout_ms(d), wait_for_all, true)
+}
+
+/// Same as [`wait_for_one`], but also returns the wall time the thread spent blocked
+/// before one of the waitables was signaled or the duration `d` expired.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_one_profiled(
+    waitables: &[&dyn WaitableExt],
+    d: Duration,
+) -> Result<(WaitablesResult, Duration), WaitError> {
+    let start = Instant::now();
+    let res = wait_for_one(waitables, d)?;
+
+    Ok((res, start.elapsed()))
+}
+
+/// Blocks the thread until all waitables are signaled or the duration `d` expires.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// Same as [`wait_for_all`], but for a slice of waitables of the same concrete type
+/// (e.g. `&[Event]`), which avoids dynamic dispatch when collecting the waitables' OS handles.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_all`]: fn.wait_for_all.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_all_typed<W: WaitableExt>(
+    waitables: &[W],
+    d: Duration,
+) -> Result<WaitableResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter(), duration_to_timeout_ms(d), true).map(all_result)
+}
+
+/// Blocks the thread until at least one of the waitables are signaled or the duration `d` expires.
+/// Maximum number of waitables is platform-dependant and returned by [`max_num_waitables`].
+///
+/// Same as [`wait_for_one`], but for a slice of waitables of the same concrete type
+/// (e.g. `&[Event]`), which avoids dynamic dispatch when collecting the waitables' OS handles.
+///
+/// # Errors
+///
+/// Returns an error if the OS function fails.
+/// Returns [`WaitError::TooManyWaitables`] if the len of `waitables` exceeds the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_one`]: fn.wait_for_one.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+/// [`WaitError::TooManyWaitables`]: enum.WaitError.html#variant.TooManyWaitables
+pub fn wait_for_one_typed<W: WaitableExt>(
+    waitables: &[W],
+    d: Duration,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_waitables_impl(waitables.iter(), duration_to_timeout_ms(d), false)
+}
+
+/// Converts the result of an all-wait to `WaitableResult`.
+pub(crate) fn all_result(result: WaitablesResult) -> WaitableResult {
+    match result {
+        WaitablesResult::AllSignaled => WaitableResult::Signaled,
+        WaitablesResult::Abandoned(_) => WaitableResult::Abandoned,
+        WaitablesResult::IoCompletion => WaitableResult::IoCompletion,
+        _ => WaitableResult::Timeout,
+    }
+}
+
+fn wait_for_waitables_impl<'w, W, I>(
+    waitables: I,
+    ms: u32,
+    wait_for_all: bool,
+) -> Result<WaitablesResult, WaitError>
+where
+    W: WaitableExt + ?Sized + 'w,
+    I: ExactSizeIterator<Item = &'w W>,
+{
+    // Validate before doing any work.
+    check_num_waitables(waitables.len())?;
+
+    let mut handles = [MaybeUninit::uninit(); MAXIMUM_WAIT_OBJECTS as usize];
+    let handles = fill_handles(waitables, &mut handles);
+
+    wait_for_handles_ex(handles, ms, wait_for_all, false)
+}
+
+/// Copies the OS handles of the `waitables` to the start of the `handles` buffer, up to its len,
+/// and returns the filled part of it.
+///
+/// Does not zero the rest of the buffer - only the handles actually waited on are written.
+fn fill_handles<'h, 'w, W, I>(waitables: I, handles: &'h mut [MaybeUninit<HANDLE>]) -> &'h [HANDLE]
+where
+    W: WaitableExt + ?Sized + 'w,
+    I: IntoIterator<Item = &'w W>,
+{
+    let mut num_waitables = 0;
+
+    // Stops once the `handles` are full, without consuming the next waitable.
+    for (handle, waitable) in handles.iter_mut().zip(waitables) {
+        handle.write(waitable.handle() as HANDLE);
+        num_waitables += 1;
+    }
+
+    // The first `num_waitables` handles were initialized above.
+    unsafe { slice::from_raw_parts(handles.as_ptr() as *const HANDLE, num_waitables) }
+}
+
+pub(crate) fn wait_for_handles_impl(
+    handles: &[HANDLE],
+    d: Duration,
+    wait_for_all: bool,
+) -> Result<WaitablesResult, WaitError> {
+    check_num_waitables(handles.len())?;
 
-    let ms = d.as_millis();
-    debug_assert!(ms <= std::u32::MAX as u128);
-    let ms = ms as u32;
+    wait_for_handles_unchecked(handles, d, wait_for_all)
+}
+
+pub(crate) fn check_num_waitables(given: usize) -> Result<(), WaitError> {
+    let max = max_num_waitables();
 
-    let handles = handles.as_ptr();
+    if given > max {
+        Err(WaitError::TooManyWaitables { given, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Same as [`wait_for_handles_impl`], but the caller guarantees the number of `handles`
+/// does not exceed the value returned by [`max_num_waitables`].
+///
+/// [`wait_for_handles_impl`]: fn.wait_for_handles_impl.html
+/// [`max_num_waitables`]: fn.max_num_waitables.html
+pub(crate) fn wait_for_handles_unchecked(
+    handles: &[HANDLE],
+    d: Duration,
+    wait_for_all: bool,
+) -> Result<WaitablesResult, WaitError> {
+    wait_for_handles_ex(handles, duration_to_timeout_ms(d), wait_for_all, false)
+}
+
+/// Same as [`wait_for_handles_unchecked`], but waits for `ms` milliseconds (or `INFINITE`),
+/// and the wait is alertable if `alertable` is `true` -
+/// returns `IoCompletion` if the wait was ended by an APC / I/O completion routine.
+///
+/// [`wait_for_handles_unchecked`]: fn.wait_for_handles_unchecked.html
+fn wait_for_handles_ex(
+    handles: &[HANDLE],
+    ms: u32,
+    wait_for_all: bool,
+    alertable: bool,
+) -> Result<WaitablesResult, WaitError> {
+    let num_waitables = handles.len();
 
     let result = unsafe {
-        let wait_for_all = if wait_for_all { 1 } else { 0 };
-        WaitForMultipleObjectsEx(num_waitables as u32, handles, wait_for_all, ms, 0)
+        let alertable = if alertable { TRUE } else { FALSE };
+
+        // Fast path for a single waitable (e.g. in generic code) - skips the multi-object wait setup.
+        // Returns the same values as waiting on one of one object, so the results below are mapped the same.
+        if let [handle] = handles {
+            WaitForSingleObjectEx(*handle, ms, alertable)
+        } else {
+            let wait_for_all = if wait_for_all { 1 } else { 0 };
+            WaitForMultipleObjectsEx(
+                num_waitables as u32,
+                handles.as_ptr(),
+                wait_for_all,
+                ms,
+                alertable,
+            )
+        }
     };
 
     if result < (WAIT_OBJECT_0 + num_waitables as u32) {
@@ -92,9 +841,238 @@ fn wait_for_waitables_impl(
         } else {
             Ok(WaitablesResult::OneSignaled(result as usize))
         }
+    } else if (WAIT_ABANDONED_0..WAIT_ABANDONED_0 + num_waitables as u32).contains(&result) {
+        Ok(WaitablesResult::Abandoned(
+            (result - WAIT_ABANDONED_0) as usize,
+        ))
     } else if result == WAIT_TIMEOUT {
         Ok(WaitablesResult::Timeout)
+    } else if result == WAIT_IO_COMPLETION {
+        Ok(WaitablesResult::IoCompletion)
+    } else {
+        Err(WaitError::FailedToWait(io::Error::last_os_error()))
+    }
+}
+
+/// Waits on the OS object `handle` for `ms` milliseconds (or `INFINITE`) using the `strategy`.
+/// Returns the result of `WaitForSingleObject` - `WAIT_TIMEOUT` if the wait timed out.
+pub(crate) fn wait_for_handle_with_strategy(
+    handle: HANDLE,
+    ms: u32,
+    strategy: WaitStrategy,
+) -> u32 {
+    let poll = || unsafe { WaitForSingleObject(handle, 0) };
+
+    match strategy {
+        WaitStrategy::Blocking => {}
+        // Zero timeout - a single check is enough.
+        _ if ms == 0 => {}
+        WaitStrategy::SpinThenBlock { spin } => {
+            for _ in 0..spin {
+                let result = poll();
+
+                if result != WAIT_TIMEOUT {
+                    return result;
+                }
+
+                std::hint::spin_loop();
+            }
+        }
+        WaitStrategy::Yielding => {
+            let deadline = if ms == INFINITE {
+                None
+            } else {
+                Some(Deadline::new(
+                    Duration::from_millis(ms as u64),
+                    &RealTimeSource,
+                ))
+            };
+
+            loop {
+                let result = poll();
+
+                if result != WAIT_TIMEOUT {
+                    return result;
+                }
+
+                if let Some(deadline) = deadline.as_ref() {
+                    if deadline.remaining(&RealTimeSource) == Duration::from_secs(0) {
+                        return WAIT_TIMEOUT;
+                    }
+                }
+
+                thread::yield_now();
+            }
+        }
+    }
+
+    unsafe { WaitForSingleObject(handle, ms) }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of OS handles closed via [`close_handle`] by the current thread.
+    pub(crate) static NUM_CLOSED_HANDLES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+
+    /// OS error codes the next attempts to create an OS object via [`create_handle`] by the current thread fail with,
+    /// without creating the object.
+    pub(crate) static INJECTED_CREATE_ERRORS: std::cell::RefCell<std::collections::VecDeque<u32>> =
+        const { std::cell::RefCell::new(std::collections::VecDeque::new()) };
+}
+
+/// Default number of times the creation of an OS object is retried after a transient failure.
+/// See [`create_handle`].
+pub(crate) const DEFAULT_CREATE_RETRIES: u32 = 2;
+
+/// Creates an OS object via `create`, which returns a null handle on failure.
+///
+/// If the creation failed with a transient error - i.e. the system was temporarily out of memory / resources,
+/// e.g. while many objects are created in a burst - retries it up to `retries` times after a short sleep.
+/// Other errors are returned immediately.
+///
+/// On success also returns `true` if `create` opened an existing named object (i.e. set `ERROR_ALREADY_EXISTS`).
+pub(crate) fn create_handle(
+    retries: u32,
+    mut create: impl FnMut() -> HANDLE,
+) -> io::Result<(HANDLE, bool)> {
+    const RETRY_DELAY: Duration = Duration::from_millis(1);
+
+    let is_transient = |err: &io::Error| {
+        matches!(
+            err.raw_os_error().map(|err| err as u32),
+            Some(
+                ERROR_NOT_ENOUGH_MEMORY
+                    | ERROR_OUTOFMEMORY
+                    | ERROR_NO_SYSTEM_RESOURCES
+                    | ERROR_COMMITMENT_LIMIT
+            )
+        )
+    };
+
+    let mut num_retries = 0;
+
+    loop {
+        match try_create_handle(&mut create) {
+            Err(err) if num_retries < retries && is_transient(&err) => {
+                num_retries += 1;
+                thread::sleep(RETRY_DELAY);
+            }
+            res => return res,
+        }
+    }
+}
+
+fn try_create_handle(create: &mut impl FnMut() -> HANDLE) -> io::Result<(HANDLE, bool)> {
+    #[cfg(test)]
+    if let Some(err) = INJECTED_CREATE_ERRORS.with(|errs| errs.borrow_mut().pop_front()) {
+        return Err(io::Error::from_raw_os_error(err as i32));
+    }
+
+    let handle = create();
+
+    // Must be read before any other OS call.
+    let err = io::Error::last_os_error();
+
+    if handle.is_null() {
+        Err(err)
+    } else {
+        Ok((
+            handle,
+            err.raw_os_error() == Some(ERROR_ALREADY_EXISTS as i32),
+        ))
+    }
+}
+
+/// Closes the owned OS `handle`.
+/// Counted per thread in tests to detect handles being closed more than once.
+///
+/// Fails with `ERROR_INVALID_HANDLE` without calling `CloseHandle` if the `handle` is null or `INVALID_HANDLE_VALUE`
+/// (e.g. passed to `from_raw_handle`) - the latter is the pseudo handle of the current process.
+pub(crate) fn close_handle(handle: HANDLE) -> io::Result<()> {
+    if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+        return Err(io::Error::from_raw_os_error(ERROR_INVALID_HANDLE as i32));
+    }
+
+    #[cfg(test)]
+    NUM_CLOSED_HANDLES.with(|num| num.set(num.get() + 1));
+
+    if unsafe { CloseHandle(handle) } == FALSE {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the security attributes for creating an OS object with the default security descriptor,
+/// whose handle is inherited by child processes if `inheritable` is `true`.
+pub(crate) fn security_attributes(inheritable: bool) -> SECURITY_ATTRIBUTES {
+    SECURITY_ATTRIBUTES {
+        nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: std::ptr::null_mut(),
+        bInheritHandle: if inheritable { TRUE } else { FALSE },
+    }
+}
+
+/// Kernel object namespace of a named OS object.
+/// See [`Kernel object namespaces`](https://docs.microsoft.com/en-us/windows/win32/termserv/kernel-object-namespaces) on MSDN.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Namespace {
+    /// Shared by all sessions, e.g. by services and user applications.
+    Global,
+    /// Private to the current session.
+    Local,
+}
+
+impl Namespace {
+    fn prefix(self) -> &'static str {
+        match self {
+            Namespace::Global => "Global\\",
+            Namespace::Local => "Local\\",
+        }
+    }
+}
+
+/// Returns the `name` prefixed with the `namespace` prefix, e.g. `Global\name`.
+/// A `name` which already has the same prefix is returned as is.
+///
+/// Returns `None` if the `name` already has a different namespace prefix (compared case-insensitively).
+pub(crate) fn namespaced_name(namespace: Namespace, name: &str) -> Option<String> {
+    const PREFIXES: [&str; 3] = ["Global\\", "Local\\", "Session\\"];
+
+    let has_prefix = |prefix: &str| {
+        name.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    };
+
+    match PREFIXES.iter().copied().find(|&prefix| has_prefix(prefix)) {
+        Some(prefix) if prefix == namespace.prefix() => Some(name.to_owned()),
+        Some(_) => None,
+        None => Some(format!("{}{}", namespace.prefix(), name)),
+    }
+}
+
+/// Duplicates the OS `handle` within the current process, with the same access rights.
+/// The returned handle must be closed independently of the original.
+pub(crate) fn duplicate_handle(handle: HANDLE) -> io::Result<HANDLE> {
+    let mut duplicate = std::ptr::null_mut();
+
+    let result = unsafe {
+        let process = GetCurrentProcess();
+
+        DuplicateHandle(
+            process,
+            handle,
+            process,
+            &mut duplicate,
+            0,
+            FALSE,
+            DUPLICATE_SAME_ACCESS,
+        )
+    };
+
+    if result == FALSE {
+        Err(io::Error::last_os_error())
     } else {
-        Err(())
+        Ok(duplicate)
     }
 }